@@ -33,6 +33,10 @@ pub struct SqlServerConnection {
     pub client: &'static mut tiberius::Client<TcpStream>,
 }
 
+/// The connection of a mock datasource, that never reaches any database
+#[cfg(feature = "mock")]
+pub struct MockConnection;
+
 /// The Canyon database connection handler. When the client's program
 /// starts, Canyon gets the information about the desired datasources,
 /// process them and generates a pool of 1 to 1 database connection for
@@ -42,6 +46,8 @@ pub enum DatabaseConnection {
     Postgres(PostgreSqlConnection),
     #[cfg(feature = "mssql")]
     SqlServer(SqlServerConnection),
+    #[cfg(feature = "mock")]
+    Mock(MockConnection),
 }
 
 unsafe impl Send for DatabaseConnection {}
@@ -51,6 +57,11 @@ impl DatabaseConnection {
     pub async fn new(
         datasource: &DatasourceConfig,
     ) -> Result<DatabaseConnection, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
+        #[cfg(feature = "mock")]
+        if datasource.is_mock() {
+            return Ok(DatabaseConnection::Mock(MockConnection));
+        }
+
         match datasource.get_db_type() {
             #[cfg(feature = "postgres")]
             DatabaseType::PostgreSql => {
@@ -64,6 +75,10 @@ impl DatabaseConnection {
                     crate::datasources::Auth::SqlServer(_) => {
                         panic!("Found SqlServer auth configuration for a PostgreSQL datasource")
                     }
+                    #[cfg(feature = "mock")]
+                    crate::datasources::Auth::Mock(_) => {
+                        panic!("Found mock auth configuration for a PostgreSQL datasource")
+                    }
                 };
                 let (new_client, new_connection) = tokio_postgres::connect(
                     &format!(
@@ -109,6 +124,10 @@ impl DatabaseConnection {
                         }
                         crate::datasources::SqlServerAuth::Integrated => AuthMethod::Integrated,
                     },
+                    #[cfg(feature = "mock")]
+                    crate::datasources::Auth::Mock(_) => {
+                        panic!("Found mock auth configuration for a SqlServer database")
+                    }
                 });
 
                 // on production, it is not a good idea to do this. We should upgrade
@@ -143,7 +162,7 @@ impl DatabaseConnection {
     pub fn postgres_connection(&self) -> &PostgreSqlConnection {
         match self {
             DatabaseConnection::Postgres(conn) => conn,
-            #[cfg(all(feature = "postgres", feature = "mssql"))]
+            #[cfg(any(feature = "mssql", feature = "mock"))]
             _ => panic!(),
         }
     }
@@ -152,7 +171,7 @@ impl DatabaseConnection {
     pub fn sqlserver_connection(&mut self) -> &mut SqlServerConnection {
         match self {
             DatabaseConnection::SqlServer(conn) => conn,
-            #[cfg(all(feature = "postgres", feature = "mssql"))]
+            #[cfg(any(feature = "postgres", feature = "mock"))]
             _ => panic!(),
         }
     }
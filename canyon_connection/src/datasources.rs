@@ -10,7 +10,7 @@ fn load_ds_config_from_array() {
         const CONFIG_FILE_MOCK_ALT_PG: &str = r#"
         [canyon_sql]
         datasources = [
-            {name = 'PostgresDS', auth = { postgresql = { basic = { username = "postgres", password = "postgres" } } }, properties.host = 'localhost', properties.db_name = 'triforce', properties.migrations='enabled' },
+            {name = 'PostgresDS', auth = { postgresql = { basic = { username = "postgres", password = "postgres" } } }, properties.host = 'localhost', properties.db_name = 'triforce', properties.migrations='enabled', properties.slow_query_threshold = 250, properties.log_query_parameters = true },
        ]
         "#;
         let config: CanyonSqlConfig = toml::from_str(CONFIG_FILE_MOCK_ALT_PG)
@@ -31,6 +31,8 @@ fn load_ds_config_from_array() {
         assert_eq!(ds_0.properties.port, None);
         assert_eq!(ds_0.properties.db_name, "triforce");
         assert_eq!(ds_0.properties.migrations, Some(Migrations::Enabled));
+        assert_eq!(ds_0.properties.slow_query_threshold, Some(250));
+        assert_eq!(ds_0.properties.log_query_parameters, Some(true));
     }
 
     #[cfg(feature = "mssql")]
@@ -61,9 +63,35 @@ fn load_ds_config_from_array() {
         assert_eq!(ds_1.properties.port, Some(3340));
         assert_eq!(ds_1.properties.db_name, "triforce2");
         assert_eq!(ds_1.properties.migrations, Some(Migrations::Disabled));
+        assert_eq!(ds_1.properties.slow_query_threshold, None);
+        assert_eq!(ds_1.properties.log_query_parameters, None);
 
         assert_eq!(ds_2.auth, Auth::SqlServer(SqlServerAuth::Integrated));
     }
+
+    #[cfg(all(feature = "mock", feature = "postgres"))]
+    {
+        const CONFIG_FILE_MOCK_ALT_MOCK: &str = r#"
+        [canyon_sql]
+        datasources = [
+            {name = 'MockDS', auth = { mock = { dialect = 'postgresql' } }, properties.host = 'localhost', properties.db_name = 'mock' },
+        ]
+        "#;
+        let config: CanyonSqlConfig = toml::from_str(CONFIG_FILE_MOCK_ALT_MOCK)
+            .expect("A failure happened retrieving the [canyon_sql] section");
+
+        let ds_3 = &config.canyon_sql.datasources[0];
+
+        assert_eq!(ds_3.name, "MockDS");
+        assert!(ds_3.is_mock());
+        assert_eq!(ds_3.get_db_type(), DatabaseType::PostgreSql);
+        assert_eq!(
+            ds_3.auth,
+            Auth::Mock(MockAuth {
+                dialect: DatabaseType::PostgreSql
+            })
+        );
+    }
 }
 ///
 #[derive(Deserialize, Debug, Clone)]
@@ -90,6 +118,20 @@ impl DatasourceConfig {
             Auth::Postgres(_) => DatabaseType::PostgreSql,
             #[cfg(feature = "mssql")]
             Auth::SqlServer(_) => DatabaseType::SqlServer,
+            #[cfg(feature = "mock")]
+            Auth::Mock(MockAuth { dialect }) => dialect,
+        }
+    }
+
+    /// Returns true if the datasource is a mock one, which doesn't connect to any database
+    pub fn is_mock(&self) -> bool {
+        #[cfg(feature = "mock")]
+        {
+            matches!(self.auth, Auth::Mock(_))
+        }
+        #[cfg(not(feature = "mock"))]
+        {
+            false
         }
     }
 }
@@ -102,6 +144,9 @@ pub enum Auth {
     #[serde(alias = "SqlServer", alias = "sqlserver", alias = "mssql")]
     #[cfg(feature = "mssql")]
     SqlServer(SqlServerAuth),
+    #[serde(alias = "Mock", alias = "mock")]
+    #[cfg(feature = "mock")]
+    Mock(MockAuth),
 }
 
 #[derive(Deserialize, Debug, Clone, PartialEq)]
@@ -120,12 +165,28 @@ pub enum SqlServerAuth {
     Integrated,
 }
 
+/// The configuration of a mock datasource, that answers the statements with the rows
+/// of the expectations registered by the tests instead of connecting to a database.
+/// The statements are generated in the SQL `dialect` of the given database type
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[cfg(feature = "mock")]
+pub struct MockAuth {
+    pub dialect: DatabaseType,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct DatasourceProperties {
     pub host: String,
     pub port: Option<u16>,
     pub db_name: String,
     pub migrations: Option<Migrations>,
+    /// The elapsed time, in milliseconds, from which the queries against the datasource
+    /// are logged as slow, with a `WARN` level event
+    pub slow_query_threshold: Option<u64>,
+    /// Whether the values of the query parameters are recorded on the `tracing` spans
+    /// of the queries, besides their count. Disabled by default, since they may hold
+    /// sensitive data
+    pub log_query_parameters: Option<bool>,
 }
 
 /// Represents the enabled or disabled migrations for a whole datasource
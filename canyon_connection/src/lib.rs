@@ -104,3 +104,23 @@ pub fn get_database_connection<'a>(
             )
     }
 }
+
+/// Retrieves the configuration of the datasource that matches the provided name,
+/// or the first declared one in the configuration file if the name is empty,
+/// following the same convention as [`get_database_connection`]
+pub fn get_database_config(datasource_name: &str) -> &'static DatasourceConfig {
+    let datasources: &'static Vec<DatasourceConfig> = &DATASOURCES;
+
+    if datasource_name.is_empty() {
+        datasources.first().expect(
+            "We didn't found any valid datasource configuration. Check your `canyon.toml` file",
+        )
+    } else {
+        datasources
+            .iter()
+            .find(|ds| ds.name == datasource_name)
+            .unwrap_or_else(||
+                panic!("Canyon couldn't find a datasource in the configuration with the argument provided: {datasource_name}")
+            )
+    }
+}
@@ -10,7 +10,10 @@ use canyon_connection::tokio_postgres::{self, types::ToSql};
 use canyon_connection::tiberius::{self, ColumnData, IntoSql};
 
 use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+#[cfg(feature = "rust_decimal")]
+use rust_decimal::Decimal;
 use std::any::Any;
+use uuid::Uuid;
 
 /// Created for retrieve the field's name of a field of a struct, giving
 /// the Canyon's autogenerated enum with the variants that maps this
@@ -104,8 +107,8 @@ impl Row for tiberius::Row {
 /// types present in the dependent crates
 // #[derive(Copy, Clone)]
 pub struct Column<'a> {
-    name: &'a str,
-    type_: ColumnType,
+    pub(crate) name: &'a str,
+    pub(crate) type_: ColumnType,
 }
 impl<'a> Column<'a> {
     pub fn name(&self) -> &'_ str {
@@ -261,8 +264,54 @@ pub trait QueryParameter<'a>: std::fmt::Debug + Sync + Send {
     fn as_postgres_param(&self) -> &(dyn ToSql + Sync);
     #[cfg(feature = "mssql")]
     fn as_sqlserver_param(&self) -> ColumnData<'_>;
+    /// Whether the value can be bound as a parameter of a SQL Server query.
+    /// The launcher rejects the statement with an error when it can't
+    #[cfg(feature = "mssql")]
+    fn supported_on_sqlserver(&self) -> bool {
+        true
+    }
+}
+
+/// The value(s) that identifies a row by its primary key, accepted by the
+/// `find_by_pk`, `find_by_pks` and `exists_by_pk` operations.
+///
+/// It's implemented for every [`QueryParameter<'a>`], for the entities with a single
+/// primary key, and for the tuples of up to four of them, for the entities with a
+/// composite primary key. The values of a tuple must follow the order in which
+/// the `#[primary_key]` fields are declared on the entity.
+///
+/// ```ignore
+/// let order_line = OrderLine::find_by_pk(&(1, 3)).await?;
+/// ```
+pub trait PrimaryKeyValues<'a>: Sync + Send {
+    /// The values of every column of the primary key, as query parameters
+    fn pk_values(&self) -> Vec<&dyn QueryParameter<'a>>;
 }
 
+impl<'a, T: QueryParameter<'a>> PrimaryKeyValues<'a> for T {
+    fn pk_values(&self) -> Vec<&dyn QueryParameter<'a>> {
+        vec![self]
+    }
+}
+
+macro_rules! impl_primary_key_values_for_tuples {
+    ($(($($name:ident: $idx:tt),+)),+) => {
+        $(
+            impl<'a, $($name: QueryParameter<'a>),+> PrimaryKeyValues<'a> for ($($name,)+) {
+                fn pk_values(&self) -> Vec<&dyn QueryParameter<'a>> {
+                    vec![$(&self.$idx),+]
+                }
+            }
+        )+
+    };
+}
+
+impl_primary_key_values_for_tuples!(
+    (A: 0, B: 1),
+    (A: 0, B: 1, C: 2),
+    (A: 0, B: 1, C: 2, D: 3)
+);
+
 /// The implementation of the [`canyon_connection::tiberius`] [`IntoSql`] for the
 /// query parameters.
 ///
@@ -272,8 +321,8 @@ pub trait QueryParameter<'a>: std::fmt::Debug + Sync + Send {
 /// that is not dependent of the specific type of the argument that holds
 /// the query parameters of the database connectors
 #[cfg(feature = "mssql")]
-impl<'a> IntoSql<'a> for &'a dyn QueryParameter<'a> {
-    fn into_sql(self) -> ColumnData<'a> {
+impl<'s, 'a: 's> IntoSql<'s> for &'s dyn QueryParameter<'a> {
+    fn into_sql(self) -> ColumnData<'s> {
         self.as_sqlserver_param()
     }
 }
@@ -662,3 +711,150 @@ impl<'a> QueryParameter<'a> for Option<DateTime<Utc>> {
         self.into_sql()
     }
 }
+impl<'a> QueryParameter<'a> for Uuid {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        self.into_sql()
+    }
+}
+impl<'a> QueryParameter<'a> for Option<Uuid> {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        self.into_sql()
+    }
+}
+impl<'a> QueryParameter<'a> for Vec<u8> {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        ColumnData::Binary(Some(std::borrow::Cow::Borrowed(self)))
+    }
+}
+impl<'a> QueryParameter<'a> for &'_ [u8] {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        ColumnData::Binary(Some(std::borrow::Cow::Borrowed(*self)))
+    }
+}
+impl<'a> QueryParameter<'a> for Option<Vec<u8>> {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        ColumnData::Binary(self.as_deref().map(std::borrow::Cow::Borrowed))
+    }
+}
+impl<'a> QueryParameter<'a> for Option<&'_ [u8]> {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        ColumnData::Binary(self.map(std::borrow::Cow::Borrowed))
+    }
+}
+// Forwards to the wrapped parameter, so the values already erased as trait objects
+// (like the ones of the `FieldValueIdentifier` enums) can be mixed with other ones
+impl<'a, 'b> QueryParameter<'a> for &dyn QueryParameter<'b> {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        (**self).as_postgres_param()
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        (**self).as_sqlserver_param()
+    }
+}
+/// Marker for the primitive types that can be stored as the elements of the
+/// PostgreSQL array columns, mapped to the `Vec<T>` and `Option<Vec<T>>` fields.
+///
+/// The `Vec<u8>` fields are mapped to the binary columns instead, so `u8` isn't
+/// an array element
+#[cfg(feature = "postgres")]
+pub trait ArrayElement: ToSql + std::fmt::Debug + Sync + Send {}
+
+#[cfg(feature = "postgres")]
+impl ArrayElement for bool {}
+#[cfg(feature = "postgres")]
+impl ArrayElement for i16 {}
+#[cfg(feature = "postgres")]
+impl ArrayElement for i32 {}
+#[cfg(feature = "postgres")]
+impl ArrayElement for i64 {}
+#[cfg(feature = "postgres")]
+impl ArrayElement for f32 {}
+#[cfg(feature = "postgres")]
+impl ArrayElement for f64 {}
+#[cfg(feature = "postgres")]
+impl ArrayElement for String {}
+#[cfg(feature = "postgres")]
+impl ArrayElement for Uuid {}
+
+#[cfg(feature = "postgres")]
+impl<'a, T: ArrayElement> QueryParameter<'a> for Vec<T> {
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        ColumnData::String(None)
+    }
+    #[cfg(feature = "mssql")]
+    fn supported_on_sqlserver(&self) -> bool {
+        false
+    }
+}
+#[cfg(feature = "postgres")]
+impl<'a, T: ArrayElement> QueryParameter<'a> for Option<Vec<T>> {
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        ColumnData::String(None)
+    }
+    #[cfg(feature = "mssql")]
+    fn supported_on_sqlserver(&self) -> bool {
+        false
+    }
+}
+#[cfg(feature = "rust_decimal")]
+impl<'a> QueryParameter<'a> for Decimal {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        tiberius::ToSql::to_sql(self)
+    }
+}
+#[cfg(feature = "rust_decimal")]
+impl<'a> QueryParameter<'a> for Option<Decimal> {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        tiberius::ToSql::to_sql(self)
+    }
+}
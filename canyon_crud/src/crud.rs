@@ -1,15 +1,20 @@
 use std::fmt::Display;
+use std::time::Instant;
 
 use async_trait::async_trait;
 use canyon_connection::canyon_database_connector::DatabaseConnection;
-use canyon_connection::{get_database_connection, CACHED_DATABASE_CONN};
+use canyon_connection::{get_database_config, get_database_connection, CACHED_DATABASE_CONN};
+use tracing::Instrument;
 
-use crate::bounds::QueryParameter;
+use crate::bounds::{FieldValueIdentifier, PrimaryKeyValues, QueryParameter};
+use crate::hooks::EntityHooks;
+use crate::interceptors::{interceptors_for, InterceptedQuery, QueryOutcome};
 use crate::mapper::RowMapper;
 use crate::query_elements::query_builder::{
     DeleteQueryBuilder, SelectQueryBuilder, UpdateQueryBuilder,
 };
-use crate::rows::CanyonRows;
+use crate::rows::{CanyonRows, DynamicRow};
+use crate::validation::Validate;
 
 /// This traits defines and implements a query against a database given
 /// an statement `stmt` and the params to pass the to the client.
@@ -30,30 +35,224 @@ pub trait Transaction<T> {
         S: AsRef<str> + Display + Sync + Send + 'a,
         Z: AsRef<[&'a dyn QueryParameter<'a>]> + Sync + Send + 'a,
     {
+        launch_query(stmt, params, datasource_name, false).await
+    }
+
+    /// Same as [`Transaction::query`], for the statements generated by the CRUD operations
+    /// that retrieves the primary keys of the affected rows with a trailing `RETURNING`
+    /// clause, which is rewritten into an `OUTPUT` clause on SQL Server
+    #[doc(hidden)]
+    async fn query_returning<'a, S, Z>(
+        stmt: S,
+        params: Z,
+        datasource_name: &'a str,
+    ) -> Result<CanyonRows<T>, Box<dyn std::error::Error + Sync + Send + 'static>>
+    where
+        S: AsRef<str> + Display + Sync + Send + 'a,
+        Z: AsRef<[&'a dyn QueryParameter<'a>]> + Sync + Send + 'a,
+    {
+        launch_query(stmt, params, datasource_name, true).await
+    }
+}
+
+/// Launches the statement against the database of the selected datasource (or the
+/// defaulted one), wrapping the resultant rows.
+///
+/// Every query runs inside a `query` span of the `canyon_sql` target, with the
+/// datasource, the statement and the number of parameters (and their values, when
+/// the `log_query_parameters` property of the datasource is enabled), where the
+/// rows returned by the database (so none for the writes without a `RETURNING` or
+/// `OUTPUT` clause) and the elapsed time are recorded once it finishes. The queries
+/// that exceed the `slow_query_threshold` of the datasource are logged as `WARN`
+/// events, and the failed ones as `ERROR` events.
+///
+/// The registered [`QueryInterceptor`]s are called before the statement is sent, being
+/// able to rewrite or reject it, and after it's executed with the outcome of the execution.
+///
+/// The `returning` flag marks the statements generated by the CRUD operations, whose
+/// trailing `RETURNING` clause (if any) is rewritten into an `OUTPUT` one on SQL Server
+async fn launch_query<'a, T, S, Z>(
+    stmt: S,
+    params: Z,
+    datasource_name: &'a str,
+    #[cfg_attr(not(feature = "mssql"), allow(unused_variables))] returning: bool,
+) -> Result<CanyonRows<T>, Box<dyn std::error::Error + Sync + Send + 'static>>
+where
+    S: AsRef<str> + Display + Sync + Send + 'a,
+    Z: AsRef<[&'a dyn QueryParameter<'a>]> + Sync + Send + 'a,
+{
+    let config = get_database_config(datasource_name);
+    let interceptors = interceptors_for(&config.name);
+    let mut query = InterceptedQuery::new(&config.name, stmt.to_string(), params.as_ref());
+    for interceptor in interceptors.iter() {
+        if let Err(error) = interceptor.before_query(&mut query).await {
+            tracing::warn!(
+                target: "canyon_sql",
+                datasource = %config.name,
+                statement = %query.statement(),
+                error = %error,
+                "Query rejected by an interceptor"
+            );
+            return Err(error);
+        }
+    }
+    let query_params = query.params();
+
+    let span = tracing::info_span!(
+        target: "canyon_sql",
+        "query",
+        datasource = %config.name,
+        statement = %query.statement(),
+        params = query_params.len(),
+        values = tracing::field::Empty,
+        returned_rows = tracing::field::Empty,
+        elapsed_ms = tracing::field::Empty,
+    );
+    if config.properties.log_query_parameters.unwrap_or(false) {
+        span.record("values", tracing::field::debug(&query_params));
+    }
+
+    let (result, elapsed) = async {
         let mut guarded_cache = CACHED_DATABASE_CONN.lock().await;
         let database_conn = get_database_connection(datasource_name, &mut guarded_cache);
 
-        match *database_conn {
-            #[cfg(feature = "postgres")]
-            DatabaseConnection::Postgres(_) => {
-                postgres_query_launcher::launch::<T>(
-                    database_conn,
-                    stmt.to_string(),
-                    params.as_ref(),
-                )
-                .await
-            }
-            #[cfg(feature = "mssql")]
-            DatabaseConnection::SqlServer(_) => {
-                sqlserver_query_launcher::launch::<T, Z>(
-                    database_conn,
-                    &mut stmt.to_string(),
-                    params,
-                )
-                .await
+        let start = Instant::now();
+        let result: Result<CanyonRows<T>, Box<dyn std::error::Error + Sync + Send>> =
+            match *database_conn {
+                #[cfg(feature = "postgres")]
+                DatabaseConnection::Postgres(_) => {
+                    postgres_query_launcher::launch::<T>(
+                        database_conn,
+                        query.statement(),
+                        &query_params,
+                    )
+                    .instrument(
+                        tracing::debug_span!(target: "canyon_sql", "postgres_query_launcher"),
+                    )
+                    .await
+                }
+                #[cfg(feature = "mssql")]
+                DatabaseConnection::SqlServer(_) => {
+                    sqlserver_query_launcher::launch::<T>(
+                        database_conn,
+                        &mut query.statement().to_string(),
+                        &query_params,
+                        returning,
+                    )
+                    .instrument(
+                        tracing::debug_span!(target: "canyon_sql", "sqlserver_query_launcher"),
+                    )
+                    .await
+                }
+                #[cfg(feature = "mock")]
+                DatabaseConnection::Mock(_) => {
+                    crate::mock::launch::<T>(&config.name, query.statement(), &query_params)
+                }
+            };
+        (result, start.elapsed())
+    }
+    .instrument(span.clone())
+    .await;
+
+    let elapsed_ms = elapsed.as_millis();
+    span.record("elapsed_ms", elapsed_ms);
+    span.in_scope(|| match &result {
+        Ok(rows) => {
+            span.record("returned_rows", rows.len());
+            match config.properties.slow_query_threshold {
+                Some(threshold_ms) if elapsed_ms >= u128::from(threshold_ms) => tracing::warn!(
+                    target: "canyon_sql",
+                    datasource = %config.name,
+                    statement = %query.statement(),
+                    elapsed_ms,
+                    threshold_ms,
+                    "Slow query"
+                ),
+                _ => tracing::debug!(
+                    target: "canyon_sql",
+                    returned_rows = rows.len(),
+                    elapsed_ms,
+                    "Query executed"
+                ),
             }
         }
+        Err(error) => tracing::error!(
+            target: "canyon_sql",
+            datasource = %config.name,
+            statement = %query.statement(),
+            elapsed_ms,
+            error = %error,
+            "Query failed"
+        ),
+    });
+
+    let outcome = QueryOutcome::new(
+        result
+            .as_ref()
+            .map(|rows| rows.len())
+            .map_err(|error| error.as_ref()),
+        elapsed,
+    );
+    for interceptor in interceptors.iter() {
+        interceptor.after_query(&query, &outcome).await;
     }
+
+    result
+}
+
+/// Performs a query against the targeted database by the selected or the defaulted
+/// datasource, mapping the resultant rows into instances of `R`.
+///
+/// `R` just needs to derive `CanyonMapper`, so the results of any `SELECT` (like
+/// the ones of reports, with joins or aggregates) can be mapped into a plain struct
+/// that isn't an entity, whose fields are matched against the columns by name.
+///
+/// ```ignore
+/// #[derive(CanyonMapper)]
+/// struct LeagueTournaments {
+///     league: String,
+///     tournaments: i64,
+/// }
+///
+/// let report = query_as::<LeagueTournaments, _, _>(
+///     "SELECT l.name AS league, COUNT(*) AS tournaments FROM league l \
+///     JOIN tournament t ON t.league = l.id GROUP BY l.name",
+///     [],
+///     "",
+/// ).await?;
+/// ```
+pub async fn query_as<'a, R, S, Z>(
+    stmt: S,
+    params: Z,
+    datasource_name: &'a str,
+) -> Result<Vec<R>, Box<dyn std::error::Error + Sync + Send + 'static>>
+where
+    R: RowMapper<R>,
+    S: AsRef<str> + Display + Sync + Send + 'a,
+    Z: AsRef<[&'a dyn QueryParameter<'a>]> + Sync + Send + 'a,
+{
+    Ok(
+        launch_query::<R, S, Z>(stmt, params, datasource_name, false)
+            .await?
+            .into_results::<R>()?,
+    )
+}
+
+/// Performs a query against the targeted database by the selected or the defaulted
+/// datasource, returning the resultant rows as [`DynamicRow`]s, whose columns are
+/// retrieved by name without mapping them into a type
+pub async fn query_rows<'a, S, Z>(
+    stmt: S,
+    params: Z,
+    datasource_name: &'a str,
+) -> Result<Vec<DynamicRow>, Box<dyn std::error::Error + Sync + Send + 'static>>
+where
+    S: AsRef<str> + Display + Sync + Send + 'a,
+    Z: AsRef<[&'a dyn QueryParameter<'a>]> + Sync + Send + 'a,
+{
+    launch_query::<DynamicRow, S, Z>(stmt, params, datasource_name, false)
+        .await?
+        .into_dynamic_rows()
 }
 
 /// *CrudOperations* it's the core part of Canyon-SQL.
@@ -71,8 +270,11 @@ pub trait Transaction<T> {
 /// See it's definition and docs to see the implementations.
 /// Also, you can find the written macro-code that performs the auto-mapping
 /// in the *canyon_sql_root::canyon_macros* crates, on the root of this project.
+///
+/// The generated operations call the lifecycle hooks defined in [`EntityHooks`], and
+/// the write operations check the rules defined in [`Validate`] before reaching the database.
 #[async_trait]
-pub trait CrudOperations<T>: Transaction<T>
+pub trait CrudOperations<T>: Transaction<T> + EntityHooks + Validate
 where
     T: CrudOperations<T> + RowMapper<T>,
 {
@@ -97,14 +299,34 @@ where
     ) -> Result<i64, Box<(dyn std::error::Error + Send + Sync + 'static)>>;
 
     async fn find_by_pk<'a>(
-        value: &'a dyn QueryParameter<'a>,
+        value: &'a dyn PrimaryKeyValues<'a>,
     ) -> Result<Option<T>, Box<(dyn std::error::Error + Send + Sync + 'static)>>;
 
     async fn find_by_pk_datasource<'a>(
-        value: &'a dyn QueryParameter<'a>,
+        value: &'a dyn PrimaryKeyValues<'a>,
         datasource_name: &'a str,
     ) -> Result<Option<T>, Box<(dyn std::error::Error + Send + Sync + 'static)>>;
 
+    async fn find_by_pks<'a>(
+        values: &'a [&'a dyn PrimaryKeyValues<'a>],
+        preserve_order: bool,
+    ) -> Result<Vec<T>, Box<dyn std::error::Error + Send + Sync + 'static>>;
+
+    async fn find_by_pks_datasource<'a>(
+        values: &'a [&'a dyn PrimaryKeyValues<'a>],
+        preserve_order: bool,
+        datasource_name: &'a str,
+    ) -> Result<Vec<T>, Box<dyn std::error::Error + Send + Sync + 'static>>;
+
+    async fn exists_by_pk<'a>(
+        value: &'a dyn PrimaryKeyValues<'a>,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync + 'static>>;
+
+    async fn exists_by_pk_datasource<'a>(
+        value: &'a dyn PrimaryKeyValues<'a>,
+        datasource_name: &'a str,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync + 'static>>;
+
     async fn insert<'a>(&mut self) -> Result<(), Box<dyn std::error::Error + Sync + Send>>;
 
     async fn insert_datasource<'a>(
@@ -121,13 +343,28 @@ where
         datasource_name: &'a str,
     ) -> Result<(), Box<(dyn std::error::Error + Send + Sync + 'static)>>;
 
-    async fn update(&self) -> Result<(), Box<dyn std::error::Error + Sync + Send>>;
+    async fn update(&mut self) -> Result<(), Box<dyn std::error::Error + Sync + Send>>;
 
     async fn update_datasource<'a>(
-        &self,
+        &mut self,
         datasource_name: &'a str,
     ) -> Result<(), Box<dyn std::error::Error + Sync + Send>>;
 
+    async fn patch<'a, Z>(
+        pk: &'a dyn PrimaryKeyValues<'a>,
+        changes: Vec<Z>,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send>>
+    where
+        Z: FieldValueIdentifier<'a, T> + Send + 'a;
+
+    async fn patch_datasource<'a, Z>(
+        pk: &'a dyn PrimaryKeyValues<'a>,
+        changes: Vec<Z>,
+        datasource_name: &'a str,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send>>
+    where
+        Z: FieldValueIdentifier<'a, T> + Send + 'a;
+
     fn update_query<'a>() -> UpdateQueryBuilder<'a, T>;
 
     fn update_query_datasource(datasource_name: &str) -> UpdateQueryBuilder<'_, T>;
@@ -152,7 +389,7 @@ mod postgres_query_launcher {
 
     pub async fn launch<'a, T>(
         db_conn: &DatabaseConnection,
-        stmt: String,
+        stmt: &str,
         params: &'a [&'_ dyn QueryParameter<'_>],
     ) -> Result<CanyonRows<T>, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
         let mut m_params = Vec::new();
@@ -163,7 +400,7 @@ mod postgres_query_launcher {
         let r = db_conn
             .postgres_connection()
             .client
-            .query(&stmt, m_params.as_slice())
+            .query(stmt, m_params.as_slice())
             .await?;
 
         Ok(CanyonRows::Postgres(r))
@@ -178,33 +415,34 @@ mod sqlserver_query_launcher {
         canyon_connection::{canyon_database_connector::DatabaseConnection, tiberius::Query},
     };
 
-    pub async fn launch<'a, T, Z>(
+    pub async fn launch<'a, T>(
         db_conn: &mut DatabaseConnection,
         stmt: &mut String,
-        params: Z,
-    ) -> Result<CanyonRows<T>, Box<(dyn std::error::Error + Send + Sync + 'static)>>
-    where
-        Z: AsRef<[&'a dyn QueryParameter<'a>]> + Sync + Send + 'a,
-    {
-        // Re-generate de insert statement to adequate it to the SQL SERVER syntax to retrieve the PK value(s) after insert
-        if stmt.contains("RETURNING") {
-            let c = stmt.clone();
-            let temp = c.split_once("RETURNING").unwrap();
-            let temp2 = temp.0.split_once("VALUES").unwrap();
-
-            *stmt = format!(
-                "{} OUTPUT inserted.{} VALUES {}",
-                temp2.0.trim(),
-                temp.1.trim(),
-                temp2.1.trim()
-            );
+        params: &[&dyn QueryParameter<'a>],
+        returning: bool,
+    ) -> Result<CanyonRows<T>, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
+        if let Some(param) = params.iter().find(|param| !param.supported_on_sqlserver()) {
+            return Err(format!(
+                "The parameter {param:?} can't be bound on SQL Server. The array columns are only supported on PostgreSQL"
+            )
+            .into());
         }
 
-        let mut mssql_query = Query::new(stmt.to_owned().replace('$', "@P"));
-        params
-            .as_ref()
-            .iter()
-            .for_each(|param| mssql_query.bind(*param));
+        // Re-generate the statements of the CRUD operations to adequate them to the SQL SERVER
+        // syntax to retrieve the PK value(s) after insert, or the PK of the affected rows after
+        // an update or a delete
+        if returning && stmt.contains(" RETURNING ") {
+            *stmt = returning_into_output(stmt)?;
+        }
+
+        let mssql_stmt = stmt.replace('$', "@P");
+        tracing::trace!(
+            target: "canyon_sql",
+            statement = %mssql_stmt,
+            "Statement adapted to the SQL Server syntax"
+        );
+        let mut mssql_query = Query::new(mssql_stmt);
+        params.iter().for_each(|param| mssql_query.bind(*param));
 
         let _results = mssql_query
             .query(db_conn.sqlserver_connection().client)
@@ -216,4 +454,81 @@ mod sqlserver_query_launcher {
             _results.into_iter().flatten().collect(),
         ))
     }
+
+    /// Rewrites the trailing `RETURNING` clause of a statement generated by the CRUD
+    /// operations into an `OUTPUT` clause, placed before the `VALUES` of an insert, or
+    /// before the `WHERE` of an update or a delete
+    fn returning_into_output(
+        stmt: &str,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let unexpected = || format!("Unexpected statement with a RETURNING clause: {stmt}");
+        let (statement, returned_columns) =
+            stmt.rsplit_once(" RETURNING ").ok_or_else(unexpected)?;
+
+        let output = if statement.starts_with("DELETE ") {
+            "deleted"
+        } else {
+            "inserted"
+        };
+        // Every returned column (more than one for composite primary keys) must be prefixed
+        let output_columns = returned_columns
+            .split(',')
+            .map(|column| format!("{output}.{}", column.trim()))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let keyword = if statement.starts_with("INSERT ") {
+            "VALUES"
+        } else {
+            "WHERE"
+        };
+        let (head, tail) = statement
+            .split_once(&format!(" {keyword} "))
+            .ok_or_else(unexpected)?;
+
+        Ok(format!(
+            "{} OUTPUT {output_columns} {keyword} {}",
+            head.trim(),
+            tail.trim()
+        ))
+    }
+
+    #[cfg(test)]
+    mod returning_into_output_tests {
+        use super::returning_into_output;
+
+        /// The `RETURNING` clauses of the inserts, updates and deletes are moved
+        /// before their `VALUES` or `WHERE` clauses as `OUTPUT` clauses
+        #[test]
+        fn test_returning_into_output() {
+            assert_eq!(
+                returning_into_output(
+                    "INSERT INTO league (\"slug\", \"returning_at\") VALUES ($1, $2) RETURNING \"id\""
+                )
+                .unwrap(),
+                "INSERT INTO league (\"slug\", \"returning_at\") OUTPUT inserted.\"id\" VALUES ($1, $2)"
+            );
+            assert_eq!(
+                returning_into_output(
+                    "UPDATE order_line SET \"amount\" = $1 WHERE \"order\" = $2 AND \"line\" = $3 \
+                    RETURNING \"order\", \"line\""
+                )
+                .unwrap(),
+                "UPDATE order_line SET \"amount\" = $1 OUTPUT inserted.\"order\", inserted.\"line\" \
+                WHERE \"order\" = $2 AND \"line\" = $3"
+            );
+            assert_eq!(
+                returning_into_output("DELETE FROM league WHERE \"id\" = $1 RETURNING \"id\"")
+                    .unwrap(),
+                "DELETE FROM league OUTPUT deleted.\"id\" WHERE \"id\" = $1"
+            );
+        }
+
+        /// The statements that can't be rewritten return an error instead of panicking
+        #[test]
+        fn test_returning_into_output_errors() {
+            assert!(returning_into_output("DELETE FROM league RETURNING \"id\"").is_err());
+            assert!(returning_into_output("SELECT * FROM league").is_err());
+        }
+    }
 }
@@ -0,0 +1,118 @@
+//! The value objects embedded in the entities, like addresses or amounts of money,
+//! whose fields are flattened into prefixed columns of the table of the entity.
+//!
+//! The code generated by the `CanyonEmbeddable` derive implements the
+//! [`CanyonEmbeddable`] trait, and the CRUD operations of the entities with
+//! `#[embedded]` fields use the functions of this module to build their statements.
+use std::sync::Mutex;
+
+#[cfg(feature = "mssql")]
+use canyon_connection::tiberius;
+#[cfg(feature = "postgres")]
+use canyon_connection::tokio_postgres;
+
+use crate::bounds::QueryParameter;
+use crate::errors::MappingError;
+
+/// A value object whose fields are stored in the columns of the entities where it's
+/// embedded, prefixed with the `prefix` of the `#[embedded(prefix = "...")]` field
+/// (the name of the field followed by an underscore by default).
+///
+/// It's implemented by `#[derive(CanyonEmbeddable)]`:
+///
+/// ```ignore
+/// #[derive(Debug, Clone, PartialEq, CanyonEmbeddable)]
+/// pub struct Address {
+///     street: String,
+///     city: String,
+/// }
+///
+/// #[derive(CanyonCrud, CanyonMapper, Fields)]
+/// #[canyon_entity]
+/// pub struct Customer {
+///     #[primary_key]
+///     id: i32,
+///     #[embedded(prefix = "billing_")]
+///     billing: Address, // Stored in the `billing_street` and `billing_city` columns
+/// }
+/// ```
+pub trait CanyonEmbeddable: Sized + Send + Sync {
+    /// The columns of the fields, without the prefix of the embedded field
+    const COLUMNS: &'static [&'static str];
+
+    /// The values of the fields sent as query parameters, in the order of [`Self::COLUMNS`]
+    fn parameters(&self) -> Vec<&dyn QueryParameter<'_>>;
+
+    /// Builds the value object from the prefixed columns of a PostgreSQL row
+    #[cfg(feature = "postgres")]
+    fn from_postgres_row(row: &tokio_postgres::Row, prefix: &str) -> Result<Self, MappingError>;
+
+    /// Builds the value object from the prefixed columns of a SQL Server row
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver_row(row: &tiberius::Row, prefix: &str) -> Result<Self, MappingError>;
+
+    /// The columns of the fields, with the given prefix
+    fn prefixed_columns(prefix: &str) -> Vec<String> {
+        Self::COLUMNS
+            .iter()
+            .map(|column| format!("{prefix}{column}"))
+            .collect()
+    }
+}
+
+/// The prefixed columns already requested, that lives as long as the program
+static PREFIXED_COLUMNS: Mutex<Vec<(&str, &str, &str)>> = Mutex::new(Vec::new());
+
+/// The column of an embedded value object with the given prefix, as it's returned by
+/// the `Fields` enums of the entities. Every pair of prefix and column is allocated once
+pub fn prefixed_column(prefix: &'static str, column: &'static str) -> &'static str {
+    let mut prefixed_columns = PREFIXED_COLUMNS
+        .lock()
+        .expect("Error acquiring Mutex guard on the prefixed columns");
+    if let Some((_, _, prefixed)) = prefixed_columns
+        .iter()
+        .find(|(p, c, _)| *p == prefix && *c == column)
+    {
+        return prefixed;
+    }
+
+    let prefixed: &'static str = Box::leak(format!("{prefix}{column}").into_boxed_str());
+    prefixed_columns.push((prefix, column, prefixed));
+    prefixed
+}
+
+/// Appends the given columns, quote escaped, to the comma separated `list` of columns
+/// of a statement
+pub fn append_columns(list: &str, columns: &[String]) -> String {
+    join_list(list, columns.iter().map(|column| format!("\"{column}\"")))
+}
+
+/// Appends `count` consecutive placeholders, starting at `$first_placeholder`, to the
+/// comma separated `list` of placeholders of an `INSERT` statement
+pub fn append_placeholders(list: &str, first_placeholder: usize, count: usize) -> String {
+    join_list(
+        list,
+        (first_placeholder..first_placeholder + count).map(|idx| format!("${idx}")),
+    )
+}
+
+/// Appends the assignments of the given columns to consecutive placeholders, starting
+/// at `$first_placeholder`, to the comma separated `list` of the `SET` clause of an
+/// `UPDATE` statement
+pub fn append_assignments(list: &str, columns: &[String], first_placeholder: usize) -> String {
+    join_list(
+        list,
+        columns
+            .iter()
+            .enumerate()
+            .map(|(idx, column)| format!("\"{column}\" = ${}", first_placeholder + idx)),
+    )
+}
+
+fn join_list(list: &str, items: impl Iterator<Item = String>) -> String {
+    std::iter::once(list.to_string())
+        .filter(|list| !list.is_empty())
+        .chain(items)
+        .collect::<Vec<String>>()
+        .join(", ")
+}
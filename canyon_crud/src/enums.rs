@@ -0,0 +1,172 @@
+//! The runtime support for the fieldless enums that derives `CanyonEnum`, so they
+//! can be used as the type of the fields of the entities and as query parameters.
+//!
+//! The code generated by the derive implements the [`CanyonEnum`] trait, and the
+//! [`CanyonType`](crate::types::CanyonType) one over the types of this module.
+use std::error::Error;
+use std::fmt::Debug;
+
+#[cfg(feature = "postgres")]
+use bytes::BytesMut;
+#[cfg(feature = "mssql")]
+use canyon_connection::tiberius::{self, ColumnData};
+#[cfg(feature = "postgres")]
+use canyon_connection::tokio_postgres::{
+    self,
+    types::{FromSql, IsNull, Kind, ToSql, Type},
+};
+
+use crate::bounds::QueryParameter;
+use crate::types::ColumnValue;
+
+/// How the variants of a [`CanyonEnum`] are stored in the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnumRepresentation {
+    /// The name of the variant (after the rename rules), as text
+    Text,
+    /// The discriminant of the variant, as an integer
+    Integer,
+    /// The name of the variant as a label of the native PostgreSQL enum type with
+    /// the given name. They're stored as text on SQL Server
+    Native(&'static str),
+}
+
+/// Declares the conversions between the variants of a fieldless enum and the
+/// values stored in the database. It's implemented by `#[derive(CanyonEnum)]`
+pub trait CanyonEnum: Sized + Debug + Sync + Send {
+    /// How the variants are stored in the database
+    const REPRESENTATION: EnumRepresentation;
+
+    /// The name of the variant stored in the database
+    fn label(&self) -> &'static str;
+
+    /// The discriminant of the variant
+    fn discriminant(&self) -> i32;
+
+    /// The variant with the given name, if exists
+    fn from_label(label: &str) -> Option<Self>;
+
+    /// The variant with the given discriminant, if exists
+    fn from_discriminant(discriminant: i32) -> Option<Self>;
+}
+
+/// The variant with the given name, or an error if there's no one
+pub fn variant_from_label<T: CanyonEnum>(
+    label: EnumLabel,
+) -> Result<T, Box<dyn Error + Send + Sync>> {
+    T::from_label(&label.0).ok_or_else(|| {
+        format!(
+            "No variant of `{}` named \"{}\"",
+            std::any::type_name::<T>(),
+            label.0
+        )
+        .into()
+    })
+}
+
+/// The variant with the given discriminant, or an error if there's no one
+pub fn variant_from_discriminant<T: CanyonEnum>(
+    discriminant: i32,
+) -> Result<T, Box<dyn Error + Send + Sync>> {
+    T::from_discriminant(discriminant).ok_or_else(|| {
+        format!(
+            "No variant of `{}` with the discriminant {discriminant}",
+            std::any::type_name::<T>()
+        )
+        .into()
+    })
+}
+
+/// The name of a variant read from the database, from a text column or from a
+/// column of a native PostgreSQL enum type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumLabel(pub String);
+
+#[cfg(feature = "postgres")]
+impl<'a> FromSql<'a> for EnumLabel {
+    // The labels of the enum types share the binary format of the text
+    fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(Self(std::str::from_utf8(raw)?.to_owned()))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&str as FromSql>::accepts(ty) || matches!(ty.kind(), Kind::Enum(_))
+    }
+}
+
+impl ColumnValue for EnumLabel {
+    #[cfg(feature = "postgres")]
+    fn from_postgres(
+        row: &tokio_postgres::Row,
+        column: &str,
+    ) -> Result<Option<Self>, Box<dyn Error + Send + Sync>> {
+        Ok(row.try_get::<&str, Option<Self>>(column)?)
+    }
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver(
+        row: &tiberius::Row,
+        column: &str,
+    ) -> Result<Option<Self>, Box<dyn Error + Send + Sync>> {
+        Ok(row
+            .try_get::<&str, &str>(column)?
+            .map(|label| Self(label.to_owned())))
+    }
+}
+
+/// The name of a variant sent as a query parameter for a column of the native
+/// PostgreSQL enum type with the given name. It's sent as text to SQL Server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NativeEnumLabel {
+    pub type_name: &'static str,
+    pub label: &'static str,
+}
+
+#[cfg(feature = "postgres")]
+impl ToSql for NativeEnumLabel {
+    fn to_sql(
+        &self,
+        _ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        out.extend_from_slice(self.label.as_bytes());
+        Ok(IsNull::No)
+    }
+
+    // The type name is only known by the values, so the check is made at `to_sql_checked`
+    fn accepts(ty: &Type) -> bool {
+        matches!(ty.kind(), Kind::Enum(_))
+    }
+
+    fn to_sql_checked(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        if ty.name() != self.type_name {
+            return Err(format!(
+                "Cannot send the \"{}\" label of the `{}` enum type as a value of the `{}` type",
+                self.label,
+                self.type_name,
+                ty.name()
+            )
+            .into());
+        }
+        if !<Self as ToSql>::accepts(ty) {
+            return Err(Box::new(tokio_postgres::types::WrongType::new::<Self>(
+                ty.clone(),
+            )));
+        }
+        self.to_sql(ty, out)
+    }
+}
+
+impl<'a> QueryParameter<'a> for NativeEnumLabel {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        ColumnData::String(Some(std::borrow::Cow::Borrowed(self.label)))
+    }
+}
@@ -0,0 +1,167 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+
+#[cfg(feature = "mssql")]
+use canyon_connection::tiberius;
+#[cfg(feature = "postgres")]
+use canyon_connection::tokio_postgres;
+
+/// Error returned by the `update` and `delete` operations of the entities that
+/// have a field annotated with `#[version]`, when the row that matches the
+/// instance was modified (or deleted) by someone else after the instance was loaded.
+///
+/// It's returned boxed as the rest of the errors of the CRUD operations, so it can
+/// be identified by downcasting it:
+///
+/// ```ignore
+/// if let Err(error) = player.update().await {
+///     if error.downcast_ref::<StaleEntityError>().is_some() {
+///         // reload the entity and decide what to do with the conflict
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleEntityError {
+    entity: &'static str,
+    primary_key: String,
+}
+
+impl StaleEntityError {
+    pub fn new(entity: &'static str, primary_key: String) -> Self {
+        Self {
+            entity,
+            primary_key,
+        }
+    }
+
+    /// The name of the type of the stale entity
+    pub fn entity(&self) -> &str {
+        self.entity
+    }
+
+    /// The value of the primary key of the stale entity, as a string
+    pub fn primary_key(&self) -> &str {
+        &self.primary_key
+    }
+}
+
+impl Display for StaleEntityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The `{}` entity with primary key `{}` was modified or deleted \
+            since it was loaded",
+            self.entity, self.primary_key
+        )
+    }
+}
+
+impl Error for StaleEntityError {}
+
+/// Error returned when a row can't be mapped into a type that derives `CanyonMapper`,
+/// because one of its columns doesn't exist, contains an unexpected `NULL` or its
+/// SQL type can't be converted into the Rust type of the field.
+///
+/// It's returned by [`crate::rows::CanyonRows::into_results`], and propagated boxed
+/// by the CRUD operations, so it can be identified by downcasting it:
+///
+/// ```ignore
+/// if let Err(error) = League::find_all().await {
+///     if let Some(mapping_error) = error.downcast_ref::<MappingError>() {
+///         eprintln!("Unexpected value on the `{}` column", mapping_error.column());
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct MappingError {
+    column: String,
+    rust_type: &'static str,
+    sql_type: Option<String>,
+    source: Box<dyn Error + Send + Sync>,
+}
+
+impl MappingError {
+    pub fn new(
+        column: &str,
+        rust_type: &'static str,
+        sql_type: Option<String>,
+        source: Box<dyn Error + Send + Sync>,
+    ) -> Self {
+        Self {
+            column: column.to_owned(),
+            rust_type,
+            sql_type,
+            source,
+        }
+    }
+
+    /// Builds the error for the `column` of a PostgreSQL row that can't be retrieved
+    /// as a value of type `T`
+    #[cfg(feature = "postgres")]
+    pub fn from_postgres_row<T>(
+        row: &tokio_postgres::Row,
+        column: &str,
+        source: Box<dyn Error + Send + Sync>,
+    ) -> Self {
+        let sql_type = row
+            .columns()
+            .iter()
+            .find(|c| c.name() == column)
+            .map(|c| c.type_().name().to_owned());
+        Self::new(column, std::any::type_name::<T>(), sql_type, source)
+    }
+
+    /// Builds the error for the `column` of a SQL Server row that can't be retrieved
+    /// as a value of type `T`
+    #[cfg(feature = "mssql")]
+    pub fn from_sqlserver_row<T>(
+        row: &tiberius::Row,
+        column: &str,
+        source: Box<dyn Error + Send + Sync>,
+    ) -> Self {
+        let sql_type = row
+            .columns()
+            .iter()
+            .find(|c| c.name() == column)
+            .map(|c| format!("{:?}", c.column_type()));
+        Self::new(column, std::any::type_name::<T>(), sql_type, source)
+    }
+
+    /// The name of the column that can't be mapped
+    pub fn column(&self) -> &str {
+        &self.column
+    }
+
+    /// The Rust type of the field where the value of the column was expected
+    pub fn rust_type(&self) -> &str {
+        self.rust_type
+    }
+
+    /// The SQL type of the column, or `None` if the row doesn't contain the column
+    pub fn sql_type(&self) -> Option<&str> {
+        self.sql_type.as_deref()
+    }
+}
+
+impl Display for MappingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.sql_type {
+            Some(sql_type) => write!(
+                f,
+                "Failed to map the `{}` column of type `{}` into a `{}`: {}",
+                self.column, sql_type, self.rust_type, self.source
+            ),
+            None => write!(
+                f,
+                "Failed to map the `{}` column into a `{}`, because it isn't present on the row",
+                self.column, self.rust_type
+            ),
+        }
+    }
+}
+
+impl Error for MappingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+
+/// Extension points for the lifecycle of the entities, called by the CRUD operations
+/// generated with the `CanyonCrud` derive macro.
+///
+/// Every method has a default implementation that does nothing, so the entities
+/// that doesn't need any hook get an empty implementation generated by the macro.
+/// To provide your own hooks, annotate the entity with `#[entity_hooks]` and
+/// implement the trait, overriding just the methods that you need:
+///
+/// ```ignore
+/// #[derive(CanyonCrud, CanyonMapper)]
+/// #[entity_hooks]
+/// #[canyon_entity]
+/// pub struct User {
+///     #[primary_key]
+///     id: i32,
+///     email: String,
+/// }
+///
+/// #[async_trait]
+/// impl EntityHooks for User {
+///     async fn before_insert(&mut self) -> Result<(), Box<dyn Error + Sync + Send>> {
+///         self.email = self.email.to_lowercase();
+///         Ok(())
+///     }
+/// }
+/// ```
+///
+/// Returning an error from any of the `before_*` hooks aborts the operation, so
+/// no statement is sent to the database. The error of the `after_*` hooks is
+/// returned by the operation once the statement was already executed.
+#[async_trait]
+pub trait EntityHooks: Send + Sync {
+    /// Called by `insert` and `multi_insert` before the entity is inserted
+    async fn before_insert(&mut self) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        Ok(())
+    }
+
+    /// Called by `insert` and `multi_insert` after the entity is inserted, when
+    /// the primary key generated by the database is already set
+    async fn after_insert(&mut self) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        Ok(())
+    }
+
+    /// Called by `update` before the row of the entity is updated
+    async fn before_update(&self) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        Ok(())
+    }
+
+    /// Called by `update` after the row of the entity is updated
+    async fn after_update(&self) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        Ok(())
+    }
+
+    /// Called by `delete` before the row of the entity is deleted
+    async fn before_delete(&self) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        Ok(())
+    }
+
+    /// Called by `delete` after the row of the entity is deleted
+    async fn after_delete(&self) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        Ok(())
+    }
+
+    /// Called over every entity retrieved from the database by the generated
+    /// selects and the `SelectQueryBuilder`, once it's mapped from its row
+    async fn after_load(&mut self) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        Ok(())
+    }
+}
+
+/// Runs the [`EntityHooks::after_load`] hook over every entity of the collection,
+/// stopping on the first failure
+pub async fn run_after_load_hooks<T: EntityHooks>(
+    mut entities: Vec<T>,
+) -> Result<Vec<T>, Box<dyn std::error::Error + Sync + Send>> {
+    for entity in entities.iter_mut() {
+        entity.after_load().await?;
+    }
+    Ok(entities)
+}
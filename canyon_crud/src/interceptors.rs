@@ -0,0 +1,238 @@
+//! The interceptors of the statements sent to the databases, to rewrite them, reject
+//! them or collect metrics of their execution.
+//!
+//! Every statement that passes through [`crate::crud::Transaction::query`] (so the
+//! ones of the CRUD operations, the query builders, the relations and the raw queries)
+//! is handed to the interceptors registered globally and to the ones registered for
+//! its datasource, in order of registration.
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::bounds::QueryParameter;
+
+/// An interceptor of the statements sent to the databases, registered globally with
+/// [`register_interceptor`] or for a single datasource with [`register_datasource_interceptor`].
+///
+/// Both methods have a default implementation that does nothing, so just the needed
+/// ones must be overridden:
+///
+/// ```ignore
+/// struct MaintenanceMode;
+///
+/// #[async_trait]
+/// impl QueryInterceptor for MaintenanceMode {
+///     async fn before_query(
+///         &self,
+///         query: &mut InterceptedQuery<'_, '_>,
+///     ) -> Result<(), Box<dyn Error + Sync + Send>> {
+///         if query.statement().starts_with("SELECT") {
+///             query.set_statement(format!("/* tenant: acme */ {}", query.statement()));
+///             Ok(())
+///         } else {
+///             Err("The database is in maintenance mode".into())
+///         }
+///     }
+/// }
+///
+/// register_interceptor(MaintenanceMode);
+/// ```
+#[async_trait]
+pub trait QueryInterceptor: Send + Sync {
+    /// Called before the statement is sent to the database, where it can be rewritten
+    /// with [`InterceptedQuery::set_statement`], along with its parameters (to keep them
+    /// consistent with the placeholders). Returning an error rejects the statement,
+    /// so it isn't sent to the database and the error is returned by the query, without
+    /// calling the rest of the interceptors
+    async fn before_query(
+        &self,
+        _query: &mut InterceptedQuery<'_, '_>,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        Ok(())
+    }
+
+    /// Called once the statement (as it was sent to the database) is executed, with
+    /// the outcome of its execution
+    async fn after_query(&self, _query: &InterceptedQuery<'_, '_>, _outcome: &QueryOutcome<'_>) {}
+}
+
+/// A statement about to be sent to the database of a datasource, along with its parameters
+pub struct InterceptedQuery<'q, 'a> {
+    datasource: &'q str,
+    statement: String,
+    params: Vec<InterceptedParam<'q, 'a>>,
+}
+
+/// A parameter of an intercepted statement, that's the one received by the query or
+/// the one provided by an interceptor
+enum InterceptedParam<'q, 'a> {
+    Received(&'q dyn QueryParameter<'a>),
+    Provided(Box<dyn QueryParameter<'a> + 'a>),
+}
+
+impl<'q, 'a> InterceptedQuery<'q, 'a> {
+    pub fn new(
+        datasource: &'q str,
+        statement: String,
+        params: &'q [&'a dyn QueryParameter<'a>],
+    ) -> Self {
+        Self {
+            datasource,
+            statement,
+            params: params
+                .iter()
+                .map(|param| InterceptedParam::Received(*param))
+                .collect(),
+        }
+    }
+
+    /// The name of the datasource targeted by the statement
+    pub fn datasource(&self) -> &str {
+        self.datasource
+    }
+
+    /// The statement, with the changes of the interceptors already called
+    pub fn statement(&self) -> &str {
+        &self.statement
+    }
+
+    /// Replaces the statement sent to the database. The placeholders added or removed
+    /// must be kept consistent with the parameters, through [`Self::push_param`],
+    /// [`Self::replace_param`] and [`Self::remove_param`]
+    pub fn set_statement(&mut self, statement: impl Into<String>) {
+        self.statement = statement.into();
+    }
+
+    /// The parameters bound to the placeholders of the statement, with the changes of
+    /// the interceptors already called
+    pub fn params(&self) -> Vec<&dyn QueryParameter<'a>> {
+        self.params
+            .iter()
+            .map(|param| match param {
+                InterceptedParam::Received(param) => *param,
+                InterceptedParam::Provided(param) => param.as_ref(),
+            })
+            .collect()
+    }
+
+    /// Appends a parameter, returning the number of the placeholder that it's bound to
+    pub fn push_param(&mut self, param: impl QueryParameter<'a> + 'a) -> usize {
+        self.params
+            .push(InterceptedParam::Provided(Box::new(param)));
+        self.params.len()
+    }
+
+    /// Replaces the parameter bound to the placeholder with the given number (`$1` is the
+    /// first one). Returns false if the statement doesn't have that many parameters
+    pub fn replace_param(
+        &mut self,
+        placeholder: usize,
+        param: impl QueryParameter<'a> + 'a,
+    ) -> bool {
+        match placeholder
+            .checked_sub(1)
+            .and_then(|idx| self.params.get_mut(idx))
+        {
+            Some(current) => {
+                *current = InterceptedParam::Provided(Box::new(param));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the parameter bound to the placeholder with the given number (`$1` is the
+    /// first one), so the following parameters are bound to the previous placeholder.
+    /// Returns false if the statement doesn't have that many parameters
+    pub fn remove_param(&mut self, placeholder: usize) -> bool {
+        match placeholder.checked_sub(1) {
+            Some(idx) if idx < self.params.len() => {
+                self.params.remove(idx);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The outcome of the execution of an intercepted statement
+pub struct QueryOutcome<'r> {
+    result: Result<usize, &'r (dyn std::error::Error + Sync + Send)>,
+    elapsed: Duration,
+}
+
+impl<'r> QueryOutcome<'r> {
+    pub fn new(
+        result: Result<usize, &'r (dyn std::error::Error + Sync + Send)>,
+        elapsed: Duration,
+    ) -> Self {
+        Self { result, elapsed }
+    }
+
+    /// The number of rows returned by the statement, if it succeeded
+    pub fn rows(&self) -> Option<usize> {
+        self.result.ok()
+    }
+
+    /// The error returned by the database, if the statement failed
+    pub fn error(&self) -> Option<&(dyn std::error::Error + Sync + Send)> {
+        self.result.err()
+    }
+
+    /// The time elapsed executing the statement
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+/// The registered interceptors, along with the datasource they're registered for,
+/// or `None` for the global ones
+type Interceptors = Vec<(Option<String>, Arc<dyn QueryInterceptor>)>;
+
+static QUERY_INTERCEPTORS: Mutex<Interceptors> = Mutex::new(Vec::new());
+
+/// Registers an interceptor for the statements sent to every datasource
+pub fn register_interceptor(interceptor: impl QueryInterceptor + 'static) {
+    register(None, Arc::new(interceptor))
+}
+
+/// Registers an interceptor for the statements sent to the datasource with the given
+/// name, as it's declared on the configuration file
+pub fn register_datasource_interceptor(
+    datasource_name: &str,
+    interceptor: impl QueryInterceptor + 'static,
+) {
+    register(Some(datasource_name.to_string()), Arc::new(interceptor))
+}
+
+/// Removes every registered interceptor, both the global ones and the ones of the datasources
+pub fn clear_interceptors() {
+    QUERY_INTERCEPTORS
+        .lock()
+        .expect("Error acquiring Mutex guard on the query interceptors")
+        .clear()
+}
+
+fn register(datasource_name: Option<String>, interceptor: Arc<dyn QueryInterceptor>) {
+    QUERY_INTERCEPTORS
+        .lock()
+        .expect("Error acquiring Mutex guard on the query interceptors")
+        .push((datasource_name, interceptor))
+}
+
+/// The interceptors that must be called for the statements sent to the datasource
+/// with the given name, in order of registration
+pub(crate) fn interceptors_for(datasource_name: &str) -> Vec<Arc<dyn QueryInterceptor>> {
+    QUERY_INTERCEPTORS
+        .lock()
+        .expect("Error acquiring Mutex guard on the query interceptors")
+        .iter()
+        .filter(|(datasource, _)| {
+            datasource
+                .as_deref()
+                .is_none_or(|datasource| datasource == datasource_name)
+        })
+        .map(|(_, interceptor)| interceptor.clone())
+        .collect()
+}
@@ -0,0 +1,120 @@
+//! The runtime support for the fields annotated with `#[json]`, whose values of any
+//! `Serialize + DeserializeOwned` type (like `serde_json::Value`) are stored as `JSONB`
+//! on PostgreSQL, and as `NVARCHAR(MAX)` text on SQL Server.
+//!
+//! The code generated by the Canyon macros wraps the annotated fields with [`Json`]
+//! when they're sent to the database as query parameters, and when they're retrieved
+//! from the rows. The same wrapper must be used for the values of the `#[json]` fields
+//! passed to the `QueryBuilder`.
+use std::fmt::Debug;
+
+#[cfg(feature = "mssql")]
+use canyon_connection::tiberius::ColumnData;
+#[cfg(feature = "postgres")]
+use canyon_connection::tokio_postgres::types::{
+    self, to_sql_checked, FromSql, IsNull, ToSql, Type,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::bounds::QueryParameter;
+
+/// Wrapper over the values stored as JSON documents in the database
+#[repr(transparent)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Json<T>(pub T);
+
+impl<T> Json<T> {
+    /// Wraps a reference to a value without copying it, so the value can be
+    /// used as a query parameter
+    pub fn from_ref(value: &T) -> &Self {
+        // SAFETY: `Json<T>` is `#[repr(transparent)]` over `T`
+        unsafe { &*(value as *const T as *const Self) }
+    }
+
+    /// Unwraps the value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// The document that represents the value, or `None` if it's serialized as `null`,
+    /// which is stored as an SQL `NULL`
+    fn document(&self) -> Result<Option<Value>, serde_json::Error>
+    where
+        T: Serialize,
+    {
+        serde_json::to_value(&self.0).map(|value| (!value.is_null()).then_some(value))
+    }
+}
+
+impl<'a, T> QueryParameter<'a> for Json<T>
+where
+    T: Serialize + Debug + Sync + Send,
+{
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        let document = self
+            .document()
+            .expect("Failed to serialize the value of a JSON column");
+        ColumnData::String(document.map(|value| value.to_string().into()))
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<T> ToSql for Json<T>
+where
+    T: Serialize + Debug,
+{
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut bytes::BytesMut,
+    ) -> Result<IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        match self.document()? {
+            Some(value) => types::Json(value).to_sql(ty, out),
+            None => Ok(IsNull::Yes),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <types::Json<Value> as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+#[cfg(feature = "postgres")]
+impl<'a, T> FromSql<'a> for Json<T>
+where
+    T: DeserializeOwned,
+{
+    fn from_sql(
+        ty: &Type,
+        raw: &'a [u8],
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        types::Json::<T>::from_sql(ty, raw).map(|json| Json(json.0))
+    }
+
+    /// The `NULL` values are deserialized as the `null` JSON value, so they're
+    /// accepted by the `Option<T>` fields
+    fn from_sql_null(_ty: &Type) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(Json(T::deserialize(Value::Null)?))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <types::Json<T> as FromSql>::accepts(ty)
+    }
+}
+
+/// Deserializes the text of a JSON column retrieved from SQL Server, where the
+/// `NULL` values are deserialized as the `null` JSON value
+pub fn from_json_text<T: DeserializeOwned>(text: Option<&str>) -> Result<T, serde_json::Error> {
+    match text {
+        Some(text) => serde_json::from_str(text),
+        None => T::deserialize(Value::Null),
+    }
+}
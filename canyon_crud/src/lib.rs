@@ -3,11 +3,28 @@ extern crate canyon_connection;
 
 pub mod bounds;
 pub mod crud;
+pub mod embedded;
+pub mod enums;
+pub mod errors;
+pub mod hooks;
+pub mod interceptors;
+pub mod json;
 pub mod mapper;
+#[cfg(feature = "mock")]
+pub mod mock;
 pub mod query_elements;
+pub mod relations;
 pub mod rows;
+pub mod tracked;
+pub mod types;
+pub mod validation;
 
 pub use query_elements::operators::*;
 
 pub use canyon_connection::{canyon_database_connector::DatabaseType, datasources::*};
 pub use chrono;
+pub use serde_json;
+pub use uuid;
+
+#[cfg(feature = "rust_decimal")]
+pub use rust_decimal;
@@ -3,14 +3,25 @@ use canyon_connection::tiberius;
 #[cfg(feature = "postgres")]
 use canyon_connection::tokio_postgres;
 
-use crate::crud::Transaction;
+use crate::errors::MappingError;
+#[cfg(feature = "mock")]
+use crate::mock::MockRow;
 
 /// Declares functions that takes care to deserialize data incoming
 /// from some supported database in Canyon-SQL into a user's defined
-/// type `T`
-pub trait RowMapper<T: Transaction<T>>: Sized {
+/// type `T`.
+///
+/// It's implemented by the `CanyonMapper` derive macro, which doesn't require the
+/// type to be an entity, so any struct can be the result of [`crate::crud::query_as`].
+///
+/// The mapping fails with a [`MappingError`] when a column doesn't exist, contains an
+/// unexpected `NULL` or can't be converted into the type of its field
+pub trait RowMapper<T>: Sized {
     #[cfg(feature = "postgres")]
-    fn deserialize_postgresql(row: &tokio_postgres::Row) -> T;
+    fn deserialize_postgresql(row: &tokio_postgres::Row) -> Result<T, MappingError>;
     #[cfg(feature = "mssql")]
-    fn deserialize_sqlserver(row: &tiberius::Row) -> T;
+    fn deserialize_sqlserver(row: &tiberius::Row) -> Result<T, MappingError>;
+    /// Takes the instance held by a row of a mock datasource
+    #[cfg(feature = "mock")]
+    fn deserialize_mock(row: MockRow) -> Result<T, MappingError>;
 }
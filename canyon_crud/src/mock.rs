@@ -0,0 +1,291 @@
+//! The mock datasources, that answer the statements with the rows of the expectations
+//! registered by the tests instead of connecting to a database, so the code that uses
+//! the entities can be unit tested without a live database.
+//!
+//! They're declared on the configuration file with the `mock` auth, and the dialect
+//! of the SQL of the statements generated for them:
+//!
+//! ```toml
+//! [[canyon_sql.datasources]]
+//! name = 'mock_ds'
+//! auth = { mock = { dialect = 'postgresql' } }
+//! properties.host = 'localhost'
+//! properties.db_name = 'mock'
+//! ```
+//!
+//! The generated CRUD operations run unchanged against them, mapping the rows of the
+//! expectations, built from instances of the entities (or from plain values, like the
+//! `i64` of a `count`), and recording every executed statement:
+//!
+//! ```ignore
+//! let mock = MockDatasource::new("mock_ds");
+//! mock.expect(r"^SELECT \* FROM league").returning(vec![league.clone()]);
+//! mock.expect(r"^DELETE FROM league").failing("The league can't be deleted");
+//!
+//! assert_eq!(League::find_all_datasource("mock_ds").await?, vec![league]);
+//! mock.assert_executed(r"^SELECT \* FROM league");
+//! ```
+use std::any::Any;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::sync::{Arc, Mutex};
+
+use canyon_connection::get_database_config;
+use regex::Regex;
+
+use crate::bounds::QueryParameter;
+use crate::errors::MappingError;
+use crate::rows::CanyonRows;
+
+/// A row returned by a mock datasource, holding an instance of the type it's mapped into
+pub struct MockRow {
+    value: Box<dyn Any + Send + Sync>,
+    type_name: &'static str,
+}
+
+impl MockRow {
+    pub fn new<V: Any + Send + Sync>(value: V) -> Self {
+        Self {
+            value: Box::new(value),
+            type_name: std::any::type_name::<V>(),
+        }
+    }
+
+    /// The name of the type of the value held by the row
+    pub fn type_name(&self) -> &str {
+        self.type_name
+    }
+
+    /// Consumes the row and returns the value that holds, or a [`MappingError`] if
+    /// it's an instance of another type
+    pub fn into_value<V: Any>(self) -> Result<V, MappingError> {
+        let type_name = self.type_name;
+        self.value.downcast::<V>().map(|value| *value).map_err(|_| {
+            MappingError::new(
+                "*",
+                std::any::type_name::<V>(),
+                Some(type_name.to_owned()),
+                "The mock row holds an instance of another type".into(),
+            )
+        })
+    }
+}
+
+/// The error returned by the statements of the expectations that fails, and by the
+/// statements that doesn't match any expectation of the mock datasource
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockError {
+    message: String,
+}
+
+impl MockError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for MockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for MockError {}
+
+/// A statement executed against a mock datasource, with the `Debug` representation
+/// of its parameters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutedStatement {
+    pub statement: String,
+    pub params: Vec<String>,
+}
+
+type RowsFactory = Arc<dyn Fn() -> Result<Vec<MockRow>, MockError> + Send + Sync>;
+
+struct Expectation {
+    pattern: Regex,
+    params: Option<Vec<String>>,
+    rows: RowsFactory,
+}
+
+impl Expectation {
+    fn matches(&self, statement: &ExecutedStatement) -> bool {
+        self.pattern.is_match(&statement.statement)
+            && self
+                .params
+                .as_ref()
+                .is_none_or(|params| *params == statement.params)
+    }
+}
+
+#[derive(Default)]
+struct MockState {
+    expectations: Vec<Expectation>,
+    executed: Vec<ExecutedStatement>,
+}
+
+/// The expectations and the executed statements of every mock datasource, by name
+static MOCK_DATASOURCES: Mutex<Vec<(String, MockState)>> = Mutex::new(Vec::new());
+
+fn with_state<R>(datasource_name: &str, f: impl FnOnce(&mut MockState) -> R) -> R {
+    let mut datasources = MOCK_DATASOURCES
+        .lock()
+        .expect("Error acquiring Mutex guard on the mock datasources");
+    let idx = match datasources
+        .iter()
+        .position(|(name, _)| name == datasource_name)
+    {
+        Some(idx) => idx,
+        None => {
+            datasources.push((datasource_name.to_string(), MockState::default()));
+            datasources.len() - 1
+        }
+    };
+    f(&mut datasources[idx].1)
+}
+
+/// A handle over a mock datasource declared on the configuration file, to register
+/// the expectations of the statements sent to it and to check the executed ones.
+///
+/// The expectations are looked up from the last registered to the first one, so the
+/// later expectations override the previous ones that matches the same statements
+#[derive(Debug, Clone)]
+pub struct MockDatasource {
+    name: String,
+}
+
+impl MockDatasource {
+    /// The handle over the mock datasource with the given name, or over the first
+    /// declared datasource if the name is empty, that must be a mock one
+    pub fn new(datasource_name: &str) -> Self {
+        let config = get_database_config(datasource_name);
+        if !config.is_mock() {
+            panic!("The datasource `{}` isn't a mock datasource", config.name);
+        }
+        Self {
+            name: config.name.clone(),
+        }
+    }
+
+    /// Starts the expectation of the statements that matches the given regular expression
+    pub fn expect(&self, pattern: &str) -> MockExpectation<'_> {
+        MockExpectation {
+            datasource: self,
+            pattern: Regex::new(pattern).unwrap_or_else(|e| {
+                panic!("Invalid pattern `{pattern}` for a mock expectation: {e}")
+            }),
+            params: None,
+        }
+    }
+
+    /// The statements executed against the datasource, in order of execution
+    pub fn executed(&self) -> Vec<ExecutedStatement> {
+        with_state(&self.name, |state| state.executed.clone())
+    }
+
+    /// Panics if none of the executed statements matches the given regular expression
+    pub fn assert_executed(&self, pattern: &str) {
+        let regex = Regex::new(pattern)
+            .unwrap_or_else(|e| panic!("Invalid pattern `{pattern}` for a mock assertion: {e}"));
+        let executed = self.executed();
+        if !executed.iter().any(|e| regex.is_match(&e.statement)) {
+            panic!(
+                "No statement matching `{pattern}` was executed against the mock datasource `{}`. \
+                Executed statements: {:#?}",
+                self.name, executed
+            );
+        }
+    }
+
+    /// Removes the registered expectations and the executed statements of the datasource
+    pub fn reset(&self) {
+        with_state(&self.name, |state| *state = MockState::default())
+    }
+
+    fn register(&self, expectation: Expectation) {
+        with_state(&self.name, |state| state.expectations.push(expectation))
+    }
+}
+
+/// An expectation of a mock datasource being built, registered by the methods that
+/// sets the outcome of the statements that matches it
+pub struct MockExpectation<'m> {
+    datasource: &'m MockDatasource,
+    pattern: Regex,
+    params: Option<Vec<String>>,
+}
+
+impl<'m> MockExpectation<'m> {
+    /// Restricts the expectation to the statements with the given parameters, compared
+    /// by their `Debug` representation
+    pub fn with_params(mut self, params: &[&dyn QueryParameter<'_>]) -> Self {
+        self.params = Some(params.iter().map(|param| format!("{param:?}")).collect());
+        self
+    }
+
+    /// The statements that matches the expectation returns a row for every given value,
+    /// mapped into the type requested by the operation
+    pub fn returning<V: Any + Clone + Send + Sync>(self, rows: Vec<V>) {
+        self.register(Arc::new(move || {
+            Ok(rows.iter().cloned().map(MockRow::new).collect())
+        }))
+    }
+
+    /// The statements that matches the expectation succeeds without returning any row
+    pub fn returning_no_rows(self) {
+        self.register(Arc::new(|| Ok(Vec::new())))
+    }
+
+    /// The statements that matches the expectation fails with a [`MockError`] with
+    /// the given message
+    pub fn failing(self, message: impl Into<String>) {
+        let error = MockError::new(message);
+        self.register(Arc::new(move || Err(error.clone())))
+    }
+
+    fn register(self, rows: RowsFactory) {
+        self.datasource.register(Expectation {
+            pattern: self.pattern,
+            params: self.params,
+            rows,
+        })
+    }
+}
+
+/// Records the statement executed against the mock datasource with the given name,
+/// and returns the rows of the last registered expectation that matches it
+pub(crate) fn launch<T>(
+    datasource_name: &str,
+    stmt: &str,
+    params: &[&dyn QueryParameter<'_>],
+) -> Result<CanyonRows<T>, Box<dyn Error + Send + Sync + 'static>> {
+    let executed = ExecutedStatement {
+        statement: stmt.to_string(),
+        params: params.iter().map(|param| format!("{param:?}")).collect(),
+    };
+
+    let rows = with_state(datasource_name, |state| {
+        state.executed.push(executed.clone());
+        state
+            .expectations
+            .iter()
+            .rev()
+            .find(|expectation| expectation.matches(&executed))
+            .map(|expectation| expectation.rows.clone())
+    })
+    .ok_or_else(|| {
+        MockError::new(format!(
+            "The mock datasource `{datasource_name}` has no expectation for the statement \
+            `{stmt}` with the parameters {:?}",
+            executed.params
+        ))
+    })?;
+
+    Ok(CanyonRows::Mock(rows()?))
+}
@@ -52,3 +52,24 @@ impl Operator for Like {
         }
     }
 }
+
+/// Enumerated type for represent the operations over the PostgreSQL
+/// array columns in SQL sentences. They aren't supported on SQL Server
+pub enum Array {
+    /// Operator "= ANY" equals to any of the elements of the array value
+    Any,
+    /// Operator "@>" contains all the elements of the array value
+    Contains,
+    /// Operator "&&" has elements in common with the array value
+    Overlaps,
+}
+
+impl Operator for Array {
+    fn as_str(&self, placeholder_counter: usize) -> String {
+        match *self {
+            Self::Any => format!(" = ANY(${placeholder_counter})"),
+            Self::Contains => format!(" @> ${placeholder_counter}"),
+            Self::Overlaps => format!(" && ${placeholder_counter}"),
+        }
+    }
+}
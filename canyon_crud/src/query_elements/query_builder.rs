@@ -1,11 +1,17 @@
 use std::fmt::Debug;
 
+use canyon_connection::get_database_config;
+
 use crate::{
     bounds::{FieldIdentifier, FieldValueIdentifier, QueryParameter},
     crud::{CrudOperations, Transaction},
+    hooks::run_after_load_hooks,
     mapper::RowMapper,
     query_elements::query::Query,
-    Operator,
+    relations::Relation,
+    rows::CanyonRows,
+    types::FieldValue,
+    DatabaseType, Operator,
 };
 
 /// Contains the elements that makes part of the formal declaration
@@ -127,6 +133,42 @@ pub mod ops {
         /// * `desc` - a boolean indicating if the generated `ORDER_BY` must be
         /// in ascending or descending order
         fn order_by<Z: FieldIdentifier<T>>(&mut self, order_by: Z, desc: bool) -> &mut Self;
+
+        /// Generates a filter over a value nested inside the JSON document stored in a
+        /// column, joined with `WHERE` or `AND` depending on the previous clauses.
+        ///
+        /// The nested value is extracted as text, with the `->`/`->>` operators on
+        /// PostgreSQL and with `JSON_VALUE` on SQL Server, so it must be compared
+        /// against a text value.
+        ///
+        /// * `column` - A [`FieldIdentifier`] that will provide the target JSON column
+        /// * `path` - The keys that leads to the nested value
+        /// * `op` - Any element that implements [`Operator`] for create the comparison
+        /// * `value` - The [`QueryParameter`] compared against the nested value
+        fn json_value<Z, Q>(
+            &mut self,
+            column: Z,
+            path: &[&str],
+            op: impl Operator,
+            value: &'a Q,
+        ) -> &mut Self
+        where
+            Z: FieldIdentifier<T>,
+            Q: QueryParameter<'a>;
+
+        /// Generates a filter that matches the JSON documents stored in a column which
+        /// contains the given one, through the PostgreSQL `@>` operator, joined with
+        /// `WHERE` or `AND` depending on the previous clauses.
+        ///
+        /// SQL Server doesn't provides a containment operator, so the query returns an
+        /// error when it targets a SQL Server datasource
+        ///
+        /// * `column` - A [`FieldIdentifier`] that will provide the target JSON column
+        /// * `value` - The document wrapped in a [`Json`](crate::json::Json)
+        fn json_contains<Z, Q>(&mut self, column: Z, value: &'a Q) -> &mut Self
+        where
+            Z: FieldIdentifier<T>,
+            Q: QueryParameter<'a>;
     }
 }
 
@@ -138,6 +180,11 @@ where
 {
     query: Query<'a, T>,
     datasource_name: &'a str,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    /// The error of a filter that can't be written in the SQL dialect of the targeted
+    /// database, returned when the query is launched
+    error: Option<&'static str>,
 }
 
 unsafe impl<'a, T> Send for QueryBuilder<'a, T> where
@@ -158,6 +205,9 @@ where
         Self {
             query,
             datasource_name,
+            limit: None,
+            offset: None,
+            error: None,
         }
     }
 
@@ -166,15 +216,258 @@ where
     pub async fn query(
         &'a mut self,
     ) -> Result<Vec<T>, Box<(dyn std::error::Error + Sync + Send + 'static)>> {
+        let results = self.launch().await?.into_results::<T>()?;
+
+        run_after_load_hooks(results).await
+    }
+
+    /// Launches the generated query against the database targeted
+    /// by the selected datasource, without mapping the retrieved rows
+    async fn launch(
+        &mut self,
+    ) -> Result<CanyonRows<T>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        self.check_error()?;
+        let pagination = self.pagination_clause()?;
+        self.query.sql.push_str(&pagination);
+        self.query.sql.push(';');
+
+        T::query(
+            self.query.sql.clone(),
+            self.query.params.to_vec(),
+            self.datasource_name,
+        )
+        .await
+    }
+
+    /// Launches the generated query against the database targeted
+    /// by the selected datasource, just checking if it returns some
+    /// row, without mapping the results
+    pub async fn exists(
+        &'a mut self,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        self.check_error()?;
+        self.limit_rows(1);
+        let pagination = self.pagination_clause()?;
+        self.query.sql.push_str(&pagination);
         self.query.sql.push(';');
 
-        Ok(T::query(
+        Ok(!T::query(
             self.query.sql.clone(),
             self.query.params.to_vec(),
             self.datasource_name,
         )
         .await?
-        .into_results::<T>())
+        .is_empty())
+    }
+
+    /// Counts the rows that matches the filters of the generated query, ignoring
+    /// the `ORDER BY` clauses and the pagination, if present
+    async fn count(&mut self) -> Result<i64, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        self.check_error()?;
+        let mut filtered_sql = self.query.sql.as_str();
+        if let Some(idx) = filtered_sql.rfind(" ORDER BY ") {
+            filtered_sql = &filtered_sql[..idx];
+        }
+        let stmt = filtered_sql.replacen("SELECT * FROM", "SELECT COUNT(*) FROM", 1) + ";";
+
+        let count = T::query(stmt, self.query.params.to_vec(), self.datasource_name).await?;
+
+        match count {
+            #[cfg(feature = "postgres")]
+            CanyonRows::Postgres(mut v) => Ok(v.remove(0).get::<usize, i64>(0)),
+            #[cfg(feature = "mssql")]
+            CanyonRows::Tiberius(mut v) => v
+                .remove(0)
+                .get::<i32, usize>(0)
+                .map(|c| c as i64)
+                .ok_or_else(|| "Failure in the COUNT query for MSSQL".into()),
+            #[cfg(feature = "mock")]
+            CanyonRows::Mock(v) => Ok(v
+                .into_iter()
+                .next()
+                .ok_or("Failure in the COUNT query for the mock datasource")?
+                .into_value::<i64>()?),
+            _ => panic!("This branch will never ever should be reachable"),
+        }
+    }
+
+    /// Returns the error of the filters that can't be written in the SQL dialect of the
+    /// targeted database (if any), so the query isn't launched
+    fn check_error(&self) -> Result<(), Box<dyn std::error::Error + Sync + Send + 'static>> {
+        match self.error {
+            Some(error) => Err(std::io::Error::new(std::io::ErrorKind::Unsupported, error).into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Restricts the query to retrieve at most `rows` rows, keeping the `limit` of the
+    /// query if it's lower.
+    ///
+    /// It's rendered as the `limit` of the pagination clause, except for the unsorted
+    /// and not paginated queries against SQL Server, that can't use the `OFFSET ... FETCH
+    /// NEXT` syntax without an `ORDER BY` clause, and are restricted with a `TOP` placed
+    /// on the `SELECT` of the top level query instead
+    fn limit_rows(&mut self, rows: usize) {
+        #[cfg(feature = "mssql")]
+        if self.limit.is_none()
+            && self.offset.is_none()
+            && !self.query.sql.contains(" ORDER BY ")
+            && get_database_config(self.datasource_name).get_db_type() == DatabaseType::SqlServer
+        {
+            if let Some(idx) = top_level_select_end(&self.query.sql) {
+                self.query.sql.insert_str(idx, &format!(" TOP ({rows})"));
+                return;
+            }
+        }
+
+        self.limit = Some(self.limit.map_or(rows, |limit| limit.min(rows)));
+    }
+
+    /// Generates the SQL clause that restricts the retrieved rows based on the `limit`
+    /// and `offset` values (if any), in the dialect of the database targeted by the
+    /// selected datasource.
+    ///
+    /// SQL Server only allows the `OFFSET ... FETCH NEXT` syntax after an `ORDER BY` clause,
+    /// so an error is returned if the query isn't sorted
+    fn pagination_clause(
+        &self,
+    ) -> Result<String, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        if self.limit.is_none() && self.offset.is_none() {
+            return Ok(String::new());
+        }
+
+        match get_database_config(self.datasource_name).get_db_type() {
+            #[cfg(feature = "postgres")]
+            DatabaseType::PostgreSql => {
+                let mut clause = String::new();
+                if let Some(limit) = self.limit {
+                    clause.push_str(&format!(" LIMIT {limit}"));
+                }
+                if let Some(offset) = self.offset {
+                    clause.push_str(&format!(" OFFSET {offset}"));
+                }
+                Ok(clause)
+            }
+            #[cfg(feature = "mssql")]
+            DatabaseType::SqlServer => {
+                if !self.query.sql.contains(" ORDER BY ") {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "SQL Server requires an ORDER BY clause to paginate the results. \
+                        Use the `order_by` method of the QueryBuilder before setting a limit or an offset",
+                    )
+                    .into());
+                }
+
+                let mut clause = format!(" OFFSET {} ROWS", self.offset.unwrap_or_default());
+                if let Some(limit) = self.limit {
+                    clause.push_str(&format!(" FETCH NEXT {limit} ROWS ONLY"));
+                }
+                Ok(clause)
+            }
+        }
+    }
+
+    /// Generates the filter and the `ORDER BY` clause for a keyset (or seek) pagination,
+    /// where the retrieved rows are the ones placed after the `cursor` values over the
+    /// ordered columns.
+    ///
+    /// For several columns, the comparison is expanded as
+    /// `(c1 > $1) OR (c1 = $1 AND c2 > $2) ...`, since SQL Server doesn't support
+    /// row value comparisons
+    pub fn after<Z, I>(&mut self, cursor: I, desc: bool)
+    where
+        Z: FieldValueIdentifier<'a, T>,
+        I: IntoIterator<Item = Z>,
+    {
+        let comparator = if desc { "<" } else { ">" };
+        let mut columns: Vec<(&str, usize)> = Vec::new();
+
+        for column in cursor {
+            let (column_name, value) = column.value();
+            self.query.params.push(value);
+            columns.push((column_name, self.query.params.len()));
+        }
+
+        if columns.is_empty() {
+            return;
+        }
+
+        let predicate = (0..columns.len())
+            .map(|idx| {
+                let mut conditions = columns[..idx]
+                    .iter()
+                    .map(|(name, placeholder)| format!("{name} = ${placeholder}"))
+                    .collect::<Vec<String>>();
+                let (name, placeholder) = columns[idx];
+                conditions.push(format!("{name} {comparator} ${placeholder}"));
+
+                format!("({})", conditions.join(" AND "))
+            })
+            .collect::<Vec<String>>()
+            .join(" OR ");
+
+        let mut order = columns
+            .iter()
+            .map(|(name, _)| format!("{name}{}", if desc { " DESC" } else { "" }))
+            .collect::<Vec<String>>();
+
+        // The filter must be placed before a previous `ORDER BY` clause, whose columns
+        // are kept after the ones of the cursor, breaking the ties between the rows
+        if let Some(idx) = self.top_level_clause_position(" ORDER BY ") {
+            let previous_order = self.query.sql.split_off(idx);
+            order.extend(
+                previous_order[" ORDER BY ".len()..]
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|ordering| {
+                        let name = ordering.split_whitespace().next().unwrap_or_default();
+                        !name.is_empty() && !columns.iter().any(|(column, _)| *column == name)
+                    })
+                    .map(String::from),
+            );
+        }
+
+        // The previous filters are grouped, so their `OR` conditions can't bypass the
+        // ones of the cursor
+        if let Some(idx) = self.top_level_clause_position(" WHERE ") {
+            let previous_filters = self.query.sql.split_off(idx);
+            self.query.sql.push_str(&format!(
+                " WHERE ({}) AND",
+                &previous_filters[" WHERE ".len()..]
+            ));
+        } else {
+            self.query.sql.push_str(" WHERE");
+        }
+
+        self.query
+            .sql
+            .push_str(&format!(" ({predicate}) ORDER BY {}", order.join(", ")));
+    }
+
+    /// Checks if the generated query already contains a `WHERE` clause, ignoring the
+    /// ones that belongs to a subquery (like the derived tables used as the source of
+    /// the entities annotated with `#[soft_delete]`)
+    fn has_where_clause(&self) -> bool {
+        self.top_level_clause_position(" WHERE ").is_some()
+    }
+
+    /// The position of the given clause in the generated query, ignoring the ones that
+    /// belongs to a subquery
+    fn top_level_clause_position(&self, clause: &str) -> Option<usize> {
+        let sql = self.query.sql.as_str();
+        let mut depth = 0;
+
+        for (idx, c) in sql.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                ' ' if depth == 0 && sql[idx..].starts_with(clause) => return Some(idx),
+                _ => {}
+            }
+        }
+
+        None
     }
 
     pub fn r#where<Z: FieldValueIdentifier<'a, T>>(&mut self, r#where: Z, op: impl Operator) {
@@ -267,16 +560,87 @@ where
         self.query.sql.push(')')
     }
 
-    #[inline]
+    /// Sorts the results by the given column, after the columns of the previous
+    /// `ORDER BY` clause (if any), since there can be just one
     pub fn order_by<Z: FieldIdentifier<T>>(&mut self, order_by: Z, desc: bool) {
+        let clause = if self.top_level_clause_position(" ORDER BY ").is_some() {
+            ","
+        } else {
+            " ORDER BY"
+        };
+
         self.query.sql.push_str(
             &(format!(
-                " ORDER BY {}{}",
+                "{clause} {}{}",
                 order_by.as_str(),
                 if desc { " DESC " } else { "" }
             )),
         );
     }
+
+    pub fn json_value<Z, Q>(&mut self, column: Z, path: &[&str], op: impl Operator, value: &'a Q)
+    where
+        Z: FieldIdentifier<T>,
+        Q: QueryParameter<'a>,
+    {
+        let keys = path
+            .iter()
+            .map(|key| key.replace('\'', "''"))
+            .collect::<Vec<String>>();
+
+        let nested_value: String = match get_database_config(self.datasource_name).get_db_type() {
+            #[cfg(feature = "postgres")]
+            DatabaseType::PostgreSql => {
+                let mut nested_value = column.as_str().to_string();
+                for (idx, key) in keys.iter().enumerate() {
+                    let arrow = if idx + 1 == keys.len() { "->>" } else { "->" };
+                    nested_value.push_str(&format!(" {arrow} '{key}'"));
+                }
+                nested_value
+            }
+            #[cfg(feature = "mssql")]
+            DatabaseType::SqlServer => {
+                format!("JSON_VALUE({}, '$.{}')", column.as_str(), keys.join("."))
+            }
+        };
+
+        let clause = if self.has_where_clause() {
+            " AND "
+        } else {
+            " WHERE "
+        };
+
+        self.query.sql.push_str(&format!(
+            "{clause}{nested_value}{}",
+            op.as_str(self.query.params.len() + 1)
+        ));
+        self.query.params.push(value);
+    }
+
+    pub fn json_contains<Z, Q>(&mut self, column: Z, value: &'a Q)
+    where
+        Z: FieldIdentifier<T>,
+        Q: QueryParameter<'a>,
+    {
+        #[cfg(feature = "mssql")]
+        if let DatabaseType::SqlServer = get_database_config(self.datasource_name).get_db_type() {
+            self.error = Some("The JSON containment filter isn't supported on SQL Server");
+            return;
+        }
+
+        let clause = if self.has_where_clause() {
+            " AND "
+        } else {
+            " WHERE "
+        };
+
+        self.query.sql.push_str(&format!(
+            "{clause}{} @> ${}",
+            column.as_str(),
+            self.query.params.len() + 1
+        ));
+        self.query.params.push(value);
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -310,6 +674,171 @@ where
         self._inner.query().await
     }
 
+    /// Eager loads the parent of every retrieved entity through the given foreign key
+    /// relation, so the query returns every entity paired with its parent (or `None`
+    /// if it doesn't exist). The parents are retrieved with just one more query.
+    ///
+    /// ```ignore
+    /// let tournaments: Vec<(Tournament, Option<League>)> = Tournament::select_query()
+    ///     .r#where(TournamentFieldValue::league(&1), Comp::Eq)
+    ///     .with(TournamentRelation::league)
+    ///     .query()
+    ///     .await?;
+    /// ```
+    pub fn with<P, K>(&mut self, relation: Relation<T, P, K>) -> WithParentQuery<'_, 'a, T, P, K> {
+        WithParentQuery {
+            builder: self,
+            relation,
+        }
+    }
+
+    /// Eager loads the children of every retrieved entity through the given foreign key
+    /// relation of the children, so the query returns every entity paired with its
+    /// children. The children are retrieved with just one more query.
+    ///
+    /// ```ignore
+    /// let leagues: Vec<(League, Vec<Tournament>)> = League::select_query()
+    ///     .with_children(TournamentRelation::league)
+    ///     .query()
+    ///     .await?;
+    /// ```
+    pub fn with_children<C, K>(
+        &mut self,
+        relation: Relation<C, T, K>,
+    ) -> WithChildrenQuery<'_, 'a, C, T, K> {
+        WithChildrenQuery {
+            builder: self,
+            relation,
+        }
+    }
+
+    /// Launches the generated query to the database pointed by the
+    /// selected datasource, returning only the first retrieved
+    /// entity, or [`None`] if the query does not produce any result.
+    ///
+    /// Just one row is requested to the database
+    pub async fn first(
+        &'a mut self,
+    ) -> Result<Option<T>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        self._inner.limit_rows(1);
+        Ok(self._inner.query().await?.into_iter().next())
+    }
+
+    /// Launches the generated query to the database pointed by the
+    /// selected datasource, expecting exactly one entity as a result.
+    ///
+    /// Returns an error if the query doesn't retrieve any row, or if
+    /// it retrieves more than one. Just two rows are requested to the
+    /// database, enough to detect the second one
+    pub async fn one(
+        &'a mut self,
+    ) -> Result<T, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        self._inner.limit_rows(2);
+        let mut results = self._inner.query().await?;
+
+        match results.len() {
+            1 => Ok(results.remove(0)),
+            0 => Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "Expected exactly one row, but the query didn't return any",
+            )
+            .into()),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Expected exactly one row, but the query returned more than one",
+            )
+            .into()),
+        }
+    }
+
+    /// Launches the generated query to the database pointed by the
+    /// selected datasource, returning true if it matches some row.
+    ///
+    /// Just one row is requested to the database
+    #[inline]
+    pub async fn exists(
+        &'a mut self,
+    ) -> Result<bool, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        self._inner.exists().await
+    }
+
+    /// Restricts the maximum number of entities retrieved by the query.
+    ///
+    /// It's rendered as a `LIMIT` clause for PostgreSQL, and as an
+    /// `OFFSET ... FETCH NEXT` clause for SQL Server, which requires
+    /// the query to be sorted with [`ops::QueryBuilder::order_by`]
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self._inner.limit = Some(limit);
+        self
+    }
+
+    /// Skips the first `offset` rows of the results of the query.
+    ///
+    /// It's rendered as an `OFFSET` clause for PostgreSQL, and as an
+    /// `OFFSET ... ROWS` clause for SQL Server, which requires
+    /// the query to be sorted with [`ops::QueryBuilder::order_by`]
+    pub fn offset(&mut self, offset: usize) -> &mut Self {
+        self._inner.offset = Some(offset);
+        self
+    }
+
+    /// Launches the generated query to the database pointed by the selected
+    /// datasource, retrieving only the entities of the requested (zero-based) `page`,
+    /// along with the total number of rows that matches the filters of the query
+    pub async fn paginate(
+        &'a mut self,
+        page: usize,
+        page_size: usize,
+    ) -> Result<Page<T>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        let total = self._inner.count().await?;
+
+        self._inner.limit = Some(page_size);
+        self._inner.offset = Some(page * page_size);
+        let items = self._inner.query().await?;
+
+        Ok(Page {
+            items,
+            total,
+            page,
+            page_size,
+        })
+    }
+
+    /// Keyset pagination. Restricts the query to the rows placed after the values
+    /// of the `cursor` (usually, the ones of the last entity of the previous page),
+    /// sorting the results by the columns of the cursor.
+    ///
+    /// Unlike an `OFFSET`, the database doesn't have to walk through all the skipped
+    /// rows, so it's the preferred way of deep paging or iterating over big tables
+    /// in chunks:
+    ///
+    /// ```ignore
+    /// let mut chunk = League::select_query().order_by(LeagueField::id, false).limit(500).query().await?;
+    /// while let Some(last) = chunk.last() {
+    ///     // ... process the chunk
+    ///     chunk = League::select_query()
+    ///         .after([LeagueFieldValue::id(&last.id)], false)
+    ///         .limit(500)
+    ///         .query()
+    ///         .await?;
+    /// }
+    /// ```
+    ///
+    /// * `cursor` - The [`FieldValueIdentifier`]s with the ordered columns and the
+    ///   values of the last retrieved row
+    /// * `desc` - If the columns are sorted in descending order
+    ///
+    /// If the query is already sorted with the `order_by` method, the results are
+    /// sorted by the columns of the cursor first, and then by the previous ones
+    pub fn after<Z, I>(&mut self, cursor: I, desc: bool) -> &mut Self
+    where
+        Z: FieldValueIdentifier<'a, T>,
+        I: IntoIterator<Item = Z>,
+    {
+        self._inner.after(cursor, desc);
+        self
+    }
+
     /// Adds a *LEFT JOIN* SQL statement to the underlying
     /// [`Query`] held by the [`QueryBuilder`], where:
     ///
@@ -375,6 +904,91 @@ where
     }
 }
 
+/// A page of entities retrieved with the [`SelectQueryBuilder::paginate`] operation
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    /// The entities that belongs to the requested page
+    pub items: Vec<T>,
+    /// The total number of rows that matches the filters of the query
+    pub total: i64,
+    /// The zero-based index of the page
+    pub page: usize,
+    /// The maximum number of entities per page
+    pub page_size: usize,
+}
+
+impl<T> Page<T> {
+    /// Returns the number of pages needed to retrieve all the matching rows
+    pub fn total_pages(&self) -> usize {
+        if self.page_size == 0 {
+            return 0;
+        }
+        (self.total as usize).div_ceil(self.page_size)
+    }
+
+    /// Returns true if there are more rows after the current page
+    pub fn has_next(&self) -> bool {
+        self.page + 1 < self.total_pages()
+    }
+}
+
+/// A [`SelectQueryBuilder`] whose retrieved entities are paired with their parents,
+/// created with [`SelectQueryBuilder::with`]
+pub struct WithParentQuery<'b, 'a, T, P, K>
+where
+    T: CrudOperations<T> + Transaction<T> + RowMapper<T>,
+{
+    builder: &'b mut SelectQueryBuilder<'a, T>,
+    relation: Relation<T, P, K>,
+}
+
+impl<T, P, K> WithParentQuery<'_, '_, T, P, K>
+where
+    T: CrudOperations<T> + Transaction<T> + RowMapper<T>,
+    P: CrudOperations<P> + RowMapper<P> + Clone,
+    K: FieldValue + for<'k> QueryParameter<'k> + PartialEq,
+{
+    /// Launches the generated query to the database pointed by the selected
+    /// datasource, and the one that retrieves the parents of the entities
+    pub async fn query(
+        self,
+    ) -> Result<Vec<(T, Option<P>)>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        let datasource_name = self.builder._inner.datasource_name;
+        let results = self.builder._inner.launch().await?.into_results::<T>()?;
+        let results = run_after_load_hooks(results).await?;
+
+        self.relation.load_parents(results, datasource_name).await
+    }
+}
+
+/// A [`SelectQueryBuilder`] whose retrieved entities are paired with their children,
+/// created with [`SelectQueryBuilder::with_children`]
+pub struct WithChildrenQuery<'b, 'a, C, T, K>
+where
+    T: CrudOperations<T> + Transaction<T> + RowMapper<T>,
+{
+    builder: &'b mut SelectQueryBuilder<'a, T>,
+    relation: Relation<C, T, K>,
+}
+
+impl<C, T, K> WithChildrenQuery<'_, '_, C, T, K>
+where
+    C: CrudOperations<C> + RowMapper<C>,
+    T: CrudOperations<T> + Transaction<T> + RowMapper<T>,
+    K: FieldValue + for<'k> QueryParameter<'k> + PartialEq,
+{
+    /// Launches the generated query to the database pointed by the selected
+    /// datasource, and the one that retrieves the children of the entities
+    pub async fn query(
+        self,
+    ) -> Result<Vec<(T, Vec<C>)>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        let datasource_name = self.builder._inner.datasource_name;
+        let rows = self.builder._inner.launch().await?;
+
+        self.relation.load_children(rows, datasource_name).await
+    }
+}
+
 impl<'a, T> ops::QueryBuilder<'a, T> for SelectQueryBuilder<'a, T>
 where
     T: Debug + CrudOperations<T> + Transaction<T> + RowMapper<T> + Send,
@@ -436,18 +1050,47 @@ where
         self._inner.order_by(order_by, desc);
         self
     }
+
+    #[inline]
+    fn json_value<Z, Q>(
+        &mut self,
+        column: Z,
+        path: &[&str],
+        op: impl Operator,
+        value: &'a Q,
+    ) -> &mut Self
+    where
+        Z: FieldIdentifier<T>,
+        Q: QueryParameter<'a>,
+    {
+        self._inner.json_value(column, path, op, value);
+        self
+    }
+
+    #[inline]
+    fn json_contains<Z, Q>(&mut self, column: Z, value: &'a Q) -> &mut Self
+    where
+        Z: FieldIdentifier<T>,
+        Q: QueryParameter<'a>,
+    {
+        self._inner.json_contains(column, value);
+        self
+    }
 }
 
 /// Contains the specific database operations of the *UPDATE* SQL statements.
 ///  
 /// * `set` - To construct a new `SET` clause to determine the columns to
-/// update with the provided values
+///   update with the provided values
+/// * `with_updated_at` - To automatically set the current time on the given
+///   column whenever a `SET` clause is generated
 #[derive(Debug, Clone)]
 pub struct UpdateQueryBuilder<'a, T>
 where
     T: CrudOperations<T> + Transaction<T> + RowMapper<T>,
 {
     _inner: QueryBuilder<'a, T>,
+    updated_at: Option<(&'a str, bool)>,
 }
 
 impl<'a, T> UpdateQueryBuilder<'a, T>
@@ -461,9 +1104,21 @@ where
                 Query::new(format!("UPDATE {table_schema_data}")),
                 datasource_name,
             ),
+            updated_at: None,
         }
     }
 
+    /// Makes the `SET` clause to also update the `column` with the current UTC time,
+    /// unless it's explicitly provided within the updated columns.
+    ///
+    /// This is how the entities with a field annotated with `#[updated_at]` keep
+    /// it up to date, where `with_time_zone` tells if the field is a `DateTime<Utc>`
+    /// or a `NaiveDateTime`
+    pub fn with_updated_at(mut self, column: &'a str, with_time_zone: bool) -> Self {
+        self.updated_at = Some((column, with_time_zone));
+        self
+    }
+
     /// Launches the generated query to the database pointed by the
     /// selected datasource
     #[inline]
@@ -507,6 +1162,15 @@ where
             self._inner.query.params.push(&column.1);
         }
 
+        if let Some((updated_at, with_time_zone)) = self.updated_at {
+            if !columns.iter().any(|column| column.0.as_str() == updated_at) {
+                set_clause.push_str(&format!(
+                    ", {updated_at} = {}",
+                    current_timestamp_sql(self._inner.datasource_name, with_time_zone)
+                ));
+            }
+        }
+
         self._inner.query.sql.push_str(&set_clause);
         self
     }
@@ -573,6 +1237,169 @@ where
         self._inner.order_by(order_by, desc);
         self
     }
+
+    #[inline]
+    fn json_value<Z, Q>(
+        &mut self,
+        column: Z,
+        path: &[&str],
+        op: impl Operator,
+        value: &'a Q,
+    ) -> &mut Self
+    where
+        Z: FieldIdentifier<T>,
+        Q: QueryParameter<'a>,
+    {
+        self._inner.json_value(column, path, op, value);
+        self
+    }
+
+    #[inline]
+    fn json_contains<Z, Q>(&mut self, column: Z, value: &'a Q) -> &mut Self
+    where
+        Z: FieldIdentifier<T>,
+        Q: QueryParameter<'a>,
+    {
+        self._inner.json_contains(column, value);
+        self
+    }
+}
+
+/// The position right after the `SELECT` keyword of the top level query (and its
+/// `DISTINCT` or `ALL` quantifier), where a `TOP` clause must be placed. The comments,
+/// the literals and the subqueries (like the ones of a `WITH` clause) are skipped
+#[cfg(feature = "mssql")]
+fn top_level_select_end(sql: &str) -> Option<usize> {
+    let bytes = sql.as_bytes();
+    let mut depth = 0;
+    let mut idx = 0;
+
+    // The end of the construct (comment or literal) that starts at `idx`, if any
+    let skipped_until = |idx: usize, open: &str, close: &str| -> Option<usize> {
+        if !sql.get(idx..).is_some_and(|rest| rest.starts_with(open)) {
+            return None;
+        }
+        let start = idx + open.len();
+        Some(
+            sql[start..]
+                .find(close)
+                .map_or(sql.len(), |end| start + end + close.len()),
+        )
+    };
+
+    while idx < bytes.len() {
+        let skipped = skipped_until(idx, "--", "\n")
+            .or_else(|| skipped_until(idx, "/*", "*/"))
+            .or_else(|| skipped_until(idx, "'", "'"))
+            .or_else(|| skipped_until(idx, "\"", "\""))
+            .or_else(|| skipped_until(idx, "[", "]"));
+        if let Some(end) = skipped {
+            idx = end;
+            continue;
+        }
+
+        match bytes[idx] {
+            b'(' => depth += 1,
+            b')' => depth -= 1,
+            _ if depth == 0 && is_keyword_at(sql, idx, "SELECT") => {
+                let end = idx + "SELECT".len();
+                let quantifier_start = sql.len() - sql[end..].trim_start().len();
+                return Some(
+                    ["DISTINCT", "ALL"]
+                        .into_iter()
+                        .find(|quantifier| is_keyword_at(sql, quantifier_start, quantifier))
+                        .map_or(end, |quantifier| quantifier_start + quantifier.len()),
+                );
+            }
+            _ => {}
+        }
+        idx += 1;
+    }
+
+    None
+}
+
+/// Checks if the given keyword (case insensitive) is placed at `idx` as a whole word
+#[cfg(feature = "mssql")]
+fn is_keyword_at(sql: &str, idx: usize, keyword: &str) -> bool {
+    let is_word_char = |c: u8| c.is_ascii_alphanumeric() || c == b'_';
+
+    sql.get(idx..idx + keyword.len())
+        .is_some_and(|word| word.eq_ignore_ascii_case(keyword))
+        && (idx == 0 || !is_word_char(sql.as_bytes()[idx - 1]))
+        && !sql
+            .as_bytes()
+            .get(idx + keyword.len())
+            .is_some_and(|c| is_word_char(*c))
+}
+
+#[cfg(all(test, feature = "mssql"))]
+mod top_level_select_tests {
+    use super::top_level_select_end;
+
+    /// The `TOP` clause is placed after the `SELECT` of the top level query, even
+    /// if it's preceded by comments or common table expressions
+    #[test]
+    fn test_top_level_select_end() {
+        let top = |sql: &str| {
+            let mut sql = sql.to_string();
+            sql.insert_str(top_level_select_end(&sql).unwrap(), " TOP (1)");
+            sql
+        };
+
+        assert_eq!(
+            top("SELECT * FROM league WHERE id = $1"),
+            "SELECT TOP (1) * FROM league WHERE id = $1"
+        );
+        assert_eq!(
+            top("select distinct region FROM league"),
+            "select distinct TOP (1) region FROM league"
+        );
+        assert_eq!(
+            top("-- The SELECT of the leagues\n/* SELECT */ SELECT * FROM league"),
+            "-- The SELECT of the leagues\n/* SELECT */ SELECT TOP (1) * FROM league"
+        );
+        assert_eq!(
+            top(
+                "WITH korea AS (SELECT * FROM league WHERE region = 'SELECT') \
+                SELECT * FROM korea"
+            ),
+            "WITH korea AS (SELECT * FROM league WHERE region = 'SELECT') \
+                SELECT TOP (1) * FROM korea"
+        );
+        assert_eq!(
+            top("SELECT selected, [select] FROM poll"),
+            "SELECT TOP (1) selected, [select] FROM poll"
+        );
+        assert_eq!(top_level_select_end("UPDATE league SET selected = 1"), None);
+    }
+}
+
+/// Returns the SQL expression that evaluates to the current UTC time in the
+/// database targeted by the selected datasource.
+///
+/// `with_time_zone` selects the expression for the columns that store the offset
+/// along with the timestamp (`DateTime<Utc>`), instead of a plain `NaiveDateTime`.
+/// This is the value that the `#[updated_at]` and `#[soft_delete]` columns receive
+pub fn current_timestamp_sql(datasource_name: &str, with_time_zone: bool) -> &'static str {
+    match get_database_config(datasource_name).get_db_type() {
+        #[cfg(feature = "postgres")]
+        DatabaseType::PostgreSql => {
+            if with_time_zone {
+                "CURRENT_TIMESTAMP"
+            } else {
+                "(CURRENT_TIMESTAMP AT TIME ZONE 'UTC')"
+            }
+        }
+        #[cfg(feature = "mssql")]
+        DatabaseType::SqlServer => {
+            if with_time_zone {
+                "SYSDATETIMEOFFSET()"
+            } else {
+                "SYSUTCDATETIME()"
+            }
+        }
+    }
 }
 
 /// Contains the specific database operations associated with the
@@ -602,6 +1429,22 @@ where
         }
     }
 
+    /// Makes the query to just mark the matching rows as deleted, setting the `column`
+    /// with the current UTC timestamp instead of removing them from the table. The rows
+    /// already marked as deleted keep their original timestamp.
+    ///
+    /// This is how the entities with a field annotated with `#[soft_delete]` are deleted
+    /// through the query builder, so they can only be permanently removed with the
+    /// builder of the `force_delete_query` operation
+    pub fn with_soft_delete(mut self, column: &str, with_time_zone: bool) -> Self {
+        self._inner.query.sql = format!(
+            "{} SET {column} = COALESCE({column}, {})",
+            self._inner.query.sql.replacen("DELETE FROM", "UPDATE", 1),
+            current_timestamp_sql(self._inner.datasource_name, with_time_zone)
+        );
+        self
+    }
+
     /// Launches the generated query to the database pointed by the
     /// selected datasource
     #[inline]
@@ -673,4 +1516,30 @@ where
         self._inner.order_by(order_by, desc);
         self
     }
+
+    #[inline]
+    fn json_value<Z, Q>(
+        &mut self,
+        column: Z,
+        path: &[&str],
+        op: impl Operator,
+        value: &'a Q,
+    ) -> &mut Self
+    where
+        Z: FieldIdentifier<T>,
+        Q: QueryParameter<'a>,
+    {
+        self._inner.json_value(column, path, op, value);
+        self
+    }
+
+    #[inline]
+    fn json_contains<Z, Q>(&mut self, column: Z, value: &'a Q) -> &mut Self
+    where
+        Z: FieldIdentifier<T>,
+        Q: QueryParameter<'a>,
+    {
+        self._inner.json_contains(column, value);
+        self
+    }
 }
@@ -0,0 +1,505 @@
+//! The foreign key relations between the entities, used to eager load the parents
+//! of a collection of children (or the children of a collection of parents) with
+//! just one query, instead of searching them one by one.
+//!
+//! The `CanyonCrud` derive generates a `<Entity>Relation` type for every entity
+//! with `#[foreign_key]` fields, with a [`Relation`] constant named as every one
+//! of those fields, which are used by the [`crate::query_elements::query_builder::SelectQueryBuilder`]:
+//!
+//! ```ignore
+//! // One query for the tournaments, and another one for all their leagues
+//! let tournaments: Vec<(Tournament, Option<League>)> = Tournament::select_query()
+//!     .with(TournamentRelation::league)
+//!     .query()
+//!     .await?;
+//!
+//! // One query for the leagues, and another one for all their tournaments
+//! let leagues: Vec<(League, Vec<Tournament>)> = League::select_query()
+//!     .with_children(TournamentRelation::league)
+//!     .query()
+//!     .await?;
+//! ```
+//!
+//! The parents can also declare their relations with the `#[has_many(...)]` and the
+//! `#[many_to_many(...)]` annotations, which generates the accessors of the related
+//! entities, and the operations that attach and detach them on the join tables:
+//!
+//! ```ignore
+//! #[derive(CanyonCrud, CanyonMapper, ForeignKeyable, Fields)]
+//! #[canyon_entity]
+//! #[has_many(Tournament)] // The `league` foreign key of the tournaments references it
+//! #[many_to_many(Region, through = "league_region")]
+//! pub struct League { /* ... */ }
+//!
+//! let tournaments: Vec<Tournament> = league.tournaments().await?;
+//! league.attach_regions(&[&korea]).await?;
+//! let regions: Vec<Region> = league.regions().await?;
+//! ```
+use std::marker::PhantomData;
+
+use crate::bounds::{ForeignKeyable, QueryParameter};
+use crate::crud::CrudOperations;
+use crate::errors::MappingError;
+use crate::hooks::run_after_load_hooks;
+use crate::mapper::RowMapper;
+use crate::rows::CanyonRows;
+use crate::types::FieldValue;
+
+/// The maximum number of keys sent on the `IN (...)` clause of a single query.
+/// SQL Server doesn't allow more than 2100 parameters
+const MAX_KEYS_PER_QUERY: usize = 1000;
+
+/// The entities that can be related with others through the join tables of the
+/// `#[many_to_many]` relations. It's implemented by the `CanyonCrud` derive for
+/// the entities with a single column primary key
+pub trait RelatedEntity {
+    /// The table of the entity, along with its schema (if declared)
+    const TABLE: &'static str;
+    /// The column of the primary key
+    const PRIMARY_KEY: &'static str;
+    /// The conditions appended to the searches of the entity (like the one that discards
+    /// the rows marked as deleted), starting with `AND`
+    const FILTER: &'static str;
+
+    /// The type of the primary key
+    type PrimaryKey;
+
+    /// The value of the primary key
+    fn primary_key(&self) -> &dyn QueryParameter<'_>;
+
+    /// The value of the primary key, with its own type
+    fn primary_key_value(&self) -> &Self::PrimaryKey;
+}
+
+/// The foreign key relation of the child entity `T` with its parent entity `P`,
+/// declared with the `#[foreign_key]` annotation of a field of type `K`
+pub struct Relation<T, P, K> {
+    parent_table: &'static str,
+    parent_column: &'static str,
+    child_table: &'static str,
+    foreign_key_column: &'static str,
+    child_filter: &'static str,
+    foreign_key: fn(&T) -> &K,
+    _parent: PhantomData<fn() -> P>,
+}
+
+impl<T, P, K> Relation<T, P, K> {
+    /// Creates a new relation.
+    ///
+    /// * `parent_table` - The table of the parent entity
+    /// * `parent_column` - The column of the parent table referenced by the foreign key
+    /// * `child_table` - The table of the child entity
+    /// * `foreign_key_column` - The column of the child table with the foreign key, as
+    ///   it's written on the statements
+    /// * `child_filter` - The conditions appended to the search of the children (like
+    ///   the one that discards the rows marked as deleted), starting with `AND`
+    /// * `foreign_key` - Retrieves the value of the foreign key of a child
+    pub const fn new(
+        parent_table: &'static str,
+        parent_column: &'static str,
+        child_table: &'static str,
+        foreign_key_column: &'static str,
+        child_filter: &'static str,
+        foreign_key: fn(&T) -> &K,
+    ) -> Self {
+        Self {
+            parent_table,
+            parent_column,
+            child_table,
+            foreign_key_column,
+            child_filter,
+            foreign_key,
+            _parent: PhantomData,
+        }
+    }
+
+    /// The value of the foreign key of the `child`
+    pub fn foreign_key<'a>(&self, child: &'a T) -> &'a K {
+        (self.foreign_key)(child)
+    }
+}
+
+impl<T, P, K> Clone for Relation<T, P, K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, P, K> Copy for Relation<T, P, K> {}
+
+impl<T, P, K> Relation<T, P, K>
+where
+    T: CrudOperations<T> + RowMapper<T>,
+    P: CrudOperations<P> + RowMapper<P>,
+    K: FieldValue + for<'k> QueryParameter<'k> + PartialEq,
+{
+    /// Loads the parents of the given children with one query (per every thousand of
+    /// distinct foreign keys), pairing every child with its parent, or with `None`
+    /// if the parent doesn't exist. The parents shared by several children are cloned
+    pub async fn load_parents(
+        &self,
+        children: Vec<T>,
+        datasource_name: &str,
+    ) -> Result<Vec<(T, Option<P>)>, Box<dyn std::error::Error + Sync + Send + 'static>>
+    where
+        P: Clone,
+    {
+        let mut keys: Vec<&K> = Vec::new();
+        for child in children.iter() {
+            let key = self.foreign_key(child);
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+
+        let mut parents: Vec<(K, P)> = Vec::new();
+        for chunk in keys.chunks(MAX_KEYS_PER_QUERY) {
+            let stmt = format!(
+                "SELECT * FROM {} WHERE \"{}\" IN ({})",
+                self.parent_table,
+                self.parent_column,
+                placeholders(chunk.len())
+            );
+            let params = chunk
+                .iter()
+                .map(|key| *key as &dyn QueryParameter<'_>)
+                .collect::<Vec<_>>();
+            let rows = P::query(stmt, params, datasource_name).await?;
+            parents.extend(map_rows_with_keys(rows, self.parent_column).await?);
+        }
+
+        Ok(children
+            .into_iter()
+            .map(|child| {
+                let key = self.foreign_key(&child);
+                let parent = parents
+                    .iter()
+                    .find(|(parent_key, _)| parent_key == key)
+                    .map(|(_, parent)| parent.clone());
+                (child, parent)
+            })
+            .collect())
+    }
+
+    /// Loads the children of the parents retrieved on the given `rows` with one query
+    /// (per every thousand of parents), pairing every parent with its children
+    pub async fn load_children(
+        &self,
+        rows: CanyonRows<P>,
+        datasource_name: &str,
+    ) -> Result<Vec<(P, Vec<T>)>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        let parents = map_rows_with_keys::<P, K>(rows, self.parent_column).await?;
+
+        let mut children: Vec<T> = Vec::new();
+        for chunk in parents.chunks(MAX_KEYS_PER_QUERY) {
+            let stmt = format!(
+                "SELECT * FROM {} WHERE {} IN ({}){}",
+                self.child_table,
+                self.foreign_key_column,
+                placeholders(chunk.len()),
+                self.child_filter
+            );
+            let params = chunk
+                .iter()
+                .map(|(key, _)| key as &dyn QueryParameter<'_>)
+                .collect::<Vec<_>>();
+            let results = T::query(stmt, params, datasource_name)
+                .await?
+                .into_results::<T>()?;
+            children.extend(run_after_load_hooks(results).await?);
+        }
+
+        let mut parents_with_children: Vec<(P, Vec<T>)> = Vec::with_capacity(parents.len());
+        let mut keys: Vec<K> = Vec::with_capacity(parents.len());
+        for (key, parent) in parents {
+            keys.push(key);
+            parents_with_children.push((parent, Vec::new()));
+        }
+        for child in children {
+            let key = self.foreign_key(&child);
+            if let Some(idx) = keys.iter().position(|parent_key| parent_key == key) {
+                parents_with_children[idx].1.push(child);
+            }
+        }
+
+        Ok(parents_with_children)
+    }
+
+    /// Searches the children of the given parent, the ones whose foreign key references
+    /// the column of the parent
+    pub async fn search_children(
+        &self,
+        parent: &P,
+        datasource_name: &str,
+    ) -> Result<Vec<T>, Box<dyn std::error::Error + Sync + Send + 'static>>
+    where
+        P: ForeignKeyable<P>,
+    {
+        let key = parent.get_fk_column(self.parent_column).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "The column `{}` referenced by the children isn't mapped by the parent",
+                    self.parent_column
+                ),
+            )
+        })?;
+        let stmt = format!(
+            "SELECT * FROM {} WHERE {} = $1{}",
+            self.child_table, self.foreign_key_column, self.child_filter
+        );
+        let results = T::query(stmt, [key], datasource_name)
+            .await?
+            .into_results::<T>()?;
+
+        run_after_load_hooks(results).await
+    }
+}
+
+/// The many to many relation of the entity `T` with the entity `O`, through a join table
+/// with the column `foreign_key`, that references the primary key of `T`, and the column
+/// `related_key`, that references the primary key of `O`
+pub struct ManyToMany<T, O> {
+    join_table: &'static str,
+    foreign_key: &'static str,
+    related_key: &'static str,
+    _entities: PhantomData<fn() -> (T, O)>,
+}
+
+impl<T, O> ManyToMany<T, O> {
+    /// Creates a new relation through the given join table and its columns
+    pub const fn new(
+        join_table: &'static str,
+        foreign_key: &'static str,
+        related_key: &'static str,
+    ) -> Self {
+        Self {
+            join_table,
+            foreign_key,
+            related_key,
+            _entities: PhantomData,
+        }
+    }
+}
+
+impl<T, O> Clone for ManyToMany<T, O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, O> Copy for ManyToMany<T, O> {}
+
+impl<T, O> ManyToMany<T, O>
+where
+    T: RelatedEntity,
+    O: RelatedEntity + CrudOperations<O> + RowMapper<O>,
+    O::PrimaryKey: FieldValue + PartialEq + for<'q> QueryParameter<'q>,
+{
+    /// Searches the entities related with the given one on the join table
+    pub async fn load(
+        &self,
+        entity: &T,
+        datasource_name: &str,
+    ) -> Result<Vec<O>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        let stmt = format!(
+            "SELECT * FROM {} WHERE \"{}\" IN (SELECT \"{}\" FROM {} WHERE \"{}\" = $1){}",
+            O::TABLE,
+            O::PRIMARY_KEY,
+            self.related_key,
+            self.join_table,
+            self.foreign_key,
+            O::FILTER
+        );
+        let results = O::query(stmt, [entity.primary_key()], datasource_name)
+            .await?
+            .into_results::<O>()?;
+
+        run_after_load_hooks(results).await
+    }
+
+    /// Relates the given entities with `entity` on the join table. The ones that were
+    /// already related aren't duplicated
+    pub async fn attach(
+        &self,
+        entity: &T,
+        related: &[&O],
+        datasource_name: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send + 'static>> {
+        let current = self.related_keys(entity, datasource_name).await?;
+        let missing = related
+            .iter()
+            .map(|related| related.primary_key_value())
+            .filter(|key| !current.contains(key))
+            .collect::<Vec<&O::PrimaryKey>>();
+
+        self.insert(entity, &missing, datasource_name).await
+    }
+
+    /// Removes the relations of the given entities with `entity` from the join table
+    pub async fn detach(
+        &self,
+        entity: &T,
+        related: &[&O],
+        datasource_name: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send + 'static>> {
+        let keys = related
+            .iter()
+            .map(|related| related.primary_key_value())
+            .collect::<Vec<&O::PrimaryKey>>();
+
+        self.delete(entity, &keys, datasource_name).await
+    }
+
+    /// Replaces the relations of `entity` on the join table, so it's only related
+    /// with the given entities.
+    ///
+    /// Just the relations that changed are inserted or deleted. The new ones are
+    /// inserted first, so a failure never loses the relations that must be kept
+    pub async fn sync(
+        &self,
+        entity: &T,
+        related: &[&O],
+        datasource_name: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send + 'static>> {
+        let current = self.related_keys(entity, datasource_name).await?;
+        let keys = related
+            .iter()
+            .map(|related| related.primary_key_value())
+            .collect::<Vec<&O::PrimaryKey>>();
+
+        let missing = keys
+            .iter()
+            .copied()
+            .filter(|key| !current.contains(key))
+            .collect::<Vec<&O::PrimaryKey>>();
+        self.insert(entity, &missing, datasource_name).await?;
+
+        let outdated = current
+            .iter()
+            .filter(|key| !keys.contains(key))
+            .collect::<Vec<&O::PrimaryKey>>();
+        self.delete(entity, &outdated, datasource_name).await
+    }
+
+    /// The primary keys of the entities currently related with `entity` on the join table
+    async fn related_keys(
+        &self,
+        entity: &T,
+        datasource_name: &str,
+    ) -> Result<Vec<O::PrimaryKey>, Box<dyn std::error::Error + Sync + Send + 'static>> {
+        let stmt = format!(
+            "SELECT \"{}\" FROM {} WHERE \"{}\" = $1",
+            self.related_key, self.join_table, self.foreign_key
+        );
+        let rows = O::query(stmt, [entity.primary_key()], datasource_name).await?;
+
+        column_values(&rows, self.related_key)
+    }
+
+    /// Inserts the rows that relates the entities with the given keys with `entity`
+    /// on the join table
+    async fn insert(
+        &self,
+        entity: &T,
+        keys: &[&O::PrimaryKey],
+        datasource_name: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send + 'static>> {
+        for chunk in keys.chunks(MAX_KEYS_PER_QUERY) {
+            let values = (0..chunk.len())
+                .map(|idx| format!("($1, ${})", idx + 2))
+                .collect::<Vec<String>>()
+                .join(", ");
+            let stmt = format!(
+                "INSERT INTO {} (\"{}\", \"{}\") VALUES {values}",
+                self.join_table, self.foreign_key, self.related_key
+            );
+            let mut params = vec![entity.primary_key()];
+            params.extend(chunk.iter().map(|key| *key as &dyn QueryParameter<'_>));
+            O::query(stmt, params, datasource_name).await?;
+        }
+        Ok(())
+    }
+
+    /// Deletes the rows that relates the entities with the given keys with `entity`
+    /// from the join table
+    async fn delete(
+        &self,
+        entity: &T,
+        keys: &[&O::PrimaryKey],
+        datasource_name: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send + 'static>> {
+        for chunk in keys.chunks(MAX_KEYS_PER_QUERY) {
+            let stmt = format!(
+                "DELETE FROM {} WHERE \"{}\" = $1 AND \"{}\" IN ({})",
+                self.join_table,
+                self.foreign_key,
+                self.related_key,
+                placeholders_from(2, chunk.len())
+            );
+            let mut params = vec![entity.primary_key()];
+            params.extend(chunk.iter().map(|key| *key as &dyn QueryParameter<'_>));
+            O::query(stmt, params, datasource_name).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Maps the rows into entities, along with the value of the given column of every row
+async fn map_rows_with_keys<E, K>(
+    rows: CanyonRows<E>,
+    column: &str,
+) -> Result<Vec<(K, E)>, Box<dyn std::error::Error + Sync + Send + 'static>>
+where
+    E: CrudOperations<E> + RowMapper<E>,
+    K: FieldValue,
+{
+    let keys: Vec<K> = column_values(&rows, column)?;
+    let entities = run_after_load_hooks(rows.into_results::<E>()?).await?;
+
+    Ok(keys.into_iter().zip(entities).collect())
+}
+
+/// Retrieves the value of the given column of every row
+fn column_values<E, K>(
+    rows: &CanyonRows<E>,
+    column: &str,
+) -> Result<Vec<K>, Box<dyn std::error::Error + Sync + Send + 'static>>
+where
+    K: FieldValue,
+{
+    let values: Vec<K> = match rows {
+        #[cfg(feature = "postgres")]
+        CanyonRows::Postgres(v) => v
+            .iter()
+            .map(|row| {
+                K::from_postgres_row(row, column)
+                    .map_err(|e| MappingError::from_postgres_row::<K>(row, column, e))
+            })
+            .collect::<Result<Vec<K>, MappingError>>()?,
+        #[cfg(feature = "mssql")]
+        CanyonRows::Tiberius(v) => v
+            .iter()
+            .map(|row| {
+                K::from_sqlserver_row(row, column)
+                    .map_err(|e| MappingError::from_sqlserver_row::<K>(row, column, e))
+            })
+            .collect::<Result<Vec<K>, MappingError>>()?,
+        _ => return Err("The related entities can't be loaded from these rows".into()),
+    };
+
+    Ok(values)
+}
+
+/// The comma separated list of `count` placeholders
+fn placeholders(count: usize) -> String {
+    placeholders_from(1, count)
+}
+
+/// The comma separated list of `count` consecutive placeholders, starting at `$first`
+fn placeholders_from(first: usize, count: usize) -> String {
+    (first..first + count)
+        .map(|idx| format!("${idx}"))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
@@ -1,5 +1,12 @@
-use crate::crud::Transaction;
+use crate::bounds::{Column, ColumnType};
+use crate::errors::MappingError;
 use crate::mapper::RowMapper;
+#[cfg(feature = "mock")]
+use crate::mock::MockRow;
+use crate::types::FieldValue;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::error::Error;
 use std::marker::PhantomData;
 
 /// Lightweight wrapper over the collection of results of the different crates
@@ -13,7 +20,11 @@ pub enum CanyonRows<T> {
     Postgres(Vec<tokio_postgres::Row>),
     #[cfg(feature = "mssql")]
     Tiberius(Vec<tiberius::Row>),
-    UnusableTypeMarker(PhantomData<T>),
+    #[cfg(feature = "mock")]
+    Mock(Vec<MockRow>),
+    /// Just tags the rows with the type they're mapped into, without owning any `T`,
+    /// so the rows are `Send` whatever the type is
+    UnusableTypeMarker(PhantomData<fn() -> T>),
 }
 
 impl<T> CanyonRows<T> {
@@ -33,20 +44,36 @@ impl<T> CanyonRows<T> {
         }
     }
 
-    /// Consumes `self` and returns the wrapped [`std::vec::Vec`] with the instances of T
-    pub fn into_results<Z: RowMapper<T>>(self) -> Vec<T>
-    where
-        T: Transaction<T>,
-    {
+    /// Consumes `self` and returns the wrapped [`std::vec::Vec`] with the instances of T,
+    /// or the [`MappingError`] of the first row that can't be mapped
+    pub fn into_results<Z: RowMapper<T>>(self) -> Result<Vec<T>, MappingError> {
         match self {
             #[cfg(feature = "postgres")]
             Self::Postgres(v) => v.iter().map(|row| Z::deserialize_postgresql(row)).collect(),
             #[cfg(feature = "mssql")]
             Self::Tiberius(v) => v.iter().map(|row| Z::deserialize_sqlserver(row)).collect(),
+            #[cfg(feature = "mock")]
+            Self::Mock(v) => v.into_iter().map(Z::deserialize_mock).collect(),
             _ => panic!("This branch will never ever should be reachable"),
         }
     }
 
+    /// Consumes `self` and returns the wrapped rows as [`DynamicRow`]s, or an error
+    /// for the rows of the mock datasources, that hold instances instead of columns
+    pub fn into_dynamic_rows(self) -> Result<Vec<DynamicRow>, Box<dyn Error + Send + Sync>> {
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Postgres(v) => Ok(v.into_iter().map(DynamicRow::Postgres).collect()),
+            #[cfg(feature = "mssql")]
+            Self::Tiberius(v) => Ok(v.into_iter().map(DynamicRow::Tiberius).collect()),
+            #[cfg(feature = "mock")]
+            Self::Mock(_) => {
+                Err("The rows of the mock datasources can't be read as dynamic rows".into())
+            }
+            _ => Err("The rows don't belong to any supported database".into()),
+        }
+    }
+
     /// Returns the number of elements present on the wrapped collection
     pub fn len(&self) -> usize {
         match self {
@@ -54,6 +81,8 @@ impl<T> CanyonRows<T> {
             Self::Postgres(v) => v.len(),
             #[cfg(feature = "mssql")]
             Self::Tiberius(v) => v.len(),
+            #[cfg(feature = "mock")]
+            Self::Mock(v) => v.len(),
             _ => panic!("This branch will never ever should be reachable"),
         }
     }
@@ -65,7 +94,289 @@ impl<T> CanyonRows<T> {
             Self::Postgres(v) => v.is_empty(),
             #[cfg(feature = "mssql")]
             Self::Tiberius(v) => v.is_empty(),
+            #[cfg(feature = "mock")]
+            Self::Mock(v) => v.is_empty(),
             _ => panic!("This branch will never ever should be reachable"),
         }
     }
 }
+
+/// A row of the results of any query, whose columns are retrieved by name, without
+/// mapping it into a type. It's returned by [`crate::crud::query_rows`].
+///
+/// ```ignore
+/// let rows = query_rows("SELECT id, name FROM league", &[], "").await?;
+/// let name: String = rows[0].get("name")?;
+/// let image_url: Option<String> = rows[0].get("image_url")?;
+/// ```
+#[derive(Debug)]
+pub enum DynamicRow {
+    #[cfg(feature = "postgres")]
+    Postgres(tokio_postgres::Row),
+    #[cfg(feature = "mssql")]
+    Tiberius(tiberius::Row),
+}
+
+impl DynamicRow {
+    /// Retrieves the value of the column with the given name, as any type that can
+    /// be the type of the field of an entity (so `Option<T>` for the nullable ones)
+    pub fn get<T: FieldValue>(&self, column: &str) -> Result<T, Box<dyn Error + Send + Sync>> {
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Postgres(row) => T::from_postgres_row(row, column),
+            #[cfg(feature = "mssql")]
+            Self::Tiberius(row) => T::from_sqlserver_row(row, column),
+            #[cfg(not(any(feature = "postgres", feature = "mssql")))]
+            _ => match *self {},
+        }
+    }
+
+    /// Retrieves the value of the column with the given name of a PostgreSQL row, as
+    /// any type that implements `FromSql`. Fails with a [`MappingError`] if the value
+    /// can't be converted, or if it's the row of another database
+    #[cfg(feature = "postgres")]
+    pub fn get_postgres<'a, Output>(&'a self, col_name: &str) -> Result<Output, MappingError>
+    where
+        Output: tokio_postgres::types::FromSql<'a>,
+    {
+        self.get_postgres_opt(col_name)?.ok_or_else(|| {
+            MappingError::new(
+                col_name,
+                std::any::type_name::<Output>(),
+                self.sql_type(col_name),
+                format!("Unexpected NULL value on the non optional `{col_name}` column").into(),
+            )
+        })
+    }
+
+    /// Same as [`DynamicRow::get_postgres`], but the `NULL` values are retrieved as `None`
+    #[cfg(feature = "postgres")]
+    pub fn get_postgres_opt<'a, Output>(
+        &'a self,
+        col_name: &str,
+    ) -> Result<Option<Output>, MappingError>
+    where
+        Output: tokio_postgres::types::FromSql<'a>,
+    {
+        match self {
+            Self::Postgres(row) => row
+                .try_get::<&str, Option<Output>>(col_name)
+                .map_err(|e| MappingError::from_postgres_row::<Output>(row, col_name, e.into())),
+            #[cfg(feature = "mssql")]
+            Self::Tiberius(row) => Err(MappingError::from_sqlserver_row::<Output>(
+                row,
+                col_name,
+                "Not a PostgreSQL row".into(),
+            )),
+        }
+    }
+
+    /// Retrieves the value of the column with the given name of a SQL Server row, as
+    /// any type that implements `FromSql`. Fails with a [`MappingError`] if the value
+    /// can't be converted, or if it's the row of another database
+    #[cfg(feature = "mssql")]
+    pub fn get_mssql<'a, Output>(&'a self, col_name: &str) -> Result<Output, MappingError>
+    where
+        Output: tiberius::FromSql<'a>,
+    {
+        self.get_mssql_opt(col_name)?.ok_or_else(|| {
+            MappingError::new(
+                col_name,
+                std::any::type_name::<Output>(),
+                self.sql_type(col_name),
+                format!("Unexpected NULL value on the non optional `{col_name}` column").into(),
+            )
+        })
+    }
+
+    /// Same as [`DynamicRow::get_mssql`], but the `NULL` values are retrieved as `None`
+    #[cfg(feature = "mssql")]
+    pub fn get_mssql_opt<'a, Output>(
+        &'a self,
+        col_name: &str,
+    ) -> Result<Option<Output>, MappingError>
+    where
+        Output: tiberius::FromSql<'a>,
+    {
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Postgres(row) => Err(MappingError::from_postgres_row::<Output>(
+                row,
+                col_name,
+                "Not a SQL Server row".into(),
+            )),
+            Self::Tiberius(row) => row
+                .try_get::<Output, &str>(col_name)
+                .map_err(|e| MappingError::from_sqlserver_row::<Output>(row, col_name, e.into())),
+        }
+    }
+
+    /// The names of the columns of the row, in the order of the query
+    pub fn column_names(&self) -> Vec<&str> {
+        self.columns().into_iter().map(|c| c.name).collect()
+    }
+
+    /// The columns of the row, with their names and SQL types, in the order of the query
+    pub fn columns(&self) -> Vec<Column<'_>> {
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Postgres(row) => row
+                .columns()
+                .iter()
+                .map(|c| Column {
+                    name: c.name(),
+                    type_: ColumnType::Postgres(c.type_().to_owned()),
+                })
+                .collect(),
+            #[cfg(feature = "mssql")]
+            Self::Tiberius(row) => row
+                .columns()
+                .iter()
+                .map(|c| Column {
+                    name: c.name(),
+                    type_: ColumnType::SqlServer(c.column_type()),
+                })
+                .collect(),
+            #[cfg(not(any(feature = "postgres", feature = "mssql")))]
+            _ => match *self {},
+        }
+    }
+
+    /// The name of the SQL type of the column with the given name, if the row has it
+    #[cfg(any(feature = "postgres", feature = "mssql"))]
+    fn sql_type(&self, col_name: &str) -> Option<String> {
+        self.columns()
+            .into_iter()
+            .find(|c| c.name == col_name)
+            .map(|c| match c.type_ {
+                #[cfg(feature = "postgres")]
+                ColumnType::Postgres(ty) => ty.name().to_owned(),
+                #[cfg(feature = "mssql")]
+                ColumnType::SqlServer(ty) => format!("{ty:?}"),
+            })
+    }
+
+    /// Converts every column of the row into a JSON value, keyed by the name of the
+    /// column. The `NULL` values are converted into [`Value::Null`], the temporal
+    /// types into their ISO 8601 representation, the decimals and UUIDs into
+    /// strings and the binary values into arrays of bytes.
+    ///
+    /// Fails if the type of any column doesn't have a conversion
+    pub fn to_map(&self) -> Result<HashMap<String, Value>, Box<dyn Error + Send + Sync>> {
+        match self {
+            #[cfg(feature = "postgres")]
+            Self::Postgres(row) => row
+                .columns()
+                .iter()
+                .enumerate()
+                .map(|(idx, c)| Ok((c.name().to_owned(), json::postgres_value(row, idx)?)))
+                .collect(),
+            #[cfg(feature = "mssql")]
+            Self::Tiberius(row) => row
+                .cells()
+                .map(|(c, data)| Ok((c.name().to_owned(), json::sqlserver_value(data)?)))
+                .collect(),
+            #[cfg(not(any(feature = "postgres", feature = "mssql")))]
+            _ => match *self {},
+        }
+    }
+
+    /// Converts the row into a JSON object, with the values described in
+    /// [`DynamicRow::to_map`]
+    pub fn to_json(&self) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        Ok(Value::Object(self.to_map()?.into_iter().collect()))
+    }
+}
+
+/// The conversions of the values of the columns into JSON values
+mod json {
+    use serde_json::Value;
+    use std::error::Error;
+
+    #[cfg(feature = "postgres")]
+    pub fn postgres_value(
+        row: &tokio_postgres::Row,
+        idx: usize,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+        use tokio_postgres::types::Type;
+
+        let ty = row.columns()[idx].type_();
+        let value = match *ty {
+            Type::BOOL => Value::from(row.try_get::<_, Option<bool>>(idx)?),
+            Type::INT2 => Value::from(row.try_get::<_, Option<i16>>(idx)?),
+            Type::INT4 => Value::from(row.try_get::<_, Option<i32>>(idx)?),
+            Type::INT8 => Value::from(row.try_get::<_, Option<i64>>(idx)?),
+            Type::FLOAT4 => Value::from(row.try_get::<_, Option<f32>>(idx)?),
+            Type::FLOAT8 => Value::from(row.try_get::<_, Option<f64>>(idx)?),
+            Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME => {
+                Value::from(row.try_get::<_, Option<String>>(idx)?)
+            }
+            Type::JSON | Type::JSONB => row.try_get::<_, Option<Value>>(idx)?.into(),
+            Type::DATE => serde_json::to_value(row.try_get::<_, Option<NaiveDate>>(idx)?)?,
+            Type::TIME => serde_json::to_value(row.try_get::<_, Option<NaiveTime>>(idx)?)?,
+            Type::TIMESTAMP => serde_json::to_value(row.try_get::<_, Option<NaiveDateTime>>(idx)?)?,
+            Type::TIMESTAMPTZ => {
+                serde_json::to_value(row.try_get::<_, Option<DateTime<Utc>>>(idx)?)?
+            }
+            Type::UUID => Value::from(
+                row.try_get::<_, Option<uuid::Uuid>>(idx)?
+                    .map(|uuid| uuid.to_string()),
+            ),
+            Type::BYTEA => Value::from(row.try_get::<_, Option<Vec<u8>>>(idx)?),
+            #[cfg(feature = "rust_decimal")]
+            Type::NUMERIC => Value::from(
+                row.try_get::<_, Option<rust_decimal::Decimal>>(idx)?
+                    .map(|decimal| decimal.to_string()),
+            ),
+            // The labels of the native enum types
+            _ if matches!(ty.kind(), tokio_postgres::types::Kind::Enum(_)) => Value::from(
+                row.try_get::<_, Option<crate::enums::EnumLabel>>(idx)?
+                    .map(|label| label.0),
+            ),
+            _ => {
+                return Err(format!(
+                    "The `{}` type of the `{}` column can't be converted into JSON",
+                    ty.name(),
+                    row.columns()[idx].name()
+                )
+                .into())
+            }
+        };
+
+        Ok(value)
+    }
+
+    #[cfg(feature = "mssql")]
+    pub fn sqlserver_value(
+        data: &tiberius::ColumnData<'static>,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+        use tiberius::{ColumnData, FromSql};
+
+        let value = match data {
+            ColumnData::U8(v) => Value::from(*v),
+            ColumnData::I16(v) => Value::from(*v),
+            ColumnData::I32(v) => Value::from(*v),
+            ColumnData::I64(v) => Value::from(*v),
+            ColumnData::F32(v) => Value::from(*v),
+            ColumnData::F64(v) => Value::from(*v),
+            ColumnData::Bit(v) => Value::from(*v),
+            ColumnData::String(v) => Value::from(v.as_deref()),
+            ColumnData::Guid(v) => Value::from(v.map(|guid| guid.to_string())),
+            ColumnData::Binary(v) => Value::from(v.as_deref().map(<[u8]>::to_vec)),
+            ColumnData::Numeric(v) => Value::from(v.map(|numeric| numeric.to_string())),
+            ColumnData::Xml(v) => Value::from(v.as_ref().map(|xml| xml.to_string())),
+            ColumnData::DateTime(_) | ColumnData::SmallDateTime(_) | ColumnData::DateTime2(_) => {
+                serde_json::to_value(NaiveDateTime::from_sql(data)?)?
+            }
+            ColumnData::Date(_) => serde_json::to_value(NaiveDate::from_sql(data)?)?,
+            ColumnData::Time(_) => serde_json::to_value(NaiveTime::from_sql(data)?)?,
+            ColumnData::DateTimeOffset(_) => {
+                serde_json::to_value(DateTime::<FixedOffset>::from_sql(data)?)?
+            }
+        };
+
+        Ok(value)
+    }
+}
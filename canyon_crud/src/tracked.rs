@@ -0,0 +1,121 @@
+//! The partial updates of the entities, that only write the columns of the fields
+//! modified since the entity was loaded.
+//!
+//! The entities annotated with `#[tracked]` implement the [`Trackable`] trait, so
+//! they can be wrapped in a [`Tracked`] to record their changes.
+use std::ops::{Deref, DerefMut};
+
+use async_trait::async_trait;
+
+/// The entities whose modified fields can be detected and updated alone. It's
+/// implemented by the `CanyonCrud` derive macro for the entities annotated with
+/// `#[tracked]`, which must have a primary key and implement `Clone`, and whose
+/// fields must implement `PartialEq`.
+///
+/// The primary key, `#[version]` and `#[updated_at]` fields aren't tracked, since
+/// they're never set from the values of the instance by the updates.
+#[async_trait]
+pub trait Trackable: Clone + Send + Sync {
+    /// The names of the tracked fields whose values differs from the ones of `original`
+    fn changed_fields(&self, original: &Self) -> Vec<&'static str>;
+
+    /// Updates the row of the entity like `update_datasource`, but with a `SET` clause
+    /// with just the columns of the given fields, besides the `#[updated_at]` and
+    /// `#[version]` ones, whose new values are written back into the entity. Nothing
+    /// is done if there's no field
+    async fn update_fields<'a>(
+        &mut self,
+        fields: &'a [&'static str],
+        datasource_name: &'a str,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>>;
+}
+
+/// Wraps an entity to record the fields modified since it was loaded (or since the
+/// last update), so [`Tracked::update`] only writes their columns, and it's a no-op
+/// if nothing changed.
+///
+/// The entity is accessed and modified through `Deref` and `DerefMut`:
+///
+/// ```ignore
+/// let mut league = Tracked::new(League::find_by_pk(&1).await?.unwrap());
+/// league.name = String::from("LEC");
+///
+/// assert_eq!(league.changed_fields(), vec!["name"]);
+/// league.update().await?; // UPDATE league SET "name" = $1 WHERE "id" = $2
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tracked<T: Trackable> {
+    entity: T,
+    original: T,
+}
+
+impl<T: Trackable> Tracked<T> {
+    /// Starts to track the changes of the given entity
+    pub fn new(entity: T) -> Self {
+        Self {
+            original: entity.clone(),
+            entity,
+        }
+    }
+
+    /// The names of the fields modified since the entity was tracked or updated
+    pub fn changed_fields(&self) -> Vec<&'static str> {
+        self.entity.changed_fields(&self.original)
+    }
+
+    /// Returns true if any field was modified since the entity was tracked or updated
+    pub fn is_changed(&self) -> bool {
+        !self.changed_fields().is_empty()
+    }
+
+    /// Updates the columns of the modified fields in the row of the entity, running
+    /// the same hooks and validations than `update`. Nothing is done if there's no
+    /// modified field
+    pub async fn update(&mut self) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        self.update_datasource("").await
+    }
+
+    /// Updates the columns of the modified fields in the row of the entity with the
+    /// specified datasource, running the same hooks and validations than
+    /// `update_datasource`. Nothing is done if there's no modified field
+    pub async fn update_datasource(
+        &mut self,
+        datasource_name: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        let changed_fields = self.changed_fields();
+        if changed_fields.is_empty() {
+            return Ok(());
+        }
+
+        self.entity
+            .update_fields(&changed_fields, datasource_name)
+            .await?;
+        self.original = self.entity.clone();
+        Ok(())
+    }
+
+    /// Stops tracking the changes, returning the entity
+    pub fn into_inner(self) -> T {
+        self.entity
+    }
+}
+
+impl<T: Trackable> From<T> for Tracked<T> {
+    fn from(entity: T) -> Self {
+        Self::new(entity)
+    }
+}
+
+impl<T: Trackable> Deref for Tracked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.entity
+    }
+}
+
+impl<T: Trackable> DerefMut for Tracked<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.entity
+    }
+}
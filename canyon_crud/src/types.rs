@@ -0,0 +1,343 @@
+//! The conversions between the values of the fields of the entities and the values
+//! of the columns of the database.
+//!
+//! The [`CanyonType`] trait allows to use user-defined types (like the `Email(String)`
+//! or `Cents(i64)` newtypes) as the type of the fields of the entities and as query
+//! parameters, by declaring them once how they're stored in the database.
+//!
+//! The [`ColumnValue`] and [`FieldValue`] traits are the ones used by the code generated
+//! by the `CanyonMapper` derive to retrieve the values of the fields from the rows.
+use std::error::Error;
+use std::fmt::Debug;
+
+#[cfg(feature = "mssql")]
+use canyon_connection::tiberius::{self, ColumnData};
+#[cfg(feature = "postgres")]
+use canyon_connection::tokio_postgres::{
+    self,
+    types::{to_sql_checked, IsNull, ToSql, Type},
+};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+#[cfg(feature = "rust_decimal")]
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+#[cfg(feature = "postgres")]
+use crate::bounds::ArrayElement;
+use crate::bounds::QueryParameter;
+
+/// Declares how a user-defined type is stored in the database, so it can be used as
+/// the type of the fields of the entities (along with its `Option`) and as a query
+/// parameter.
+///
+/// The type is sent to the database as the [`QueryParameter`] returned by
+/// [`CanyonType::to_parameter`], and built from the [`ColumnValue`] read from the
+/// columns with [`CanyonType::from_value`].
+///
+/// The migrations create the columns with the declared SQL types, so the implementation
+/// must be annotated with `#[canyon_type]`. The types must be written as they're reported
+/// by the `information_schema` of the database (like `text` or `character varying` on
+/// PostgreSQL, and `nvarchar` on SQL Server), or the migrations will alter the columns
+/// on every run.
+///
+/// ```ignore
+/// #[derive(Debug, Clone, PartialEq)]
+/// pub struct Email(String);
+///
+/// #[canyon_type]
+/// impl CanyonType for Email {
+///     const POSTGRES_TYPE: &'static str = "text";
+///     const SQLSERVER_TYPE: &'static str = "nvarchar(320)";
+///
+///     type Value = String;
+///
+///     fn to_parameter(&self) -> &dyn QueryParameter<'_> {
+///         &self.0
+///     }
+///
+///     fn from_value(value: String) -> Result<Self, Box<dyn Error + Send + Sync>> {
+///         if value.contains('@') {
+///             Ok(Self(value))
+///         } else {
+///             Err(format!("`{value}` isn't a valid email").into())
+///         }
+///     }
+/// }
+/// ```
+pub trait CanyonType: Sized + Debug + Sync + Send {
+    /// The type of the column on PostgreSQL
+    const POSTGRES_TYPE: &'static str;
+    /// The type of the column on SQL Server
+    const SQLSERVER_TYPE: &'static str;
+
+    /// The supported type read from the columns
+    type Value: ColumnValue;
+
+    /// The value sent to the database as a query parameter
+    fn to_parameter(&self) -> &dyn QueryParameter<'_>;
+
+    /// Builds the value from the one read from a column
+    fn from_value(value: Self::Value) -> Result<Self, Box<dyn Error + Send + Sync>>;
+}
+
+impl<'a, T: CanyonType> QueryParameter<'a> for T {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        self.to_parameter().as_postgres_param()
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        self.to_parameter().as_sqlserver_param()
+    }
+}
+impl<'a, T: CanyonType> QueryParameter<'a> for Option<T> {
+    #[cfg(feature = "postgres")]
+    fn as_postgres_param(&self) -> &(dyn ToSql + Sync) {
+        match self {
+            Some(value) => value.to_parameter().as_postgres_param(),
+            None => &Null,
+        }
+    }
+    #[cfg(feature = "mssql")]
+    fn as_sqlserver_param(&self) -> ColumnData<'_> {
+        match self {
+            Some(value) => value.to_parameter().as_sqlserver_param(),
+            None => null_column_data(T::SQLSERVER_TYPE),
+        }
+    }
+}
+
+/// The `NULL` value of a column of any type of PostgreSQL
+#[cfg(feature = "postgres")]
+#[derive(Debug)]
+struct Null;
+
+#[cfg(feature = "postgres")]
+impl ToSql for Null {
+    fn to_sql(
+        &self,
+        _ty: &Type,
+        _out: &mut bytes::BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        Ok(IsNull::Yes)
+    }
+
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+
+    to_sql_checked!();
+}
+
+/// The `NULL` value of the parameters sent to SQL Server for a column of the given type
+#[cfg(feature = "mssql")]
+fn null_column_data(sqlserver_type: &str) -> ColumnData<'static> {
+    let sqlserver_type = sqlserver_type.to_lowercase();
+    match sqlserver_type.split('(').next().unwrap_or_default().trim() {
+        "bit" => ColumnData::Bit(None),
+        "tinyint" => ColumnData::U8(None),
+        "smallint" => ColumnData::I16(None),
+        "int" => ColumnData::I32(None),
+        "bigint" => ColumnData::I64(None),
+        "real" => ColumnData::F32(None),
+        "float" => ColumnData::F64(None),
+        "binary" | "varbinary" => ColumnData::Binary(None),
+        "uniqueidentifier" => ColumnData::Guid(None),
+        _ => ColumnData::String(None),
+    }
+}
+
+/// A type whose values are retrieved from the columns of the rows, that are `None`
+/// when the column is `NULL`.
+///
+/// It's implemented for every type supported by Canyon (with the exception of the
+/// `#[json]` fields), and for the types that implements [`CanyonType`]
+pub trait ColumnValue: Sized {
+    /// Retrieves the value of the column with the given name of a PostgreSQL row
+    #[cfg(feature = "postgres")]
+    fn from_postgres(
+        row: &tokio_postgres::Row,
+        column: &str,
+    ) -> Result<Option<Self>, Box<dyn Error + Send + Sync>>;
+
+    /// Retrieves the value of the column with the given name of a SQL Server row
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver(
+        row: &tiberius::Row,
+        column: &str,
+    ) -> Result<Option<Self>, Box<dyn Error + Send + Sync>>;
+}
+
+macro_rules! impl_column_value {
+    ($($ty:ty),+) => {
+        $(
+            impl ColumnValue for $ty {
+                #[cfg(feature = "postgres")]
+                fn from_postgres(
+                    row: &tokio_postgres::Row,
+                    column: &str,
+                ) -> Result<Option<Self>, Box<dyn Error + Send + Sync>> {
+                    Ok(row.try_get::<&str, Option<Self>>(column)?)
+                }
+                #[cfg(feature = "mssql")]
+                fn from_sqlserver(
+                    row: &tiberius::Row,
+                    column: &str,
+                ) -> Result<Option<Self>, Box<dyn Error + Send + Sync>> {
+                    Ok(row.try_get::<Self, &str>(column)?)
+                }
+            }
+        )+
+    };
+}
+
+impl_column_value!(
+    bool,
+    i16,
+    i32,
+    i64,
+    f32,
+    f64,
+    NaiveDate,
+    NaiveTime,
+    NaiveDateTime,
+    DateTime<FixedOffset>,
+    DateTime<Utc>,
+    Uuid
+);
+
+#[cfg(feature = "rust_decimal")]
+impl_column_value!(Decimal);
+
+impl ColumnValue for String {
+    #[cfg(feature = "postgres")]
+    fn from_postgres(
+        row: &tokio_postgres::Row,
+        column: &str,
+    ) -> Result<Option<Self>, Box<dyn Error + Send + Sync>> {
+        Ok(row.try_get::<&str, Option<Self>>(column)?)
+    }
+    // Tiberius only borrows the text values from the rows
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver(
+        row: &tiberius::Row,
+        column: &str,
+    ) -> Result<Option<Self>, Box<dyn Error + Send + Sync>> {
+        Ok(row.try_get::<&str, &str>(column)?.map(str::to_owned))
+    }
+}
+
+impl ColumnValue for Vec<u8> {
+    #[cfg(feature = "postgres")]
+    fn from_postgres(
+        row: &tokio_postgres::Row,
+        column: &str,
+    ) -> Result<Option<Self>, Box<dyn Error + Send + Sync>> {
+        Ok(row.try_get::<&str, Option<Self>>(column)?)
+    }
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver(
+        row: &tiberius::Row,
+        column: &str,
+    ) -> Result<Option<Self>, Box<dyn Error + Send + Sync>> {
+        Ok(row.try_get::<&[u8], &str>(column)?.map(<[u8]>::to_vec))
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<T> ColumnValue for Vec<T>
+where
+    T: ArrayElement + for<'a> tokio_postgres::types::FromSql<'a>,
+{
+    fn from_postgres(
+        row: &tokio_postgres::Row,
+        column: &str,
+    ) -> Result<Option<Self>, Box<dyn Error + Send + Sync>> {
+        Ok(row.try_get::<&str, Option<Self>>(column)?)
+    }
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver(
+        _row: &tiberius::Row,
+        column: &str,
+    ) -> Result<Option<Self>, Box<dyn Error + Send + Sync>> {
+        Err(format!("The `{column}` array column isn't supported on SQL Server").into())
+    }
+}
+
+impl<T: CanyonType> ColumnValue for T {
+    #[cfg(feature = "postgres")]
+    fn from_postgres(
+        row: &tokio_postgres::Row,
+        column: &str,
+    ) -> Result<Option<Self>, Box<dyn Error + Send + Sync>> {
+        T::Value::from_postgres(row, column)?
+            .map(T::from_value)
+            .transpose()
+    }
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver(
+        row: &tiberius::Row,
+        column: &str,
+    ) -> Result<Option<Self>, Box<dyn Error + Send + Sync>> {
+        T::Value::from_sqlserver(row, column)?
+            .map(T::from_value)
+            .transpose()
+    }
+}
+
+/// The type of a field of an entity, that's a [`ColumnValue`] or its `Option`, which
+/// is `None` when the column is `NULL`
+pub trait FieldValue: Sized {
+    /// Retrieves the value of the column with the given name of a PostgreSQL row
+    #[cfg(feature = "postgres")]
+    fn from_postgres_row(
+        row: &tokio_postgres::Row,
+        column: &str,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>>;
+
+    /// Retrieves the value of the column with the given name of a SQL Server row
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver_row(
+        row: &tiberius::Row,
+        column: &str,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>>;
+}
+
+impl<T: ColumnValue> FieldValue for T {
+    #[cfg(feature = "postgres")]
+    fn from_postgres_row(
+        row: &tokio_postgres::Row,
+        column: &str,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        T::from_postgres(row, column)?.ok_or_else(|| unexpected_null(column))
+    }
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver_row(
+        row: &tiberius::Row,
+        column: &str,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        T::from_sqlserver(row, column)?.ok_or_else(|| unexpected_null(column))
+    }
+}
+
+impl<T: ColumnValue> FieldValue for Option<T> {
+    #[cfg(feature = "postgres")]
+    fn from_postgres_row(
+        row: &tokio_postgres::Row,
+        column: &str,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        T::from_postgres(row, column)
+    }
+    #[cfg(feature = "mssql")]
+    fn from_sqlserver_row(
+        row: &tiberius::Row,
+        column: &str,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        T::from_sqlserver(row, column)
+    }
+}
+
+#[cfg(any(feature = "postgres", feature = "mssql"))]
+fn unexpected_null(column: &str) -> Box<dyn Error + Send + Sync> {
+    format!("Unexpected NULL value on the non optional `{column}` column").into()
+}
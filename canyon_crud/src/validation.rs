@@ -0,0 +1,235 @@
+//! The runtime support for the `#[validate(...)]` field annotations. The checks
+//! generated by the `CanyonCrud` derive macro for every annotated field are
+//! gathered in the [`Validate`] implementation of the entity, and launched by the
+//! `insert`, `multi_insert` and `update` operations before touching the database.
+//!
+//! The available rules are:
+//!
+//! * `length(min = x, max = y)` for `String` fields, counting its chars
+//! * `range(min = x, max = y)` for numeric fields
+//! * `regex = "pattern"` for `String` fields
+//! * `custom = "fn_path"`, a function with the signature `fn(&T) -> Result<(), String>`,
+//!   where `T` is the type of the field and the error is the description of the failure
+//!
+//! The null values of the `Option<T>` fields are never validated.
+use std::error::Error;
+use std::fmt::{self, Display};
+
+pub use regex::Regex;
+
+/// Checks the rules declared with the `#[validate(...)]` annotations over the
+/// fields of an entity. It's implemented by the `CanyonCrud` derive macro.
+pub trait Validate {
+    /// Returns every rule that the current values of the entity doesn't satisfy
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+/// A validation rule not satisfied by the value of a field
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldError {
+    /// The name of the field
+    pub field: &'static str,
+    /// The kind of rule that failed: `length`, `range`, `regex` or `custom`
+    pub rule: &'static str,
+    /// A description of the failure
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &'static str, rule: &'static str, message: String) -> Self {
+        Self {
+            field,
+            rule,
+            message,
+        }
+    }
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` {}", self.field, self.message)
+    }
+}
+
+/// The collection of [`FieldError`] found validating an entity.
+///
+/// It's returned boxed by the write operations, so it can be recovered by
+/// downcasting it with `error.downcast_ref::<ValidationErrors>()`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationErrors {
+    errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, error: FieldError) {
+        self.errors.push(error)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Every rule not satisfied, in the order that the fields are declared
+    pub fn errors(&self) -> &[FieldError] {
+        &self.errors
+    }
+
+    /// The rules not satisfied by the field called `field`
+    pub fn field_errors<'a>(&'a self, field: &'a str) -> impl Iterator<Item = &'a FieldError> {
+        self.errors.iter().filter(move |error| error.field == field)
+    }
+
+    /// `Ok(())` if there's no errors, `Err(self)` otherwise
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The entity has invalid values: ")?;
+        for (idx, error) in self.errors.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ValidationErrors {}
+
+/// The types whose length can be checked with the `length` rule.
+/// Null values (`None`) aren't validated
+pub trait ValidateLength {
+    fn validation_length(&self) -> Option<u64>;
+}
+
+impl ValidateLength for String {
+    fn validation_length(&self) -> Option<u64> {
+        Some(self.chars().count() as u64)
+    }
+}
+
+impl ValidateLength for Option<String> {
+    fn validation_length(&self) -> Option<u64> {
+        self.as_ref().and_then(|v| v.validation_length())
+    }
+}
+
+/// The types whose value can be checked with the `range` rule.
+/// Null values (`None`) aren't validated
+pub trait ValidateRange {
+    fn validation_value(&self) -> Option<f64>;
+}
+
+macro_rules! impl_validate_range {
+    ($($ty:ty),*) => {
+        $(
+            impl ValidateRange for $ty {
+                fn validation_value(&self) -> Option<f64> {
+                    Some(*self as f64)
+                }
+            }
+
+            impl ValidateRange for Option<$ty> {
+                fn validation_value(&self) -> Option<f64> {
+                    self.map(|v| v as f64)
+                }
+            }
+        )*
+    };
+}
+
+impl_validate_range!(i16, i32, i64, f32, f64);
+
+/// The types whose value can be checked with the `regex` rule.
+/// Null values (`None`) aren't validated
+pub trait ValidateRegex {
+    fn validation_str(&self) -> Option<&str>;
+}
+
+impl ValidateRegex for String {
+    fn validation_str(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl ValidateRegex for Option<String> {
+    fn validation_str(&self) -> Option<&str> {
+        self.as_deref()
+    }
+}
+
+/// Checks the `length(min, max)` rule over the value of `field`
+pub fn validate_length<T: ValidateLength>(
+    field: &'static str,
+    value: &T,
+    min: Option<u64>,
+    max: Option<u64>,
+) -> Result<(), FieldError> {
+    let length = match value.validation_length() {
+        Some(length) => length,
+        None => return Ok(()),
+    };
+
+    if min.is_some_and(|min| length < min) || max.is_some_and(|max| length > max) {
+        let message = match (min, max) {
+            (Some(min), Some(max)) => format!("must have between {min} and {max} characters"),
+            (Some(min), None) => format!("must have at least {min} characters"),
+            (_, Some(max)) => format!("must have at most {max} characters"),
+            (None, None) => unreachable!(),
+        };
+        return Err(FieldError::new(field, "length", message));
+    }
+    Ok(())
+}
+
+/// Checks the `range(min, max)` rule over the value of `field`
+pub fn validate_range<T: ValidateRange>(
+    field: &'static str,
+    value: &T,
+    min: Option<f64>,
+    max: Option<f64>,
+) -> Result<(), FieldError> {
+    let value = match value.validation_value() {
+        Some(value) => value,
+        None => return Ok(()),
+    };
+
+    if min.is_some_and(|min| value < min) || max.is_some_and(|max| value > max) {
+        let message = match (min, max) {
+            (Some(min), Some(max)) => format!("must be between {min} and {max}"),
+            (Some(min), None) => format!("must be greater than or equal to {min}"),
+            (_, Some(max)) => format!("must be less than or equal to {max}"),
+            (None, None) => unreachable!(),
+        };
+        return Err(FieldError::new(field, "range", message));
+    }
+    Ok(())
+}
+
+/// Checks the `regex` rule over the value of `field`
+pub fn validate_regex<T: ValidateRegex>(
+    field: &'static str,
+    value: &T,
+    pattern: &Regex,
+) -> Result<(), FieldError> {
+    match value.validation_str() {
+        Some(value) if !pattern.is_match(value) => Err(FieldError::new(
+            field,
+            "regex",
+            format!("must match the pattern `{}`", pattern.as_str()),
+        )),
+        _ => Ok(()),
+    }
+}
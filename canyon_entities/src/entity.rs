@@ -26,16 +26,25 @@ unsafe impl Send for CanyonEntity {}
 unsafe impl Sync for CanyonEntity {}
 
 impl CanyonEntity {
+    /// The fields mapped to a column of the table, so the ones that aren't `#[transient]`
+    pub fn persisted_fields(&self) -> impl Iterator<Item = &EntityField> {
+        self.fields.iter().filter(|f| !f.is_transient())
+    }
+
     /// Generates as many variants for the enum as fields has the type
     /// which this enum is related to, and that type it's the entity
     /// stored in [`CanyonEntity`]
     /// of the corresponding field
     pub fn get_fields_as_enum_variants(&self) -> Vec<TokenStream> {
-        self.fields
-            .iter()
+        self.persisted_fields()
             .map(|f| {
                 let field_name = &f.name;
-                quote! { #field_name }
+                if f.embedded_prefix().is_some() {
+                    let field_enum = f.embedded_enum_type("Field");
+                    quote! { #field_name(#field_enum) }
+                } else {
+                    quote! { #field_name }
+                }
             })
             .collect::<Vec<_>>()
     }
@@ -46,21 +55,30 @@ impl CanyonEntity {
     /// Makes a variant `#field_name(#ty)` where `#ty` it's a trait object
     /// of type [`canyon_crud::bounds::QueryParameter`]
     pub fn get_fields_as_enum_variants_with_value(&self) -> Vec<TokenStream> {
-        self.fields
-            .iter()
+        self.persisted_fields()
             .map(|f| {
                 let field_name = &f.name;
-                quote! { #field_name(&'a dyn canyon_sql::crud::bounds::QueryParameter<'a>) }
+                if f.embedded_prefix().is_some() {
+                    let field_value_enum = f.embedded_enum_type("FieldValue");
+                    quote! { #field_name(#field_value_enum<'a>) }
+                } else {
+                    quote! { #field_name(&'a dyn canyon_sql::crud::bounds::QueryParameter<'a>) }
+                }
             })
             .collect::<Vec<_>>()
     }
 
     pub fn create_match_arm_for_get_variant_as_str(&self, enum_name: &Ident) -> Vec<TokenStream> {
-        self.fields
-            .iter()
+        self.persisted_fields()
             .map(|f| {
                 let field_name = &f.name;
-                let field_name_as_str = f.name.to_string();
+                if let Some(prefix) = f.embedded_prefix() {
+                    return quote! {
+                        #enum_name::#field_name(ref field) =>
+                            canyon_sql::crud::embedded::prefixed_column(#prefix, field.column())
+                    };
+                }
+                let field_name_as_str = f.column_identifier();
 
                 quote! {
                     #enum_name::#field_name => #field_name_as_str
@@ -76,11 +94,15 @@ impl CanyonEntity {
         &self,
         enum_name: &Ident,
     ) -> Vec<TokenStream> {
-        self.fields
-            .iter()
+        self.persisted_fields()
             .map(|f| {
                 let field_name = &f.name;
-                let field_name_as_string = f.name.to_string();
+                if let Some(prefix) = f.embedded_prefix() {
+                    return quote! {
+                        #enum_name::#field_name(ref field) => format!("{}{}", #prefix, field.column())
+                    };
+                }
+                let field_name_as_string = f.column_identifier();
 
                 quote! {
                     #enum_name::#field_name => #field_name_as_string.to_string()
@@ -96,11 +118,18 @@ impl CanyonEntity {
         &self,
         enum_name: &Ident,
     ) -> Vec<TokenStream> {
-        self.fields
-            .iter()
+        self.persisted_fields()
             .map(|f| {
                 let field_name = &f.name;
-                let field_name_as_string = f.name.to_string();
+                if let Some(prefix) = f.embedded_prefix() {
+                    return quote! {
+                        #enum_name::#field_name(v) => {
+                            let (column, value) = v.value();
+                            (canyon_sql::crud::embedded::prefixed_column(#prefix, column), value)
+                        }
+                    };
+                }
+                let field_name_as_string = f.column_identifier();
 
                 quote! {
                     #enum_name::#field_name(v) => (#field_name_as_string, v)
@@ -2,7 +2,7 @@ use partialdebug::placeholder::PartialDebug;
 use proc_macro2::Ident;
 use quote::ToTokens;
 use std::convert::TryFrom;
-use syn::{Attribute, Field, Type};
+use syn::{Attribute, Field, GenericArgument, PathArguments, Type};
 
 use super::field_annotation::EntityFieldAnnotation;
 /// Represents any of the fields and annotations (if any valid annotation) found for an Rust struct
@@ -35,6 +35,64 @@ impl EntityField {
         }
     }
 
+    /// The name of the column mapped by the field, declared with `#[column(name = "...")]`,
+    /// or the name of the field otherwise
+    pub fn column_name(&self) -> String {
+        self.attributes
+            .iter()
+            .find_map(|attr| match attr {
+                EntityFieldAnnotation::Column(options) => options.name.clone(),
+                _ => None,
+            })
+            .unwrap_or_else(|| self.name.to_string())
+    }
+
+    /// The column of the field as it's written on the statements of the query builders.
+    /// The names declared with `#[column(name = "...")]` are quote escaped, so they're
+    /// matched verbatim
+    pub fn column_identifier(&self) -> String {
+        let column = self.column_name();
+        if self.name == column {
+            column
+        } else {
+            format!("\"{column}\"")
+        }
+    }
+
+    /// Returns true if the field is annotated with `#[transient]` (or `#[canyon(skip)]`),
+    /// so it isn't persisted in the database
+    pub fn is_transient(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|attr| matches!(attr, EntityFieldAnnotation::Transient))
+    }
+
+    /// The prefix of the columns of the field, if it's an embedded value object declared
+    /// with `#[embedded]`. It's the name of the field followed by an underscore, unless
+    /// it's declared with `#[embedded(prefix = "...")]`
+    pub fn embedded_prefix(&self) -> Option<String> {
+        self.attributes.iter().find_map(|attr| match attr {
+            EntityFieldAnnotation::Embedded(prefix) => {
+                Some(prefix.clone().unwrap_or_else(|| format!("{}_", self.name)))
+            }
+            _ => None,
+        })
+    }
+
+    /// The type of the enum generated by the `CanyonEmbeddable` derive for the type of
+    /// the field, named after it plus the given suffix (like `AddressField` for `Address`),
+    /// in the same module than the type
+    pub fn embedded_enum_type(&self, suffix: &str) -> Type {
+        let mut enum_type = self.field_type.clone();
+        if let Type::Path(type_path) = &mut enum_type {
+            if let Some(segment) = type_path.path.segments.last_mut() {
+                segment.ident =
+                    Ident::new(&format!("{}{suffix}", segment.ident), segment.ident.span());
+            }
+        }
+        enum_type
+    }
+
     pub fn new(name: &Ident, raw_helper_attributes: &[Attribute], ty: &Type) -> syn::Result<Self> {
         let mut attributes = Vec::new();
         for attr in raw_helper_attributes {
@@ -45,11 +103,78 @@ impl EntityField {
             }
         }
 
-        Ok(Self {
+        let field = Self {
             name: name.clone(),
             field_type: ty.clone(),
             attributes,
-        })
+        };
+        field.check_array_element_type()?;
+
+        Ok(field)
+    }
+
+    /// Rejects the `Vec<T>` (or `Option<Vec<T>>`) fields mapped to a PostgreSQL array
+    /// column whose elements can't be stored on it. The binary `Vec<u8>`, and the fields
+    /// annotated with `#[json]` or `#[transient]`, aren't mapped to an array column
+    fn check_array_element_type(&self) -> syn::Result<()> {
+        if self.attributes.iter().any(|attr| {
+            matches!(
+                attr,
+                EntityFieldAnnotation::Json | EntityFieldAnnotation::Transient
+            )
+        }) {
+            return Ok(());
+        }
+
+        let element_type = match array_element_type(&self.field_type) {
+            Some(element_type) => element_type,
+            None => return Ok(()),
+        };
+        let element_name = element_type
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .unwrap_or_default();
+
+        match element_name.as_str() {
+            "u8" | "bool" | "i16" | "i32" | "i64" | "f32" | "f64" | "String" | "Uuid" => Ok(()),
+            _ => Err(syn::Error::new_spanned(
+                &self.field_type,
+                format!(
+                    "`{}` isn't supported as the type of the elements of an array column. \
+                    Use one of `bool`, `i16`, `i32`, `i64`, `f32`, `f64`, `String` or `Uuid`, \
+                    or store the field as a JSON document with `#[json]`",
+                    element_type.to_token_stream().to_string().replace(' ', "")
+                ),
+            )),
+        }
+    }
+}
+
+/// Return the path of the type of the elements of a `Vec<T>` or an `Option<Vec<T>>`
+fn array_element_type(ty: &Type) -> Option<&syn::Path> {
+    let ty = generic_argument(ty, "Option").unwrap_or(ty);
+    match generic_argument(ty, "Vec")? {
+        Type::Path(element_type) => Some(&element_type.path),
+        _ => None,
+    }
+}
+
+/// Return the first generic argument of the type, if it's the one with the given name
+fn generic_argument<'a>(ty: &'a Type, name: &str) -> Option<&'a Type> {
+    let segment = match ty {
+        Type::Path(type_path) => type_path.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != name {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        }),
+        _ => None,
     }
 }
 
@@ -0,0 +1,162 @@
+use proc_macro2::{Ident, Span};
+use syn::{parse::ParseStream, punctuated::Punctuated, Attribute, Lit, MetaNameValue, Path, Token};
+
+/// The relations of an entity with the entities that references it, declared with
+/// the `#[has_many(...)]` and `#[many_to_many(...)]` annotations of the struct
+#[derive(Clone)]
+pub struct EntityRelation {
+    /// The type of the related entity
+    pub related: Path,
+    /// The name of the accessor generated for the relation
+    pub name: Ident,
+    pub kind: EntityRelationKind,
+}
+
+#[derive(Clone)]
+pub enum EntityRelationKind {
+    /// `#[has_many(Child, foreign_key = "...")]`, with the field of the child entity that
+    /// holds the `#[foreign_key]` pointing to the entity
+    HasMany { foreign_key: Ident },
+    /// `#[many_to_many(Other, through = "...", foreign_key = "...", related_key = "...")]`,
+    /// with the join table and its columns that references the entity and the related one
+    ManyToMany {
+        through: String,
+        foreign_key: String,
+        related_key: String,
+    },
+}
+
+impl EntityRelation {
+    /// Returns true if the attribute declares a relation of the entity
+    pub fn is_relation_attribute(attribute: &Attribute) -> bool {
+        attribute.path.is_ident("has_many") || attribute.path.is_ident("many_to_many")
+    }
+
+    /// Parses the relations declared on the attributes of the entity `owner`
+    pub fn from_attributes(owner: &Ident, attributes: &[Attribute]) -> syn::Result<Vec<Self>> {
+        attributes
+            .iter()
+            .filter(|attr| Self::is_relation_attribute(attr))
+            .map(|attr| Self::new(owner, attr))
+            .collect()
+    }
+
+    /// Parses a `#[has_many(...)]` or a `#[many_to_many(...)]` annotation of the entity `owner`.
+    ///
+    /// The omitted arguments are named after the entities: the foreign keys after the
+    /// entity they reference, in snake case, and the accessor after the related entity,
+    /// in snake case and plural (like `tournaments` for `Tournament`)
+    pub fn new(owner: &Ident, attribute: &Attribute) -> syn::Result<Self> {
+        let (related, name_values) = attribute.parse_args_with(|input: ParseStream| {
+            let related = input.parse::<Path>()?;
+            let name_values = if input.is_empty() {
+                Punctuated::new()
+            } else {
+                input.parse::<Token![,]>()?;
+                Punctuated::<MetaNameValue, Token![,]>::parse_terminated(input)?
+            };
+            Ok((related, name_values))
+        })?;
+        let related_ident = &related
+            .segments
+            .last()
+            .ok_or_else(|| syn::Error::new_spanned(&related, "Expected the related entity"))?
+            .ident;
+
+        let is_many_to_many = attribute.path.is_ident("many_to_many");
+        let allowed_args: &[&str] = if is_many_to_many {
+            &["through", "foreign_key", "related_key", "name"]
+        } else {
+            &["foreign_key", "name"]
+        };
+
+        let mut args: Vec<(String, String)> = Vec::new();
+        for nv in name_values {
+            let arg = nv.path.get_ident().map(|ident| ident.to_string());
+            match (arg, &nv.lit) {
+                (Some(arg), Lit::Str(value)) if allowed_args.contains(&arg.as_str()) => {
+                    args.push((arg, value.value()))
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        nv,
+                        format!(
+                            "The arguments available on the `{}` annotation are {}, with string literals",
+                            attribute.path.get_ident().unwrap(),
+                            allowed_args
+                                .iter()
+                                .map(|arg| format!("`{arg}`"))
+                                .collect::<Vec<String>>()
+                                .join(", ")
+                        ),
+                    ))
+                }
+            }
+        }
+        let arg = |name: &str| {
+            args.iter()
+                .find(|(arg, _)| arg == name)
+                .map(|(_, value)| value.clone())
+        };
+
+        let name = Ident::new(
+            &arg("name").unwrap_or_else(|| format!("{}s", snake_case(related_ident))),
+            Span::call_site(),
+        );
+        let foreign_key = arg("foreign_key").unwrap_or_else(|| snake_case(owner));
+
+        let kind = if is_many_to_many {
+            EntityRelationKind::ManyToMany {
+                through: arg("through").ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        attribute,
+                        "Missed `through` argument with the join table on the `many_to_many` annotation",
+                    )
+                })?,
+                foreign_key,
+                related_key: arg("related_key").unwrap_or_else(|| snake_case(related_ident)),
+            }
+        } else {
+            EntityRelationKind::HasMany {
+                foreign_key: Ident::new(&foreign_key, Span::call_site()),
+            }
+        };
+
+        Ok(Self {
+            related,
+            name,
+            kind,
+        })
+    }
+
+    /// The type of the related entity with the given suffix (like `TournamentRelation`
+    /// for `Tournament`), in the same module than the related entity
+    pub fn related_type_with_suffix(&self, suffix: &str) -> Path {
+        let mut path = self.related.clone();
+        if let Some(segment) = path.segments.last_mut() {
+            segment.ident = Ident::new(&format!("{}{suffix}", segment.ident), segment.ident.span());
+        }
+        path
+    }
+
+    /// The name of the related entity
+    pub fn related_name(&self) -> String {
+        self.related
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// The snake case version of the name of an entity
+fn snake_case(ident: &Ident) -> String {
+    let mut snake_case = String::new();
+    for (idx, char) in ident.to_string().chars().enumerate() {
+        if idx > 0 && char.is_ascii_uppercase() {
+            snake_case.push('_');
+        }
+        snake_case.push(char.to_ascii_lowercase());
+    }
+    snake_case
+}
@@ -1,13 +1,82 @@
 use proc_macro2::Ident;
-use std::{collections::HashMap, convert::TryFrom};
-use syn::{punctuated::Punctuated, Attribute, MetaNameValue, Token};
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    fmt::{self, Display},
+};
+use syn::{punctuated::Punctuated, Attribute, Lit, Meta, MetaNameValue, NestedMeta, Token};
 
 /// The available annotations for a field that belongs to any struct
 /// annotaded with `#[canyon_entity]`
 #[derive(Debug, Clone)]
 pub enum EntityFieldAnnotation {
-    PrimaryKey(bool),
+    PrimaryKey(bool, Option<PrimaryKeyGeneration>),
     ForeignKey(String, String),
+    SoftDelete,
+    CreatedAt,
+    UpdatedAt,
+    Version,
+    Validate(Vec<ValidationRule>),
+    Column(ColumnOptions),
+    Json,
+    Transient,
+    /// The prefix of the columns of an embedded value object, if declared
+    Embedded(Option<String>),
+}
+
+/// The options of the column mapped by a field, declared with the
+/// `#[column(...)]` annotation
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColumnOptions {
+    /// `name = "..."`, the name of the column, when it's different from the name of the field
+    pub name: Option<String>,
+    /// `precision = x`, the total number of digits of a `NUMERIC` column
+    pub precision: Option<u32>,
+    /// `scale = y`, the number of digits after the decimal point of a `NUMERIC` column
+    pub scale: Option<u32>,
+}
+
+/// The strategies available to generate the value of a primary key, declared
+/// with `#[primary_key(generated = "...")]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PrimaryKeyGeneration {
+    /// `uuid_v4`, a random `Uuid` created by Canyon before the insert
+    UuidV4,
+    /// `uuid_v7`, a time ordered `Uuid` created by Canyon before the insert
+    UuidV7,
+    /// `db`, the value is generated by the database, and read back after the insert
+    Database,
+}
+
+impl PrimaryKeyGeneration {
+    /// True if the value is created by Canyon, instead of by the database
+    pub fn is_client_side(&self) -> bool {
+        !matches!(self, Self::Database)
+    }
+}
+
+impl Display for PrimaryKeyGeneration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UuidV4 => write!(f, "uuid_v4"),
+            Self::UuidV7 => write!(f, "uuid_v7"),
+            Self::Database => write!(f, "db"),
+        }
+    }
+}
+
+/// The rules that can be declared on a field with the `#[validate(...)]` annotation,
+/// checked by Canyon before writing the entity in the database
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationRule {
+    /// `length(min = x, max = y)`, the bounds (inclusive) for the number of chars of the value
+    Length(Option<u64>, Option<u64>),
+    /// `range(min = x, max = y)`, the bounds (inclusive) for a numeric value
+    Range(Option<f64>, Option<f64>),
+    /// `regex = "..."`, the pattern that the value must match
+    Regex(String),
+    /// `custom = "fn_path"`, the path of a function that validates the value
+    Custom(String),
 }
 
 impl EntityFieldAnnotation {
@@ -15,58 +84,118 @@ impl EntityFieldAnnotation {
     /// operations that requires character matching
     pub fn get_as_string(&self) -> String {
         match self {
-            Self::PrimaryKey(autoincremental) => {
+            Self::PrimaryKey(autoincremental, None) => {
                 format!("Annotation: PrimaryKey, Autoincremental: {autoincremental}")
             }
+            Self::PrimaryKey(autoincremental, Some(generated)) => format!(
+                "Annotation: PrimaryKey, Autoincremental: {autoincremental}, Generated: {generated}"
+            ),
             Self::ForeignKey(table, column) => {
                 format!("Annotation: ForeignKey, Table: {table}, Column: {column}")
             }
+            Self::SoftDelete => "Annotation: SoftDelete".to_string(),
+            Self::CreatedAt => "Annotation: CreatedAt".to_string(),
+            Self::UpdatedAt => "Annotation: UpdatedAt".to_string(),
+            Self::Version => "Annotation: Version".to_string(),
+            Self::Validate(rules) => format!("Annotation: Validate, Rules: {rules:?}"),
+            Self::Column(options) => {
+                let mut annotation = "Annotation: Column".to_string();
+                if let Some(name) = &options.name {
+                    annotation.push_str(&format!(", Name: {name}"));
+                }
+                if let Some(precision) = options.precision {
+                    annotation.push_str(&format!(", Precision: {precision}"));
+                }
+                if let Some(scale) = options.scale {
+                    annotation.push_str(&format!(", Scale: {scale}"));
+                }
+                annotation
+            }
+            Self::Json => "Annotation: Json".to_string(),
+            Self::Transient => "Annotation: Transient".to_string(),
+            Self::Embedded(None) => "Annotation: Embedded".to_string(),
+            Self::Embedded(Some(prefix)) => format!("Annotation: Embedded, Prefix: {prefix}"),
         }
     }
 
-    /// Retrieves the user defined data in the #[primary_key] attribute
+    /// Retrieves the user defined data in the #[primary_key] attribute.
+    ///
+    /// Accepts either an `autoincremental = bool` argument, or a `generated = "..."`
+    /// one with the strategy used to create the value of the key
     fn primary_key_parser(
         ident: &Ident,
         attr_args: &Result<Punctuated<MetaNameValue, Token![,]>, syn::Error>,
     ) -> syn::Result<Self> {
         match attr_args {
             Ok(name_value) => {
-                let mut data: HashMap<String, bool> = HashMap::new();
+                let mut autoincremental = None;
+                let mut generated = None;
                 for nv in name_value {
                     // The identifier
                     let attr_value_ident = nv.path.get_ident().unwrap().to_string();
                     // The value after the Token[=]
-                    let attr_value = match &nv.lit {
-                        // Error if the token is not a boolean literal
-                        syn::Lit::Bool(v) => v.value(),
+                    match (attr_value_ident.as_str(), &nv.lit) {
+                        ("autoincremental", syn::Lit::Bool(v)) => autoincremental = Some(v.value()),
+                        ("generated", syn::Lit::Str(v)) => {
+                            generated = Some(match v.value().as_str() {
+                                "uuid_v4" => PrimaryKeyGeneration::UuidV4,
+                                "uuid_v7" => PrimaryKeyGeneration::UuidV7,
+                                "db" => PrimaryKeyGeneration::Database,
+                                _ => {
+                                    return Err(syn::Error::new_spanned(
+                                        v,
+                                        "Unknown generation strategy. The available ones are \
+                                        `uuid_v4`, `uuid_v7` and `db`",
+                                    ))
+                                }
+                            })
+                        }
+                        ("autoincremental", _) => {
+                            return Err(syn::Error::new_spanned(
+                                nv.path.clone(),
+                                "Only bool literals are supported for the `autoincremental` attribute",
+                            ))
+                        }
+                        ("generated", _) => {
+                            return Err(syn::Error::new_spanned(
+                                nv.path.clone(),
+                                "Only string literals are supported for the `generated` attribute",
+                            ))
+                        }
                         _ => {
                             return Err(syn::Error::new_spanned(
                                 nv.path.clone(),
                                 format!(
-                                    "Only bool literals are supported for the `{}` attribute",
-                                    &attr_value_ident
+                                    "Unknown argument `{attr_value_ident}` on the Primary Key annotation"
                                 ),
                             ))
                         }
-                    };
-                    data.insert(attr_value_ident, attr_value);
+                    }
                 }
 
-                Ok(EntityFieldAnnotation::PrimaryKey(
-                    match data.get("autoincremental") {
-                        Some(aut) => aut.to_owned(),
-                        None => {
-                            // TODO En vez de error, false para default
-                            return Err(syn::Error::new_spanned(
-                                ident,
-                                "Missed `autoincremental` argument on the Primary Key annotation"
-                                    .to_string(),
-                            ));
-                        }
-                    },
-                ))
+                match (autoincremental, generated) {
+                    (Some(_), Some(_)) => Err(syn::Error::new_spanned(
+                        ident,
+                        "The `autoincremental` and `generated` arguments of the Primary Key \
+                        annotation are mutually exclusive",
+                    )),
+                    // The keys generated by the database are read back as the autoincremental ones
+                    (None, Some(generated)) => Ok(EntityFieldAnnotation::PrimaryKey(
+                        !generated.is_client_side(),
+                        Some(generated),
+                    )),
+                    (Some(autoincremental), None) => {
+                        Ok(EntityFieldAnnotation::PrimaryKey(autoincremental, None))
+                    }
+                    // TODO En vez de error, false para default
+                    (None, None) => Err(syn::Error::new_spanned(
+                        ident,
+                        "Missed `autoincremental` argument on the Primary Key annotation"
+                            .to_string(),
+                    )),
+                }
             }
-            Err(_) => Ok(EntityFieldAnnotation::PrimaryKey(true)),
+            Err(_) => Ok(EntityFieldAnnotation::PrimaryKey(true, None)),
         }
     }
 
@@ -126,6 +255,217 @@ impl EntityFieldAnnotation {
             )),
         }
     }
+    /// Retrieves the rules declared in the #[validate(...)] attribute
+    fn validate_parser(attribute: &Attribute) -> syn::Result<Self> {
+        let nested = match attribute.parse_meta()? {
+            Meta::List(list) if !list.nested.is_empty() => list.nested,
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "Expected at least one rule on the `validate` annotation",
+                ))
+            }
+        };
+
+        let mut rules = Vec::new();
+        for rule in nested {
+            rules.push(match rule {
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("length") => {
+                    let (min, max) = Self::validation_bounds(&list.nested)?;
+                    let as_length = |bound: Option<f64>| bound.map(|b| b as u64);
+                    ValidationRule::Length(as_length(min), as_length(max))
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("range") => {
+                    let (min, max) = Self::validation_bounds(&list.nested)?;
+                    ValidationRule::Range(min, max)
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("regex") => match &nv.lit
+                {
+                    Lit::Str(pattern) => {
+                        // Invalid patterns are reported at compile time
+                        if let Err(error) = regex::Regex::new(&pattern.value()) {
+                            return Err(syn::Error::new_spanned(
+                                pattern,
+                                format!("Invalid regular expression: {error}"),
+                            ));
+                        }
+                        ValidationRule::Regex(pattern.value())
+                    }
+                    lit => {
+                        return Err(syn::Error::new_spanned(
+                            lit,
+                            "Only string literals are supported for the `regex` rule",
+                        ))
+                    }
+                },
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("custom") => {
+                    match &nv.lit {
+                        Lit::Str(path) => {
+                            path.parse::<syn::Path>()?;
+                            ValidationRule::Custom(path.value())
+                        }
+                        lit => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                "Only string literals are supported for the `custom` rule",
+                            ))
+                        }
+                    }
+                }
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        other,
+                        "Unknown validation rule. The available ones are `length(min, max)`, \
+                        `range(min, max)`, `regex = \"...\"` and `custom = \"fn_path\"`",
+                    ))
+                }
+            });
+        }
+
+        Ok(EntityFieldAnnotation::Validate(rules))
+    }
+
+    /// Retrieves the prefix of the columns declared in the #[embedded(prefix = "...")]
+    /// attribute, which is optional
+    fn embedded_parser(attribute: &Attribute) -> syn::Result<Self> {
+        if attribute.tokens.is_empty() {
+            return Ok(EntityFieldAnnotation::Embedded(None));
+        }
+
+        let name_values: Punctuated<MetaNameValue, Token![,]> =
+            attribute.parse_args_with(Punctuated::parse_terminated)?;
+        let mut prefix = None;
+        for nv in name_values {
+            match &nv.lit {
+                Lit::Str(v) if nv.path.is_ident("prefix") => prefix = Some(v.value()),
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        nv,
+                        "The only argument available on the `embedded` annotation \
+                        is `prefix = \"...\"`",
+                    ))
+                }
+            }
+        }
+
+        Ok(EntityFieldAnnotation::Embedded(prefix))
+    }
+
+    /// Retrieves the options declared in the #[column(...)] attribute
+    fn column_parser(
+        ident: &Ident,
+        attr_args: &Result<Punctuated<MetaNameValue, Token![,]>, syn::Error>,
+    ) -> syn::Result<Self> {
+        let name_values = match attr_args {
+            Ok(name_values) if !name_values.is_empty() => name_values,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "Expected at least one `name = value` argument on the `column` annotation",
+                ))
+            }
+        };
+
+        let mut options = ColumnOptions::default();
+        for nv in name_values {
+            if nv.path.is_ident("name") {
+                match &nv.lit {
+                    Lit::Str(v) if !v.value().is_empty() => options.name = Some(v.value()),
+                    lit => {
+                        return Err(syn::Error::new_spanned(
+                            lit,
+                            "Only non empty string literals are supported for the `name` argument",
+                        ))
+                    }
+                }
+                continue;
+            }
+
+            let value = match &nv.lit {
+                Lit::Int(v) => v.base10_parse::<u32>()?,
+                lit => return Err(syn::Error::new_spanned(
+                    lit,
+                    "Only integer literals are supported for the `precision` and `scale` arguments",
+                )),
+            };
+
+            if nv.path.is_ident("precision") {
+                options.precision = Some(value)
+            } else if nv.path.is_ident("scale") {
+                options.scale = Some(value)
+            } else {
+                return Err(syn::Error::new_spanned(
+                    &nv.path,
+                    "Unknown argument on the `column` annotation. The available ones are \
+                    `name`, `precision` and `scale`",
+                ));
+            }
+        }
+
+        match (options.precision, options.scale) {
+            (Some(0), _) => Err(syn::Error::new_spanned(
+                ident,
+                "The `precision` of a column must be greater than zero",
+            )),
+            (None, Some(_)) => Err(syn::Error::new_spanned(
+                ident,
+                "The `scale` of a column requires a `precision`",
+            )),
+            (Some(precision), Some(scale)) if scale > precision => Err(syn::Error::new_spanned(
+                ident,
+                "The `scale` of a column can't be greater than its `precision`",
+            )),
+            _ => Ok(EntityFieldAnnotation::Column(options)),
+        }
+    }
+
+    /// Retrieves the `min` and `max` numeric bounds of the `length` and `range` validation rules
+    fn validation_bounds(
+        args: &Punctuated<NestedMeta, Token![,]>,
+    ) -> syn::Result<(Option<f64>, Option<f64>)> {
+        let (mut min, mut max) = (None, None);
+        for arg in args {
+            let nv = match arg {
+                NestedMeta::Meta(Meta::NameValue(nv)) => nv,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        arg,
+                        "Expected a `min = x` or a `max = y` argument",
+                    ))
+                }
+            };
+            let value = match &nv.lit {
+                Lit::Int(v) => v.base10_parse::<f64>()?,
+                Lit::Float(v) => v.base10_parse::<f64>()?,
+                lit => {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        "Only numeric literals are supported for the validation bounds",
+                    ))
+                }
+            };
+
+            if nv.path.is_ident("min") {
+                min = Some(value)
+            } else if nv.path.is_ident("max") {
+                max = Some(value)
+            } else {
+                return Err(syn::Error::new_spanned(
+                    &nv.path,
+                    "Expected a `min = x` or a `max = y` argument",
+                ));
+            }
+        }
+
+        if min.is_none() && max.is_none() {
+            return Err(syn::Error::new_spanned(
+                args,
+                "Expected at least a `min` or a `max` bound",
+            ));
+        }
+
+        Ok((min, max))
+    }
 }
 
 impl TryFrom<&&Attribute> for EntityFieldAnnotation {
@@ -139,6 +479,25 @@ impl TryFrom<&&Attribute> for EntityFieldAnnotation {
         Ok(match ident.to_string().as_str() {
             "primary_key" => EntityFieldAnnotation::primary_key_parser(&ident, &name_values)?,
             "foreign_key" => EntityFieldAnnotation::foreign_key_parser(&ident, &name_values)?,
+            "soft_delete" => EntityFieldAnnotation::SoftDelete,
+            "created_at" => EntityFieldAnnotation::CreatedAt,
+            "updated_at" => EntityFieldAnnotation::UpdatedAt,
+            "version" => EntityFieldAnnotation::Version,
+            "validate" => EntityFieldAnnotation::validate_parser(attribute)?,
+            "column" => EntityFieldAnnotation::column_parser(&ident, &name_values)?,
+            "json" => EntityFieldAnnotation::Json,
+            "transient" => EntityFieldAnnotation::Transient,
+            "embedded" => EntityFieldAnnotation::embedded_parser(attribute)?,
+            // `#[canyon(skip)]` is an alias of `#[transient]`
+            "canyon" => match attribute.parse_args::<Ident>() {
+                Ok(arg) if arg == "skip" => EntityFieldAnnotation::Transient,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        attribute,
+                        "The only argument available on the `canyon` annotation is `skip`",
+                    ))
+                }
+            },
             _ => {
                 return Err(syn::Error::new_spanned(
                     ident.clone(),
@@ -1,11 +1,25 @@
-use crate::register_types::CanyonRegisterEntity;
+use crate::register_types::{
+    CanyonRegisterEmbeddable, CanyonRegisterEntity, CanyonRegisterEnum, CanyonRegisterJoinTable,
+    CanyonRegisterType,
+};
 use std::sync::Mutex;
 
 pub mod entity;
 pub mod entity_fields;
+pub mod entity_relation;
 pub mod field_annotation;
 pub mod manager_builder;
 pub mod register_types;
 
 pub static CANYON_REGISTER_ENTITIES: Mutex<Vec<CanyonRegisterEntity<'static>>> =
     Mutex::new(Vec::new());
+
+pub static CANYON_REGISTER_ENUMS: Mutex<Vec<CanyonRegisterEnum>> = Mutex::new(Vec::new());
+
+pub static CANYON_REGISTER_TYPES: Mutex<Vec<CanyonRegisterType>> = Mutex::new(Vec::new());
+
+pub static CANYON_REGISTER_EMBEDDABLES: Mutex<Vec<CanyonRegisterEmbeddable>> =
+    Mutex::new(Vec::new());
+
+pub static CANYON_REGISTER_JOIN_TABLES: Mutex<Vec<CanyonRegisterJoinTable>> =
+    Mutex::new(Vec::new());
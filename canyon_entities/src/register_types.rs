@@ -1,5 +1,6 @@
 /// This file contains `Rust` types that represents an entry on the `CanyonRegister`
 /// where `Canyon` tracks the user types that has to manage
+use crate::{CANYON_REGISTER_EMBEDDABLES, CANYON_REGISTER_ENUMS, CANYON_REGISTER_TYPES};
 
 pub const NUMERIC_PK_DATATYPE: [&str; 6] = ["i16", "u16", "i32", "u32", "i64", "u64"];
 
@@ -13,6 +14,157 @@ pub struct CanyonRegisterEntity<'a> {
     pub entity_fields: Vec<CanyonRegisterEntityField>,
 }
 
+impl CanyonRegisterEntity<'_> {
+    /// Replaces the fields annotated with `#[embedded]` by the fields of their registered
+    /// embeddable types, whose columns are named with the prefix of the embedded field
+    pub fn flatten_embedded_fields(&mut self) {
+        let mut entity_fields = Vec::with_capacity(self.entity_fields.len());
+        for field in self.entity_fields.drain(..) {
+            let prefix = match field.embedded_prefix() {
+                Some(prefix) => prefix.to_string(),
+                None => {
+                    entity_fields.push(field);
+                    continue;
+                }
+            };
+            let type_name = field
+                .field_type
+                .rsplit("::")
+                .next()
+                .unwrap_or(&field.field_type);
+            let embeddable = CANYON_REGISTER_EMBEDDABLES
+                .lock()
+                .expect("Error acquiring Mutex guard on the Canyon embeddables register")
+                .iter()
+                .find(|embeddable| embeddable.type_name == type_name)
+                .cloned()
+                .unwrap_or_else(|| {
+                    panic!(
+                        "The `{type_name}` type of the `{}` embedded field of `{}` must derive `CanyonEmbeddable`",
+                        field.field_name, self.entity_name
+                    )
+                });
+
+            entity_fields.extend(embeddable.fields.into_iter().map(|embeddable_field| {
+                CanyonRegisterEntityField {
+                    field_name: format!("{prefix}{}", embeddable_field.field_name),
+                    ..embeddable_field
+                }
+            }));
+        }
+        self.entity_fields = entity_fields;
+    }
+}
+
+/// Gets the join table of a `#[many_to_many]` relation between two entities, created by
+/// the migrations with a column for the primary key of every entity, referencing it,
+/// that together are the primary key of the join table
+#[derive(Debug, Clone, Default)]
+pub struct CanyonRegisterJoinTable {
+    pub table_name: String,
+    pub user_schema_name: Option<String>,
+    /// The entity that declares the relation, and the column that references it
+    pub entity_name: String,
+    pub foreign_key: String,
+    /// The related entity, and the column that references it
+    pub related_entity_name: String,
+    pub related_key: String,
+}
+
+impl CanyonRegisterJoinTable {
+    /// Builds the entity managed by the migrations for the join table, whose columns
+    /// have the type of the primary keys of the registered entities that they reference.
+    ///
+    /// Returns `None` if the join table is already the table of a registered entity,
+    /// or if any of the related entities isn't registered with a single column primary key
+    pub fn as_entity<'a>(
+        &'a self,
+        entities: &[CanyonRegisterEntity<'a>],
+    ) -> Option<CanyonRegisterEntity<'a>> {
+        if entities
+            .iter()
+            .any(|entity| entity.entity_db_table_name == self.table_name)
+        {
+            return None;
+        }
+
+        let join_column = |entity_name: &str, column: &str| {
+            let entity = entities
+                .iter()
+                .find(|entity| entity.entity_name == entity_name)?;
+            let mut primary_keys = entity.entity_fields.iter().filter(|field| {
+                field
+                    .annotations
+                    .iter()
+                    .any(|a| a.starts_with("Annotation: PrimaryKey"))
+            });
+            let primary_key = primary_keys
+                .next()
+                .filter(|_| primary_keys.next().is_none())?;
+
+            Some(CanyonRegisterEntityField {
+                field_name: column.to_string(),
+                field_type: primary_key.field_type.clone(),
+                annotations: vec![
+                    "Annotation: PrimaryKey, Autoincremental: false".to_string(),
+                    format!(
+                        "Annotation: ForeignKey, Table: {}, Column: {}",
+                        entity.entity_db_table_name, primary_key.field_name
+                    ),
+                ],
+            })
+        };
+
+        Some(CanyonRegisterEntity {
+            entity_name: &self.table_name,
+            entity_db_table_name: &self.table_name,
+            user_schema_name: self.user_schema_name.as_deref(),
+            entity_fields: vec![
+                join_column(&self.entity_name, &self.foreign_key)?,
+                join_column(&self.related_entity_name, &self.related_key)?,
+            ],
+        })
+    }
+}
+
+/// Gets the fields of a value object that derives `CanyonEmbeddable`, to create the
+/// columns of the entities that embeds it
+#[derive(Debug, Clone, Default)]
+pub struct CanyonRegisterEmbeddable {
+    pub type_name: String,
+    /// The fields with the name of their columns, without the prefix of the embedded field
+    pub fields: Vec<CanyonRegisterEntityField>,
+}
+
+/// Gets the identifiers of a fieldless enum that derives `CanyonEnum`, used as the
+/// type of the fields of the entities, to create the columns that stores its variants
+#[derive(Debug, Clone, Default)]
+pub struct CanyonRegisterEnum {
+    pub enum_name: String,
+    pub representation: CanyonRegisterEnumRepresentation,
+    /// The names of the variants, or their discriminants for the integer representation
+    pub values: Vec<String>,
+}
+
+/// How the variants of a registered enum are stored in the database
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum CanyonRegisterEnumRepresentation {
+    #[default]
+    Text,
+    Integer,
+    /// The name of the native PostgreSQL enum type
+    Native(String),
+}
+
+/// Gets the SQL types declared by the implementation of `CanyonType` of a user-defined
+/// type, annotated with `#[canyon_type]`, to create the columns of the fields of that type
+#[derive(Debug, Clone, Default)]
+pub struct CanyonRegisterType {
+    pub type_name: String,
+    pub postgres_type: String,
+    pub sqlserver_type: String,
+}
+
 /// Complementary type for a field that represents a struct field that maps
 /// some real database column data
 #[derive(Debug, Clone, Default)]
@@ -38,6 +190,107 @@ impl CanyonRegisterEntityField {
         NUMERIC_PK_DATATYPE.contains(&self.field_type.as_str()) && pk_is_autoincremental
     }
 
+    /// Return if the field is a `Uuid` primary key whose value is generated by
+    /// the database, declared with `#[primary_key(generated = "db")]`
+    pub fn is_db_generated_uuid(&self) -> bool {
+        self.field_type.ends_with("Uuid")
+            && self
+                .annotations
+                .iter()
+                .any(|a| a.starts_with("Annotation: PrimaryKey") && a.ends_with("Generated: db"))
+    }
+
+    /// Return if the field is filled by Canyon with the current time, because
+    /// it's annotated with `#[created_at]` or `#[updated_at]`
+    pub fn is_auto_timestamp(&self) -> bool {
+        self.annotations.iter().any(|a| {
+            a.starts_with("Annotation: CreatedAt") || a.starts_with("Annotation: UpdatedAt")
+        })
+    }
+
+    /// Return the precision and the scale declared with `#[column(precision = x, scale = y)]`
+    /// (if exists). The scale is zero when only the precision is declared
+    pub fn numeric_precision_and_scale(&self) -> Option<(u32, u32)> {
+        let annotation = self
+            .annotations
+            .iter()
+            .find(|a| a.starts_with("Annotation: Column"))?;
+        let option = |name: &str| {
+            annotation
+                .split(", ")
+                .find_map(|option| option.strip_prefix(name))
+                .and_then(|value| value.parse::<u32>().ok())
+        };
+
+        option("Precision: ").map(|precision| (precision, option("Scale: ").unwrap_or(0)))
+    }
+
+    /// Return the prefix of the columns of the field (if any), because it's an embedded
+    /// value object annotated with `#[embedded]`
+    pub fn embedded_prefix(&self) -> Option<&str> {
+        self.annotations
+            .iter()
+            .find_map(|a| a.strip_prefix("Annotation: Embedded, Prefix: "))
+    }
+
+    /// Return if the field is stored as a JSON document, because it's annotated with `#[json]`
+    pub fn is_json(&self) -> bool {
+        self.annotations
+            .iter()
+            .any(|a| a.starts_with("Annotation: Json"))
+    }
+
+    /// Return the type of the elements of the field if it's mapped to a PostgreSQL array
+    /// column, because it's a `Vec<T>` or an `Option<Vec<T>>` other than the binary `Vec<u8>`
+    pub fn array_element_type(&self) -> Option<&str> {
+        let field_type = self.field_type.trim();
+        let field_type = field_type
+            .strip_prefix("Option<")
+            .and_then(|inner| inner.strip_suffix('>'))
+            .unwrap_or(field_type);
+
+        field_type
+            .strip_prefix("Vec<")
+            .and_then(|inner| inner.strip_suffix('>'))
+            .filter(|element| *element != "u8")
+    }
+
+    /// Return the registered enum that's the type of the field (if any), because
+    /// it's a fieldless enum that derives `CanyonEnum`
+    pub fn canyon_enum(&self) -> Option<CanyonRegisterEnum> {
+        let field_type = self.field_type.trim();
+        let field_type = field_type
+            .strip_prefix("Option<")
+            .and_then(|inner| inner.strip_suffix('>'))
+            .unwrap_or(field_type);
+        let enum_name = field_type.rsplit("::").next().unwrap_or(field_type);
+
+        CANYON_REGISTER_ENUMS
+            .lock()
+            .expect("Error acquiring Mutex guard on the Canyon enums register")
+            .iter()
+            .find(|canyon_enum| canyon_enum.enum_name == enum_name)
+            .cloned()
+    }
+
+    /// Return the registered user-defined type that's the type of the field (if any),
+    /// because it implements `CanyonType`
+    pub fn canyon_type(&self) -> Option<CanyonRegisterType> {
+        let field_type = self.field_type.trim();
+        let field_type = field_type
+            .strip_prefix("Option<")
+            .and_then(|inner| inner.strip_suffix('>'))
+            .unwrap_or(field_type);
+        let type_name = field_type.rsplit("::").next().unwrap_or(field_type);
+
+        CANYON_REGISTER_TYPES
+            .lock()
+            .expect("Error acquiring Mutex guard on the Canyon types register")
+            .iter()
+            .find(|canyon_type| canyon_type.type_name == type_name)
+            .cloned()
+    }
+
     /// Return the nullability of a the field
     pub fn is_nullable(&self) -> bool {
         self.field_type.to_uppercase().starts_with("OPTION")
@@ -0,0 +1,160 @@
+use canyon_entities::{
+    field_annotation::EntityFieldAnnotation,
+    register_types::{CanyonRegisterEmbeddable, CanyonRegisterEntityField},
+    CANYON_REGISTER_EMBEDDABLES,
+};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{DeriveInput, Field};
+
+use crate::utils::helpers;
+
+/// Generates the implementation of the `CanyonEmbeddable` trait for a struct with named
+/// fields, the `Field` and `FieldValue` enums used to filter the entities by the fields
+/// of the value object, and registers it for the migrations
+pub(crate) fn generate_canyon_embeddable_tokens(ast: &DeriveInput) -> syn::Result<TokenStream> {
+    let ty = &ast.ident;
+    let vis = &ast.vis;
+    let fields = match &ast.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => fields.named.iter().collect::<Vec<&Field>>(),
+        _ => {
+            return Err(syn::Error::new(
+                ty.span(),
+                "CanyonEmbeddable only works with structs with named fields",
+            ))
+        }
+    };
+
+    register_embeddable(ty, &fields)?;
+
+    let idents = fields
+        .iter()
+        .map(|field| field.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+    let columns = fields
+        .iter()
+        .map(|field| helpers::column_name(field))
+        .collect::<Vec<_>>();
+
+    let field_enum = Ident::new(&format!("{ty}Field"), Span::call_site());
+    let field_value_enum = Ident::new(&format!("{ty}FieldValue"), Span::call_site());
+
+    let postgres_mapper = cfg!(feature = "postgres").then(|| {
+        quote! {
+            fn from_postgres_row(
+                row: &canyon_sql::db_clients::tokio_postgres::Row,
+                prefix: &str,
+            ) -> Result<Self, canyon_sql::crud::MappingError> {
+                Ok(Self {
+                    #(#idents: {
+                        let column = format!("{}{}", prefix, #columns);
+                        <#types as canyon_sql::crud::types::FieldValue>::from_postgres_row(row, &column)
+                            .map_err(|e| canyon_sql::crud::MappingError::from_postgres_row::<#types>(row, &column, e))?
+                    }),*
+                })
+            }
+        }
+    });
+    let sqlserver_mapper = cfg!(feature = "mssql").then(|| {
+        quote! {
+            fn from_sqlserver_row(
+                row: &canyon_sql::db_clients::tiberius::Row,
+                prefix: &str,
+            ) -> Result<Self, canyon_sql::crud::MappingError> {
+                Ok(Self {
+                    #(#idents: {
+                        let column = format!("{}{}", prefix, #columns);
+                        <#types as canyon_sql::crud::types::FieldValue>::from_sqlserver_row(row, &column)
+                            .map_err(|e| canyon_sql::crud::MappingError::from_sqlserver_row::<#types>(row, &column, e))?
+                    }),*
+                })
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl canyon_sql::crud::embedded::CanyonEmbeddable for #ty {
+            const COLUMNS: &'static [&'static str] = &[#(#columns),*];
+
+            fn parameters(&self) -> Vec<&dyn canyon_sql::crud::bounds::QueryParameter<'_>> {
+                vec![#(&self.#idents),*]
+            }
+
+            #postgres_mapper
+            #sqlserver_mapper
+        }
+
+        #[derive(Clone, Debug)]
+        #[allow(non_camel_case_types)]
+        #[allow(dead_code)]
+        /// Auto-generated enum to represent every field of the embeddable type, used
+        /// through the variant of the embedded field on the `Field` enum of the entities
+        #vis enum #field_enum {
+            #(#idents),*
+        }
+
+        impl #field_enum {
+            /// The column of the field, without the prefix of the embedded field
+            pub fn column(&self) -> &'static str {
+                match self {
+                    #(Self::#idents => #columns),*
+                }
+            }
+        }
+
+        #[derive(Debug)]
+        #[allow(non_camel_case_types)]
+        #[allow(dead_code)]
+        /// Auto-generated enum to represent every field of the embeddable type with a
+        /// value, used through the variant of the embedded field on the `FieldValue`
+        /// enum of the entities
+        #vis enum #field_value_enum<'a> {
+            #(#idents(&'a dyn canyon_sql::crud::bounds::QueryParameter<'a>)),*
+        }
+
+        impl<'a> #field_value_enum<'a> {
+            /// The column of the field, without the prefix of the embedded field, and its value
+            pub fn value(self) -> (&'static str, &'a dyn canyon_sql::crud::bounds::QueryParameter<'a>) {
+                match self {
+                    #(Self::#idents(v) => (#columns, v)),*
+                }
+            }
+        }
+    })
+}
+
+/// Adds the embeddable type to the register used by the migrations, replacing the
+/// previous entry of the same type (if any)
+fn register_embeddable(ty: &Ident, fields: &[&Field]) -> syn::Result<()> {
+    let mut register_fields = Vec::new();
+    for field in fields {
+        let mut annotations = Vec::new();
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("column"))
+        {
+            annotations.push(EntityFieldAnnotation::try_from(&attr)?.get_as_string());
+        }
+        register_fields.push(CanyonRegisterEntityField {
+            field_name: helpers::column_name(field),
+            field_type: field.ty.to_token_stream().to_string().replace(' ', ""),
+            annotations,
+        });
+    }
+
+    let mut register = CANYON_REGISTER_EMBEDDABLES
+        .lock()
+        .expect("Error acquiring Mutex guard on the CanyonEmbeddable macro");
+    register.retain(|embeddable| *ty != embeddable.type_name);
+    register.push(CanyonRegisterEmbeddable {
+        type_name: ty.to_string(),
+        fields: register_fields,
+    });
+
+    Ok(())
+}
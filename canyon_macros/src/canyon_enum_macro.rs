@@ -0,0 +1,327 @@
+use canyon_entities::{
+    register_types::{CanyonRegisterEnum, CanyonRegisterEnumRepresentation},
+    CANYON_REGISTER_ENUMS,
+};
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use syn::{Attribute, DeriveInput, Fields, Lit, Meta, NestedMeta};
+
+use crate::utils::helpers::default_database_table_name_from_entity_name;
+
+/// How the variants of the enum are stored, declared on the `#[canyon_enum(...)]`
+/// attribute of the enum
+enum Representation {
+    Text,
+    Integer,
+    Native(String),
+}
+
+/// Generates the implementation of the `CanyonEnum` and the `CanyonType` traits for a
+/// fieldless enum, and registers it for the migrations
+pub(crate) fn generate_canyon_enum_tokens(ast: &DeriveInput) -> syn::Result<TokenStream> {
+    let ty = &ast.ident;
+    let variants = match &ast.data {
+        syn::Data::Enum(data) => &data.variants,
+        _ => {
+            return Err(syn::Error::new(
+                ty.span(),
+                "CanyonEnum only works with fieldless enums",
+            ))
+        }
+    };
+
+    let mut representation = Representation::Text;
+    let mut rename_all = None;
+    for arg in canyon_enum_args(&ast.attrs)? {
+        match arg {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("integer") => {
+                representation = Representation::Integer
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("pg_enum") => {
+                representation = Representation::Native(
+                    default_database_table_name_from_entity_name(&ty.to_string()),
+                )
+            }
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("pg_enum") => {
+                representation = Representation::Native(string_value(&nv.lit)?)
+            }
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("rename_all") => {
+                let rule = string_value(&nv.lit)?;
+                if apply_rename_rule(&rule, "Variant").is_none() {
+                    return Err(syn::Error::new_spanned(
+                        &nv.lit,
+                        format!("Unknown rename rule `{rule}`"),
+                    ));
+                }
+                rename_all = Some(rule)
+            }
+            other => {
+                return Err(syn::Error::new_spanned(
+                    other,
+                    "Expected `integer`, `pg_enum`, `pg_enum = \"...\"` or `rename_all = \"...\"`",
+                ))
+            }
+        }
+    }
+
+    let mut idents: Vec<&Ident> = Vec::new();
+    let mut labels: Vec<String> = Vec::new();
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "CanyonEnum only works with fieldless enums",
+            ));
+        }
+
+        let mut label = match &rename_all {
+            Some(rule) => apply_rename_rule(rule, &variant.ident.to_string()).unwrap(),
+            None => variant.ident.to_string(),
+        };
+        for arg in canyon_enum_args(&variant.attrs)? {
+            match arg {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("rename") => {
+                    label = string_value(&nv.lit)?
+                }
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        other,
+                        "Expected a `rename = \"...\"` argument",
+                    ))
+                }
+            }
+        }
+
+        idents.push(&variant.ident);
+        labels.push(label);
+    }
+
+    register_enum(ty, &representation, &labels, variants.iter())?;
+
+    let representation_tokens = match &representation {
+        Representation::Text => quote! { canyon_sql::crud::enums::EnumRepresentation::Text },
+        Representation::Integer => {
+            quote! { canyon_sql::crud::enums::EnumRepresentation::Integer }
+        }
+        Representation::Native(name) => {
+            quote! { canyon_sql::crud::enums::EnumRepresentation::Native(#name) }
+        }
+    };
+
+    // The parameters are references to constants, promoted to `'static`
+    let (postgres_type, sqlserver_type, value_ty, to_parameter, from_value) = match &representation
+    {
+        Representation::Text => (
+            "text".to_owned(),
+            "nvarchar",
+            quote! { canyon_sql::crud::enums::EnumLabel },
+            quote! { #(Self::#idents => &#labels),* },
+            quote! { canyon_sql::crud::enums::variant_from_label(value) },
+        ),
+        Representation::Integer => (
+            "integer".to_owned(),
+            "int",
+            quote! { i32 },
+            quote! { #(Self::#idents => &(Self::#idents as i32)),* },
+            quote! { canyon_sql::crud::enums::variant_from_discriminant(value) },
+        ),
+        Representation::Native(name) => (
+            name.clone(),
+            "nvarchar",
+            quote! { canyon_sql::crud::enums::EnumLabel },
+            quote! {
+                #(Self::#idents => &canyon_sql::crud::enums::NativeEnumLabel {
+                    type_name: #name,
+                    label: #labels,
+                }),*
+            },
+            quote! { canyon_sql::crud::enums::variant_from_label(value) },
+        ),
+    };
+
+    Ok(quote! {
+        impl canyon_sql::crud::enums::CanyonEnum for #ty {
+            const REPRESENTATION: canyon_sql::crud::enums::EnumRepresentation =
+                #representation_tokens;
+
+            fn label(&self) -> &'static str {
+                match self {
+                    #(Self::#idents => #labels),*
+                }
+            }
+
+            fn discriminant(&self) -> i32 {
+                match self {
+                    #(Self::#idents => Self::#idents as i32),*
+                }
+            }
+
+            fn from_label(label: &str) -> Option<Self> {
+                match label {
+                    #(#labels => Some(Self::#idents),)*
+                    _ => None,
+                }
+            }
+
+            fn from_discriminant(discriminant: i32) -> Option<Self> {
+                #(
+                    if discriminant == Self::#idents as i32 {
+                        return Some(Self::#idents);
+                    }
+                )*
+                None
+            }
+        }
+
+        impl canyon_sql::crud::types::CanyonType for #ty {
+            const POSTGRES_TYPE: &'static str = #postgres_type;
+            const SQLSERVER_TYPE: &'static str = #sqlserver_type;
+
+            type Value = #value_ty;
+
+            fn to_parameter(&self) -> &dyn canyon_sql::crud::bounds::QueryParameter<'_> {
+                match self {
+                    #to_parameter
+                }
+            }
+
+            fn from_value(
+                value: Self::Value,
+            ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+                #from_value
+            }
+        }
+    })
+}
+
+/// Adds the enum to the register used by the migrations, replacing the previous entry
+/// of the same enum (if any)
+fn register_enum<'a>(
+    ty: &Ident,
+    representation: &Representation,
+    labels: &[String],
+    variants: impl Iterator<Item = &'a syn::Variant>,
+) -> syn::Result<()> {
+    let (representation, values) = match representation {
+        Representation::Text => (CanyonRegisterEnumRepresentation::Text, labels.to_vec()),
+        Representation::Native(name) => (
+            CanyonRegisterEnumRepresentation::Native(name.clone()),
+            labels.to_vec(),
+        ),
+        Representation::Integer => {
+            // The implicit discriminants follows the previous explicit one
+            let mut next_discriminant = 0;
+            let mut discriminants = Vec::new();
+            for variant in variants {
+                if let Some((_, expr)) = &variant.discriminant {
+                    next_discriminant = integer_discriminant(expr)?;
+                }
+                discriminants.push(next_discriminant.to_string());
+                next_discriminant += 1;
+            }
+            (CanyonRegisterEnumRepresentation::Integer, discriminants)
+        }
+    };
+
+    let mut register = CANYON_REGISTER_ENUMS
+        .lock()
+        .expect("Error acquiring Mutex guard on the CanyonEnum macro");
+    register.retain(|canyon_enum| *ty != canyon_enum.enum_name);
+    register.push(CanyonRegisterEnum {
+        enum_name: ty.to_string(),
+        representation,
+        values,
+    });
+
+    Ok(())
+}
+
+/// The arguments of the `#[canyon_enum(...)]` attributes
+fn canyon_enum_args(attrs: &[Attribute]) -> syn::Result<Vec<NestedMeta>> {
+    let mut args = Vec::new();
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("canyon_enum"))
+    {
+        match attr.parse_meta()? {
+            Meta::List(list) => args.extend(list.nested),
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "Expected a list of arguments, like `#[canyon_enum(...)]`",
+                ))
+            }
+        }
+    }
+    Ok(args)
+}
+
+fn string_value(lit: &Lit) -> syn::Result<String> {
+    match lit {
+        Lit::Str(value) => Ok(value.value()),
+        lit => Err(syn::Error::new_spanned(
+            lit,
+            "Only string literals are valid values for the attributes",
+        )),
+    }
+}
+
+/// The value of an explicit discriminant, which must be an integer literal for
+/// the enums stored as integers
+fn integer_discriminant(expr: &syn::Expr) -> syn::Result<i64> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: Lit::Int(value),
+            ..
+        }) => value.base10_parse::<i64>(),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => integer_discriminant(expr).map(|value| -value),
+        expr => Err(syn::Error::new_spanned(
+            expr,
+            "Only integer literals are supported as the discriminants of a CanyonEnum",
+        )),
+    }
+}
+
+/// Renames a variant with the given rule, returning `None` if the rule doesn't exists
+pub(crate) fn apply_rename_rule(rule: &str, variant: &str) -> Option<String> {
+    let snake_case = default_database_table_name_from_entity_name(variant);
+    Some(match rule {
+        "lowercase" => variant.to_lowercase(),
+        "UPPERCASE" => variant.to_uppercase(),
+        "PascalCase" => variant.to_string(),
+        "camelCase" => variant[..1].to_lowercase() + &variant[1..],
+        "snake_case" => snake_case,
+        "SCREAMING_SNAKE_CASE" => snake_case.to_uppercase(),
+        "kebab-case" => snake_case.replace('_', "-"),
+        _ => return None,
+    })
+}
+
+#[test]
+fn test_canyon_enum_rename_rules() {
+    assert_eq!(
+        apply_rename_rule("snake_case", "InProgress"),
+        Some("in_progress".to_owned())
+    );
+    assert_eq!(
+        apply_rename_rule("SCREAMING_SNAKE_CASE", "InProgress"),
+        Some("IN_PROGRESS".to_owned())
+    );
+    assert_eq!(
+        apply_rename_rule("kebab-case", "InProgress"),
+        Some("in-progress".to_owned())
+    );
+    assert_eq!(
+        apply_rename_rule("camelCase", "InProgress"),
+        Some("inProgress".to_owned())
+    );
+    assert_eq!(
+        apply_rename_rule("lowercase", "InProgress"),
+        Some("inprogress".to_owned())
+    );
+    assert_eq!(apply_rename_rule("Title Case", "InProgress"), None);
+}
@@ -0,0 +1,69 @@
+use canyon_entities::{register_types::CanyonRegisterType, CANYON_REGISTER_TYPES};
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{Expr, ImplItem, ItemImpl, Lit, Type};
+
+/// Registers the SQL types declared by an implementation of `CanyonType` for the
+/// migrations, leaving the implementation untouched
+pub(crate) fn generate_canyon_type_tokens(item_impl: &ItemImpl) -> syn::Result<TokenStream> {
+    let type_name = match &*item_impl.self_ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        syn::Error::new_spanned(
+            &item_impl.self_ty,
+            "#[canyon_type] only works with the implementations of `CanyonType` for named types",
+        )
+    })?;
+
+    let postgres_type = declared_sql_type(item_impl, "POSTGRES_TYPE")?;
+    let sqlserver_type = declared_sql_type(item_impl, "SQLSERVER_TYPE")?;
+
+    let mut register = CANYON_REGISTER_TYPES
+        .lock()
+        .expect("Error acquiring Mutex guard on the canyon_type macro");
+    register.retain(|canyon_type| canyon_type.type_name != type_name);
+    register.push(CanyonRegisterType {
+        type_name,
+        postgres_type,
+        sqlserver_type,
+    });
+
+    Ok(item_impl.to_token_stream())
+}
+
+/// The value of the associated constant with the given name, that must be a string literal
+fn declared_sql_type(item_impl: &ItemImpl, name: &str) -> syn::Result<String> {
+    let constant = item_impl
+        .items
+        .iter()
+        .find_map(|item| match item {
+            ImplItem::Const(constant) if constant.ident == name => Some(constant),
+            _ => None,
+        })
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                &item_impl.self_ty,
+                format!("Missing the `{name}` associated constant of `CanyonType`"),
+            )
+        })?;
+
+    match &constant.expr {
+        Expr::Lit(expr_lit) => match &expr_lit.lit {
+            Lit::Str(value) => Ok(value.value()),
+            lit => Err(syn::Error::new_spanned(
+                lit,
+                format!("The `{name}` must be a string literal"),
+            )),
+        },
+        expr => Err(syn::Error::new_spanned(
+            expr,
+            format!("The `{name}` must be a string literal"),
+        )),
+    }
+}
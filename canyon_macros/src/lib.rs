@@ -1,39 +1,53 @@
 extern crate proc_macro;
 
+mod canyon_embeddable_macro;
 mod canyon_entity_macro;
+mod canyon_enum_macro;
 #[cfg(feature = "migrations")]
 mod canyon_macro;
+mod canyon_type_macro;
 mod query_operations;
 mod utils;
 
 use canyon_entity_macro::parse_canyon_entity_proc_macro_attr;
 use proc_macro::TokenStream as CompilerTokenStream;
 use proc_macro2::{Ident, TokenStream};
-use quote::{quote, ToTokens};
-use syn::{DeriveInput, Fields, Type, Visibility};
+use quote::quote;
+use syn::DeriveInput;
 
 #[cfg(feature = "migrations")]
 use canyon_macro::main_with_queries;
 
 use query_operations::{
-    delete::{generate_delete_query_tokens, generate_delete_tokens},
+    delete::{
+        generate_delete_query_tokens, generate_delete_tokens,
+        generate_soft_delete_operations_tokens,
+    },
     insert::{generate_insert_tokens, generate_multiple_insert_tokens},
+    relations::generate_relations_tokens,
     select::{
-        generate_count_tokens, generate_find_all_query_tokens, generate_find_all_tokens,
-        generate_find_all_unchecked_tokens, generate_find_by_foreign_key_tokens,
-        generate_find_by_pk_tokens, generate_find_by_reverse_foreign_key_tokens,
+        generate_count_tokens, generate_exists_by_pk_tokens, generate_find_all_query_tokens,
+        generate_find_all_tokens, generate_find_all_unchecked_tokens,
+        generate_find_by_foreign_key_tokens, generate_find_by_pk_tokens,
+        generate_find_by_pks_tokens, generate_find_by_reverse_foreign_key_tokens,
+    },
+    update::{
+        generate_patch_tokens, generate_trackable_tokens, generate_update_query_tokens,
+        generate_update_tokens,
     },
-    update::{generate_update_query_tokens, generate_update_tokens},
+    validate::generate_validate_tokens,
 };
 use utils::{function_parser::FunctionParser, helpers, macro_tokens::MacroTokens};
 
 use canyon_entities::{
     entity::CanyonEntity,
+    entity_relation::{EntityRelation, EntityRelationKind},
+    field_annotation::EntityFieldAnnotation,
     manager_builder::{
         generate_enum_with_fields, generate_enum_with_fields_values, generate_user_struct,
     },
-    register_types::{CanyonRegisterEntity, CanyonRegisterEntityField},
-    CANYON_REGISTER_ENTITIES,
+    register_types::{CanyonRegisterEntity, CanyonRegisterEntityField, CanyonRegisterJoinTable},
+    CANYON_REGISTER_ENTITIES, CANYON_REGISTER_JOIN_TABLES,
 };
 
 /// Macro for handling the entry point to the program.
@@ -175,21 +189,59 @@ pub fn canyon_entity(
     new_entity.user_schema_name = schema_name;
 
     // The entity fields
-    for field in entity.fields.iter() {
+    for field in entity.persisted_fields() {
         let mut new_entity_field = CanyonRegisterEntityField {
-            field_name: field.name.to_string(),
+            field_name: field.column_name(),
             field_type: field.get_field_type_as_string().replace(' ', ""),
             ..Default::default()
         };
 
-        field
-            .attributes
-            .iter()
-            .for_each(|attr| new_entity_field.annotations.push(attr.get_as_string()));
+        // The embedded fields are registered with their resolved prefix, and flattened
+        // into the fields of their embeddable type by the migrations
+        field.attributes.iter().for_each(|attr| {
+            let annotation = match attr {
+                EntityFieldAnnotation::Embedded(_) => {
+                    EntityFieldAnnotation::Embedded(field.embedded_prefix())
+                }
+                _ => attr.clone(),
+            };
+            new_entity_field
+                .annotations
+                .push(annotation.get_as_string())
+        });
 
         new_entity.entity_fields.push(new_entity_field);
     }
 
+    // The join tables of the many to many relations are created by the migrations
+    let entity_relations = match EntityRelation::from_attributes(&entity.struct_name, &entity.attrs)
+    {
+        Ok(entity_relations) => entity_relations,
+        Err(error) => return error.into_compile_error().into(),
+    };
+    let mut join_tables = CANYON_REGISTER_JOIN_TABLES
+        .lock()
+        .expect("Error acquiring Mutex guard on Canyon Entity macro");
+    for relation in entity_relations {
+        let related_entity_name = relation.related_name();
+        if let EntityRelationKind::ManyToMany {
+            through,
+            foreign_key,
+            related_key,
+        } = relation.kind
+        {
+            join_tables.retain(|join_table| join_table.table_name != through);
+            join_tables.push(CanyonRegisterJoinTable {
+                table_name: through,
+                user_schema_name: schema_name.map(String::from),
+                entity_name: e.to_string(),
+                foreign_key,
+                related_entity_name,
+                related_key,
+            });
+        }
+    }
+
     // Fill the register with the data of the attached struct
     CANYON_REGISTER_ENTITIES
         .lock()
@@ -216,7 +268,19 @@ pub fn canyon_entity(
 /// Allows the implementors to auto-derive the `CrudOperations` trait, which defines the methods
 /// that will perform the database communication and the implementation of the queries for every
 /// type, as defined in the `CrudOperations` + `Transaction` traits.
-#[proc_macro_derive(CanyonCrud)]
+///
+/// The type gets an empty implementation of the `EntityHooks` trait, unless it's
+/// annotated with `#[entity_hooks]`, meaning that the lifecycle hooks are implemented
+/// by the user.
+///
+/// The types annotated with `#[tracked]` also implement the `Trackable` trait, so
+/// they can be wrapped in a `Tracked` to only update the columns of the modified fields.
+///
+/// The `#[has_many(Child, foreign_key = "...")]` annotations generates an accessor for
+/// the children whose `#[foreign_key]` field references the type, and the
+/// `#[many_to_many(Other, through = "join_table")]` ones an accessor for the entities
+/// related on the join table, along with the operations that attach, detach and sync them.
+#[proc_macro_derive(CanyonCrud, attributes(entity_hooks, tracked, has_many, many_to_many))]
 pub fn crud_operations(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     // Construct a representation of Rust code as a syntax tree
     // that we can manipulate
@@ -257,6 +321,10 @@ fn impl_crud_operations_trait_for_struct(
 
     // Builds the find_by_pk() query
     let _find_by_pk_tokens = generate_find_by_pk_tokens(macro_data, &table_schema_data);
+    // Builds the find_by_pks() query
+    let _find_by_pks_tokens = generate_find_by_pks_tokens(macro_data, &table_schema_data);
+    // Builds the exists_by_pk() query
+    let _exists_by_pk_tokens = generate_exists_by_pk_tokens(macro_data, &table_schema_data);
 
     // Builds the insert() query
     let _insert_tokens = generate_insert_tokens(macro_data, &table_schema_data);
@@ -265,6 +333,8 @@ fn impl_crud_operations_trait_for_struct(
 
     // Builds the update() queries
     let _update_tokens = generate_update_tokens(macro_data, &table_schema_data);
+    // Builds the patch() queries
+    let _patch_tokens = generate_patch_tokens(macro_data, &table_schema_data);
     // Builds the update() query as a QueryBuilder
     let _update_query_tokens = generate_update_query_tokens(macro_data, &table_schema_data);
 
@@ -288,12 +358,28 @@ fn impl_crud_operations_trait_for_struct(
     let rev_fk_method_implementations =
         _search_by_revese_fk_tokens.iter().map(|(_, m_impl)| m_impl);
 
+    // The relations used to eager load the parents, or the children of the parents,
+    // and the accessors of the entities related by `#[has_many]` and `#[many_to_many]`
+    let relations_tokens = generate_relations_tokens(macro_data, &table_schema_data);
+
     // The autogenerated name for the trait that holds the fk and rev fk searches
     let fk_trait_ident = Ident::new(
         &format!("{}FkOperations", &ty.to_string()),
         proc_macro2::Span::call_site(),
     );
 
+    // The operations available only for the entities with a `#[soft_delete]` field
+    let _soft_delete_tokens: Vec<(TokenStream, TokenStream)> =
+        generate_soft_delete_operations_tokens(macro_data, &table_schema_data);
+    let soft_delete_method_signatures = _soft_delete_tokens.iter().map(|(sign, _)| sign);
+    let soft_delete_method_implementations = _soft_delete_tokens.iter().map(|(_, m_impl)| m_impl);
+
+    // The autogenerated name for the trait that holds the soft delete operations
+    let soft_delete_trait_ident = Ident::new(
+        &format!("{}SoftDeleteOperations", &ty.to_string()),
+        proc_macro2::Span::call_site(),
+    );
+
     let crud_operations_tokens = quote! {
         // The find_all_result impl
         #_find_all_tokens
@@ -310,6 +396,12 @@ fn impl_crud_operations_trait_for_struct(
         // The find_by_pk impl
         #_find_by_pk_tokens
 
+        // The find_by_pks impl
+        #_find_by_pks_tokens
+
+        // The exists_by_pk impl
+        #_exists_by_pk_tokens
+
         // The insert impl
         #_insert_tokens
 
@@ -319,6 +411,9 @@ fn impl_crud_operations_trait_for_struct(
         // The update impl
         #_update_tokens
 
+        // The patch impl
+        #_patch_tokens
+
         // The update as a querybuilder impl
         #_update_query_tokens
 
@@ -329,15 +424,8 @@ fn impl_crud_operations_trait_for_struct(
         #_delete_query_tokens
     };
 
-    let tokens = if !_search_by_fk_tokens.is_empty() {
+    let fk_operations_tokens = if !_search_by_fk_tokens.is_empty() {
         quote! {
-            #[canyon_sql::macros::async_trait]
-            impl canyon_sql::crud::CrudOperations<#ty> for #ty {
-                #crud_operations_tokens
-            }
-
-            impl canyon_sql::crud::Transaction<#ty> for #ty {}
-
             /// Hidden trait for generate the foreign key operations available
             /// in Canyon without have to define them before hand in CrudOperations
             /// because it's just impossible with the actual system (where the methods
@@ -360,19 +448,118 @@ fn impl_crud_operations_trait_for_struct(
             }
         }
     } else {
+        quote! {}
+    };
+
+    let soft_delete_operations_tokens = if !_soft_delete_tokens.is_empty() {
         quote! {
+            /// Hidden trait for generate the operations over the rows marked as
+            /// deleted, only available for the entities that declares a field
+            /// annotated with `#[soft_delete]`
             #[canyon_sql::macros::async_trait]
-            impl canyon_sql::crud::CrudOperations<#ty> for #ty {
-                #crud_operations_tokens
+            pub trait #soft_delete_trait_ident {
+                #(#soft_delete_method_signatures)*
             }
+            #[canyon_sql::macros::async_trait]
+            impl #soft_delete_trait_ident for #ty {
+                #(#soft_delete_method_implementations)*
+            }
+        }
+    } else {
+        quote! {}
+    };
 
-            impl canyon_sql::crud::Transaction<#ty> for #ty {}
+    // The checks of the rules declared with the `#[validate(...)]` field annotations
+    let validate_tokens = generate_validate_tokens(macro_data);
+
+    // The entities annotated with `#[entity_hooks]` implement the lifecycle hooks by
+    // themselves, while the rest of them just use the default ones
+    let entity_hooks_tokens = if macro_data.has_entity_hooks() {
+        quote! {}
+    } else {
+        quote! {
+            impl canyon_sql::crud::EntityHooks for #ty {}
         }
     };
 
+    // The entities annotated with `#[tracked]` can record their modified fields
+    let trackable_tokens = if macro_data.is_tracked() {
+        generate_trackable_tokens(macro_data, &table_schema_data)
+    } else {
+        quote! {}
+    };
+
+    let tokens = quote! {
+        #[canyon_sql::macros::async_trait]
+        impl canyon_sql::crud::CrudOperations<#ty> for #ty {
+            #crud_operations_tokens
+        }
+
+        impl canyon_sql::crud::Transaction<#ty> for #ty {}
+
+        #entity_hooks_tokens
+
+        #validate_tokens
+
+        #trackable_tokens
+
+        #fk_operations_tokens
+
+        #relations_tokens
+
+        #soft_delete_operations_tokens
+    };
+
     tokens.into()
 }
 
+/// Allows a fieldless enum to be used as the type of the fields of the entities and
+/// as a query parameter. The variants are stored by default as text, with the name of
+/// the variant, which can be changed with the `#[canyon_enum(rename_all = "...")]`
+/// attribute of the enum and the `#[canyon_enum(rename = "...")]` one of a variant.
+///
+/// With `#[canyon_enum(integer)]` the variants are stored as their discriminants, and
+/// with `#[canyon_enum(pg_enum)]` (or `pg_enum = "type_name"`) as the labels of a native
+/// PostgreSQL enum type, that it's stored as text on SQL Server
+#[proc_macro_derive(CanyonEnum, attributes(canyon_enum))]
+pub fn canyon_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast: DeriveInput = syn::parse_macro_input!(input as DeriveInput);
+
+    canyon_enum_macro::generate_canyon_enum_tokens(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Allows a struct to be embedded in the entities as a value object, with the
+/// `#[embedded]` (or `#[embedded(prefix = "...")]`) annotation on the field of the entity.
+///
+/// The fields of the struct are stored in columns of the table of the entity, named with
+/// the prefix followed by the name of the field (or the one declared with
+/// `#[column(name = "...")]`). The prefix is the name of the embedded field followed by
+/// an underscore by default
+#[proc_macro_derive(CanyonEmbeddable, attributes(column))]
+pub fn canyon_embeddable(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast: DeriveInput = syn::parse_macro_input!(input as DeriveInput);
+
+    canyon_embeddable_macro::generate_canyon_embeddable_tokens(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Registers the SQL types declared by an implementation of the `CanyonType` trait,
+/// so the migrations can create the columns of the fields of that type.
+///
+/// The `POSTGRES_TYPE` and `SQLSERVER_TYPE` constants must be declared with string
+/// literals
+#[proc_macro_attribute]
+pub fn canyon_type(_meta: CompilerTokenStream, input: CompilerTokenStream) -> CompilerTokenStream {
+    let item_impl = syn::parse_macro_input!(input as syn::ItemImpl);
+
+    canyon_type_macro::generate_canyon_type_tokens(&item_impl)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
 /// proc-macro for annotate struct fields that holds a foreign key relation.
 ///
 /// So basically, if you have some `ForeignKey` attribute, annotate the parent
@@ -386,18 +573,23 @@ pub fn implement_foreignkeyable_for_type(
     let ast: DeriveInput = syn::parse(input).unwrap();
     let ty = ast.ident;
 
-    // Recovers the identifiers of the structs members
-    let fields = filter_fields(match ast.data {
-        syn::Data::Struct(ref s) => &s.fields,
+    // Recovers the identifiers of the persisted structs members, along with their columns
+    let fields = match ast.data {
+        syn::Data::Struct(ref s) => s
+            .fields
+            .iter()
+            .filter(|field| !helpers::is_transient(field))
+            .filter(|field| helpers::embedded_prefix(field).is_none())
+            .map(|field| (field.ident.as_ref().unwrap(), helpers::column_name(field)))
+            .collect::<Vec<_>>(),
         _ => {
             return syn::Error::new(ty.span(), "ForeignKeyable only works with Structs")
                 .to_compile_error()
                 .into()
         }
-    });
+    };
 
-    let field_idents = fields.iter().map(|(_vis, ident)| {
-        let i = ident.to_string();
+    let field_idents = fields.iter().map(|(ident, i)| {
         quote! {
             #i => Some(&self.#ident as &dyn canyon_sql::crud::bounds::QueryParameter<'_>)
         }
@@ -428,105 +620,96 @@ pub fn implement_foreignkeyable_for_type(
     }.into()
 }
 
-#[proc_macro_derive(CanyonMapper)]
+#[proc_macro_derive(CanyonMapper, attributes(column, transient, canyon, embedded))]
 pub fn implement_row_mapper_for_type(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     // Gets the data from the AST
     let ast: DeriveInput = syn::parse(input).unwrap();
 
-    // Recovers the identifiers of the structs members
-    let fields = fields_with_types(match ast.data {
-        syn::Data::Struct(ref s) => &s.fields,
+    // Recovers the structs members
+    let fields = match ast.data {
+        syn::Data::Struct(ref s) => s.fields.iter().collect::<Vec<_>>(),
         _ => {
             return syn::Error::new(ast.ident.span(), "CanyonMapper only works with Structs")
                 .to_compile_error()
                 .into()
         }
-    });
+    };
 
-    // Here it's where the incoming values of the DatabaseResult are wired into a new
-    // instance, mapping the fields of the type against the columns
-    let init_field_values = fields.iter().map(|(_vis, ident, _ty)| {
-        let ident_name = ident.to_string();
-        quote! {
-            #ident: row.try_get(#ident_name)
-                .expect(format!("Failed to retrieve the {} field", #ident_name).as_ref())
-        }
-    });
+    // The array columns only exists on PostgreSQL
+    let postgres_enabled = cfg!(feature = "postgres");
+    let mssql_enabled = cfg!(feature = "mssql");
 
-    let init_field_values_sqlserver = fields.iter().map(|(_vis, ident, ty)| {
-        let ident_name = ident.to_string();
+    if !postgres_enabled {
+        if let Some(field) = fields
+            .iter()
+            .find(|field| !helpers::is_transient(field) && helpers::is_array_type(&field.ty))
+        {
+            return syn::Error::new_spanned(
+                &field.ty,
+                "The `Vec<T>` fields are mapped to PostgreSQL array columns, so they \
+                require the `postgres` feature of Canyon",
+            )
+            .to_compile_error()
+            .into();
+        }
+    }
 
-        if get_field_type_as_string(ty) == "String" {
-            quote! {
-                #ident: row.get::<&str, &str>(#ident_name)
-                    .expect(format!("Failed to retrieve the `{}` field", #ident_name).as_ref())
-                    .to_string()
-            }
-        } else if get_field_type_as_string(ty).replace(' ', "") == "Option<i64>" {
-            quote! {
-                #ident: row.get::<i64, &str>(#ident_name)
-            }
-        } else if get_field_type_as_string(ty).replace(' ', "") == "Option<i32>" {
-            quote! {
-                #ident: row.get::<i32, &str>(#ident_name)
-            }
-        } else if get_field_type_as_string(ty).replace(' ', "") == "Option<i16>" {
-            quote! {
-                #ident: row.get::<i16, &str>(#ident_name)
-            }
-        } else if get_field_type_as_string(ty).replace(' ', "") == "Option<f32>" {
-            quote! {
-                #ident: row.get::<f32, &str>(#ident_name)
-            }
-        } else if get_field_type_as_string(ty).replace(' ', "") == "Option<f64>" {
-            quote! {
-                #ident: row.get::<f64, &str>(#ident_name)
-            }
-        } else if get_field_type_as_string(ty).replace(' ', "") == "Option<String>" {
-            quote! {
-                #ident: row.get::<&str, &str>(#ident_name)
-                    .map( |x| x.to_owned() )
-            }
-        } else if get_field_type_as_string(ty) == "NaiveDate" {
-            quote! {
-                #ident: row.get::<canyon_sql::date_time::NaiveDate, &str>(#ident_name)
-                    .expect(format!("Failed to retrieve the `{}` field", #ident_name).as_ref())
-            }
-        } else if get_field_type_as_string(ty).replace(' ', "") == "Option<NaiveDate>" {
-            quote! {
-                #ident: row.get::<canyon_sql::date_time::NaiveDate, &str>(#ident_name)
-            }
-        } else if get_field_type_as_string(ty) == "NaiveTime" {
-            quote! {
-                #ident: row.get::<canyon_sql::date_time::NaiveTime, &str>(#ident_name)
-                    .expect(format!("Failed to retrieve the `{}` field", #ident_name).as_ref())
-            }
-        } else if get_field_type_as_string(ty).replace(' ', "") == "Option<NaiveTime>" {
+    // Here it's where the incoming values of the DatabaseResult are wired into a new
+    // instance, mapping the fields of the type against their columns. The `#[json]`
+    // fields are deserialized from the JSON documents stored in the columns, and the
+    // `#[transient]` ones are filled with their default value. The `#[embedded]` value
+    // objects are built from their prefixed columns.
+    //
+    // Any value that can't be retrieved is returned as a `MappingError`
+    let init_field_values = fields.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let ident_name = helpers::column_name(field);
+        if helpers::is_transient(field) {
+            quote! { #ident: ::std::default::Default::default() }
+        } else if let Some(prefix) = helpers::embedded_prefix(field) {
             quote! {
-                #ident: row.get::<canyon_sql::date_time::NaiveTime, &str>(#ident_name)
+                #ident: <#ty as canyon_sql::crud::embedded::CanyonEmbeddable>::from_postgres_row(row, #prefix)?
             }
-        } else if get_field_type_as_string(ty) == "NaiveDateTime" {
+        } else if helpers::is_json_field(field) {
             quote! {
-                #ident: row.get::<canyon_sql::date_time::NaiveDateTime, &str>(#ident_name)
-                    .expect(format!("Failed to retrieve the `{}` field", #ident_name).as_ref())
+                #ident: row.try_get::<&str, canyon_sql::json::Json<#ty>>(#ident_name)
+                    .map_err(|e| canyon_sql::crud::MappingError::from_postgres_row::<#ty>(row, #ident_name, e.into()))?
+                    .into_inner()
             }
-        } else if get_field_type_as_string(ty).replace(' ', "") == "Option<NaiveDateTime>" {
+        } else {
             quote! {
-                #ident: row.get::<canyon_sql::date_time::NaiveDateTime, &str>(#ident_name)
+                #ident: <#ty as canyon_sql::crud::types::FieldValue>::from_postgres_row(row, #ident_name)
+                    .map_err(|e| canyon_sql::crud::MappingError::from_postgres_row::<#ty>(row, #ident_name, e))?
             }
-        } else if get_field_type_as_string(ty) == "DateTime" {
+        }
+    });
+
+    let init_field_values_sqlserver = fields.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let ident_name = helpers::column_name(field);
+        if helpers::is_transient(field) {
+            quote! { #ident: ::std::default::Default::default() }
+        } else if let Some(prefix) = helpers::embedded_prefix(field) {
             quote! {
-                #ident: row.get::<canyon_sql::date_time::DateTime, &str>(#ident_name)
-                    .expect(format!("Failed to retrieve the `{}` field", #ident_name).as_ref())
+                #ident: <#ty as canyon_sql::crud::embedded::CanyonEmbeddable>::from_sqlserver_row(row, #prefix)?
             }
-        } else if get_field_type_as_string(ty).replace(' ', "") == "Option<DateTime>" {
+        } else if helpers::is_json_field(field) {
             quote! {
-                #ident: row.get::<canyon_sql::date_time::DateTime, &str>(#ident_name)
+                #ident: match row.try_get::<&str, &str>(#ident_name) {
+                    Ok(text) => canyon_sql::json::from_json_text::<#ty>(text).map_err(|e| {
+                        canyon_sql::crud::MappingError::from_sqlserver_row::<#ty>(row, #ident_name, e.into())
+                    }),
+                    Err(e) => Err(
+                        canyon_sql::crud::MappingError::from_sqlserver_row::<#ty>(row, #ident_name, e.into())
+                    ),
+                }?
             }
         } else {
             quote! {
-                #ident: row.get::<#ty, &str>(#ident_name)
-                    .expect(format!("Failed to retrieve the `{}` field", #ident_name).as_ref())
+                #ident: <#ty as canyon_sql::crud::types::FieldValue>::from_sqlserver_row(row, #ident_name)
+                    .map_err(|e| canyon_sql::crud::MappingError::from_sqlserver_row::<#ty>(row, #ident_name, e))?
             }
         }
     });
@@ -534,42 +717,61 @@ pub fn implement_row_mapper_for_type(input: proc_macro::TokenStream) -> proc_mac
     // The type of the Struct
     let ty = ast.ident;
 
-    let postgres_enabled = cfg!(feature = "postgres");
-    let mssql_enabled = cfg!(feature = "mssql");
+    // The rows of the mock datasources already hold the instances of the type
+    let mock_mapper = cfg!(feature = "mock").then(|| {
+        quote! {
+            fn deserialize_mock(row: canyon_sql::crud::mock::MockRow)
+                -> Result<#ty, canyon_sql::crud::MappingError>
+            {
+                row.into_value::<Self>()
+            }
+        }
+    });
 
     let tokens = if postgres_enabled && mssql_enabled {
         quote! {
             impl canyon_sql::crud::RowMapper<Self> for #ty {
-                fn deserialize_postgresql(row: &canyon_sql::db_clients::tokio_postgres::Row) -> #ty {
-                    Self {
+                fn deserialize_postgresql(row: &canyon_sql::db_clients::tokio_postgres::Row)
+                    -> Result<#ty, canyon_sql::crud::MappingError>
+                {
+                    Ok(Self {
                         #(#init_field_values),*
-                    }
+                    })
                 }
-                fn deserialize_sqlserver(row: &canyon_sql::db_clients::tiberius::Row) -> #ty {
-                    Self {
+                fn deserialize_sqlserver(row: &canyon_sql::db_clients::tiberius::Row)
+                    -> Result<#ty, canyon_sql::crud::MappingError>
+                {
+                    Ok(Self {
                         #(#init_field_values_sqlserver),*
-                    }
+                    })
                 }
+                #mock_mapper
             }
         }
     } else if postgres_enabled {
         quote! {
             impl canyon_sql::crud::RowMapper<Self> for #ty {
-                fn deserialize_postgresql(row: &canyon_sql::db_clients::tokio_postgres::Row) -> #ty {
-                    Self {
+                fn deserialize_postgresql(row: &canyon_sql::db_clients::tokio_postgres::Row)
+                    -> Result<#ty, canyon_sql::crud::MappingError>
+                {
+                    Ok(Self {
                         #(#init_field_values),*
-                    }
+                    })
                 }
+                #mock_mapper
             }
         }
     } else if mssql_enabled {
         quote! {
             impl canyon_sql::crud::RowMapper<Self> for #ty {
-                fn deserialize_sqlserver(row: &canyon_sql::db_clients::tiberius::Row) -> #ty {
-                    Self {
+                fn deserialize_sqlserver(row: &canyon_sql::db_clients::tiberius::Row)
+                    -> Result<#ty, canyon_sql::crud::MappingError>
+                {
+                    Ok(Self {
                         #(#init_field_values_sqlserver),*
-                    }
+                    })
                 }
+                #mock_mapper
             }
         }
     } else {
@@ -584,45 +786,3 @@ pub fn implement_row_mapper_for_type(input: proc_macro::TokenStream) -> proc_mac
 
     tokens.into()
 }
-
-/// Helper for generate the fields data for the Custom Derives Macros
-fn filter_fields(fields: &Fields) -> Vec<(Visibility, Ident)> {
-    fields
-        .iter()
-        .map(|field| (field.vis.clone(), field.ident.as_ref().unwrap().clone()))
-        .collect::<Vec<_>>()
-}
-
-fn fields_with_types(fields: &Fields) -> Vec<(Visibility, Ident, Type)> {
-    fields
-        .iter()
-        .map(|field| {
-            (
-                field.vis.clone(),
-                field.ident.as_ref().unwrap().clone(),
-                field.ty.clone(),
-            )
-        })
-        .collect::<Vec<_>>()
-}
-
-fn get_field_type_as_string(typ: &Type) -> String {
-    match typ {
-        Type::Array(type_) => type_.to_token_stream().to_string(),
-        Type::BareFn(type_) => type_.to_token_stream().to_string(),
-        Type::Group(type_) => type_.to_token_stream().to_string(),
-        Type::ImplTrait(type_) => type_.to_token_stream().to_string(),
-        Type::Infer(type_) => type_.to_token_stream().to_string(),
-        Type::Macro(type_) => type_.to_token_stream().to_string(),
-        Type::Never(type_) => type_.to_token_stream().to_string(),
-        Type::Paren(type_) => type_.to_token_stream().to_string(),
-        Type::Path(type_) => type_.to_token_stream().to_string(),
-        Type::Ptr(type_) => type_.to_token_stream().to_string(),
-        Type::Reference(type_) => type_.to_token_stream().to_string(),
-        Type::Slice(type_) => type_.to_token_stream().to_string(),
-        Type::TraitObject(type_) => type_.to_token_stream().to_string(),
-        Type::Tuple(type_) => type_.to_token_stream().to_string(),
-        Type::Verbatim(type_) => type_.to_token_stream().to_string(),
-        _ => "".to_owned(),
-    }
-}
@@ -1,6 +1,7 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 
+use crate::utils::helpers::{column_identifier, filtered_table_source, has_time_zone};
 use crate::utils::macro_tokens::MacroTokens;
 
 /// Generates the TokenStream for the __delete() CRUD operation
@@ -8,46 +9,142 @@ use crate::utils::macro_tokens::MacroTokens;
 pub fn generate_delete_tokens(macro_data: &MacroTokens, table_schema_data: &String) -> TokenStream {
     let ty = macro_data.ty;
 
-    let fields = macro_data.get_struct_fields();
-    let pk = macro_data.get_primary_key_annotation();
+    let primary_keys = macro_data.get_primary_keys();
 
-    if let Some(primary_key) = pk {
-        let pk_field = fields
-            .iter()
-            .find(|f| *f.to_string() == primary_key)
-            .expect(
-                "Something really bad happened finding the Ident for the pk field on the delete",
-            );
-        let pk_field_value =
-            quote! { &self.#pk_field as &dyn canyon_sql::crud::bounds::QueryParameter<'_> };
+    if !primary_keys.is_empty() {
+        let pk_fields = macro_data
+            .get_primary_key_fields()
+            .into_iter()
+            .map(|field| field.ident.as_ref().unwrap())
+            .collect::<Vec<_>>();
+        let pk_fields_values = quote! {
+            #(&self.#pk_fields as &dyn canyon_sql::crud::bounds::QueryParameter<'_>),*
+        };
+        let pk_debug = if let [pk_field] = pk_fields.as_slice() {
+            quote! { self.#pk_field }
+        } else {
+            quote! { (#(&self.#pk_fields),*) }
+        };
+        let pk_condition = macro_data.primary_key_condition(1);
+
+        // With a `#[version]` field, the row is only deleted if it wasn't modified
+        // since the instance was loaded
+        let version = macro_data.get_version_annotation();
+        let (version_increment, version_condition, returning) = match &version {
+            Some(column) => (
+                format!(", {column:?} = {column:?} + 1"),
+                format!(" AND {column:?} = ${}", primary_keys.len() + 1),
+                format!(" RETURNING {}", macro_data.primary_key_columns_list()),
+            ),
+            None => (String::new(), String::new(), String::new()),
+        };
+
+        // The entities with a `#[soft_delete]` field are just marked as deleted. The rows
+        // already marked keep their original deletion timestamp, as with the `delete_query`
+        // builder, and the current UTC timestamp depends on the targeted database
+        let stmt = |datasource_name: TokenStream| match macro_data
+            .get_annotated_field("soft_delete")
+        {
+            Some(field) => {
+                let soft_delete_column = column_identifier(field);
+                let with_time_zone = has_time_zone(field);
+                let stmt_start = format!(
+                    "UPDATE {table_schema_data} SET {soft_delete_column} = \
+                    COALESCE({soft_delete_column}, "
+                );
+                let stmt_end = format!(
+                    "){version_increment} WHERE {pk_condition}{version_condition}{returning}"
+                );
+                quote! {
+                    &format!(
+                        "{}{}{}",
+                        #stmt_start,
+                        canyon_sql::query::current_timestamp_sql(#datasource_name, #with_time_zone),
+                        #stmt_end
+                    )
+                }
+            }
+            None => {
+                let stmt = format!(
+                    "DELETE FROM {table_schema_data} WHERE {pk_condition}\
+                    {version_condition}{returning}"
+                );
+                quote! { #stmt }
+            }
+        };
+        let (delete_stmt, delete_datasource_stmt) =
+            (stmt(quote! { "" }), stmt(quote! { datasource_name }));
+
+        let (rows, delete_values, stale_check) = match version {
+            Some(_) => {
+                let version_field = macro_data
+                    .get_annotated_field("version")
+                    .and_then(|field| field.ident.as_ref());
+                (
+                    quote! { rows },
+                    quote! {
+                        #pk_fields_values,
+                        &self.#version_field as &dyn canyon_sql::crud::bounds::QueryParameter<'_>
+                    },
+                    quote! {
+                        if rows.is_empty() {
+                            return Err(Box::new(canyon_sql::crud::StaleEntityError::new(
+                                stringify!(#ty), format!("{:?}", #pk_debug)
+                            )));
+                        }
+                    },
+                )
+            }
+            None => (quote! { _ }, pk_fields_values, quote! {}),
+        };
 
         quote! {
             /// Deletes from a database entity the row that matches
             /// the current instance of a T type, returning a result
             /// indicating a possible failure querying the database.
+            ///
+            /// If the entity has a field annotated with `#[soft_delete]`, the row
+            /// is just marked as deleted with the current UTC timestamp, unless it
+            /// was already marked.
+            ///
+            /// If the entity has a field annotated with `#[version]`, the row is only
+            /// deleted if its version still matches the one of the instance. Otherwise,
+            /// a [`canyon_sql::crud::StaleEntityError`] is returned.
             async fn delete(&self) -> Result<(), Box<(dyn std::error::Error + Send + Sync + 'static)>> {
-                <#ty as canyon_sql::crud::Transaction<#ty>>::query(
-                    format!("DELETE FROM {} WHERE {:?} = $1", #table_schema_data, #primary_key),
-                    &[#pk_field_value],
+                canyon_sql::crud::EntityHooks::before_delete(self).await?;
+                let #rows = <#ty as canyon_sql::crud::Transaction<#ty>>::query_returning(
+                    #delete_stmt,
+                    &[#delete_values],
                     ""
                 ).await?;
+                #stale_check
 
-                Ok(())
+                canyon_sql::crud::EntityHooks::after_delete(self).await
             }
 
             /// Deletes from a database entity the row that matches
             /// the current instance of a T type, returning a result
             /// indicating a possible failure querying the database with the specified datasource.
+            ///
+            /// If the entity has a field annotated with `#[soft_delete]`, the row
+            /// is just marked as deleted with the current UTC timestamp, unless it
+            /// was already marked.
+            ///
+            /// If the entity has a field annotated with `#[version]`, the row is only
+            /// deleted if its version still matches the one of the instance. Otherwise,
+            /// a [`canyon_sql::crud::StaleEntityError`] is returned.
             async fn delete_datasource<'a>(&self, datasource_name: &'a str)
                 -> Result<(), Box<(dyn std::error::Error + Send + Sync + 'static)>>
             {
-                <#ty as canyon_sql::crud::Transaction<#ty>>::query(
-                    format!("DELETE FROM {} WHERE {:?} = $1", #table_schema_data, #primary_key),
-                    &[#pk_field_value],
+                canyon_sql::crud::EntityHooks::before_delete(self).await?;
+                let #rows = <#ty as canyon_sql::crud::Transaction<#ty>>::query_returning(
+                    #delete_datasource_stmt,
+                    &[#delete_values],
                     datasource_name
                 ).await?;
+                #stale_check
 
-                Ok(())
+                canyon_sql::crud::EntityHooks::after_delete(self).await
             }
         }
     } else {
@@ -87,6 +184,14 @@ pub fn generate_delete_query_tokens(
 ) -> TokenStream {
     let ty = macro_data.ty;
 
+    // The entities with a `#[soft_delete]` field are just marked as deleted, as
+    // their `delete` operation does
+    let soft_delete = macro_data.get_annotated_field("soft_delete").map(|field| {
+        let soft_delete_column = column_identifier(field);
+        let with_time_zone = has_time_zone(field);
+        quote! { .with_soft_delete(#soft_delete_column, #with_time_zone) }
+    });
+
     quote! {
         /// Generates a [`canyon_sql::query::DeleteQueryBuilder`]
         /// that allows you to customize the query by adding parameters and constrains dynamically.
@@ -95,8 +200,11 @@ pub fn generate_delete_query_tokens(
         /// entity but converted to the corresponding database convention,
         /// unless concrete values are set on the available parameters of the
         /// `canyon_macro(table_name = "table_name", schema = "schema")`
+        ///
+        /// If the entity has a field annotated with `#[soft_delete]`, the matching rows
+        /// are just marked as deleted with the current UTC timestamp.
         fn delete_query<'a>() -> canyon_sql::query::DeleteQueryBuilder<'a, #ty> {
-            canyon_sql::query::DeleteQueryBuilder::new(#table_schema_data, "")
+            canyon_sql::query::DeleteQueryBuilder::new(#table_schema_data, "")#soft_delete
         }
 
         /// Generates a [`canyon_sql::query::DeleteQueryBuilder`]
@@ -107,11 +215,218 @@ pub fn generate_delete_query_tokens(
         /// unless concrete values are set on the available parameters of the
         /// `canyon_macro(table_name = "table_name", schema = "schema")`
         ///
+        /// If the entity has a field annotated with `#[soft_delete]`, the matching rows
+        /// are just marked as deleted with the current UTC timestamp.
+        ///
         /// The query it's made against the database with the configured datasource
         /// described in the configuration file, and selected with the [`&str`]
         /// passed as parameter.
         fn delete_query_datasource<'a>(datasource_name: &'a str) -> canyon_sql::query::DeleteQueryBuilder<'a, #ty> {
-            canyon_sql::query::DeleteQueryBuilder::new(#table_schema_data, datasource_name)
+            canyon_sql::query::DeleteQueryBuilder::new(#table_schema_data, datasource_name)#soft_delete
         }
     }
 }
+
+/// Generates the TokenStream for the operations available over the entities that
+/// has a field annotated with `#[soft_delete]`, as pairs of (method signature,
+/// method implementation). Returns an empty collection for the rest of entities.
+pub fn generate_soft_delete_operations_tokens(
+    macro_data: &MacroTokens,
+    table_schema_data: &String,
+) -> Vec<(TokenStream, TokenStream)> {
+    let mut soft_delete_quotes: Vec<(TokenStream, TokenStream)> = Vec::new();
+    let ty = macro_data.ty;
+
+    let soft_delete_column = match macro_data.get_annotated_field("soft_delete") {
+        Some(field) => column_identifier(field),
+        None => return soft_delete_quotes,
+    };
+    let deleted_rows_source = filtered_table_source(
+        table_schema_data,
+        &format!("{soft_delete_column} IS NOT NULL"),
+    );
+
+    soft_delete_quotes.push((
+        quote! {
+            fn with_deleted<'a>() -> canyon_sql::query::SelectQueryBuilder<'a, #ty>;
+            fn with_deleted_datasource<'a>(datasource_name: &'a str)
+                -> canyon_sql::query::SelectQueryBuilder<'a, #ty>;
+        },
+        quote! {
+            /// Generates a [`canyon_sql::query::SelectQueryBuilder`] that includes
+            /// the rows marked as deleted in the retrieved entities
+            fn with_deleted<'a>() -> canyon_sql::query::SelectQueryBuilder<'a, #ty> {
+                canyon_sql::query::SelectQueryBuilder::new(#table_schema_data, "")
+            }
+
+            /// Generates a [`canyon_sql::query::SelectQueryBuilder`] that includes
+            /// the rows marked as deleted in the retrieved entities, with the specified datasource
+            fn with_deleted_datasource<'a>(datasource_name: &'a str)
+                -> canyon_sql::query::SelectQueryBuilder<'a, #ty>
+            {
+                canyon_sql::query::SelectQueryBuilder::new(#table_schema_data, datasource_name)
+            }
+        },
+    ));
+
+    soft_delete_quotes.push((
+        quote! {
+            fn only_deleted<'a>() -> canyon_sql::query::SelectQueryBuilder<'a, #ty>;
+            fn only_deleted_datasource<'a>(datasource_name: &'a str)
+                -> canyon_sql::query::SelectQueryBuilder<'a, #ty>;
+        },
+        quote! {
+            /// Generates a [`canyon_sql::query::SelectQueryBuilder`] that only
+            /// retrieves the rows marked as deleted
+            fn only_deleted<'a>() -> canyon_sql::query::SelectQueryBuilder<'a, #ty> {
+                canyon_sql::query::SelectQueryBuilder::new(#deleted_rows_source, "")
+            }
+
+            /// Generates a [`canyon_sql::query::SelectQueryBuilder`] that only
+            /// retrieves the rows marked as deleted, with the specified datasource
+            fn only_deleted_datasource<'a>(datasource_name: &'a str)
+                -> canyon_sql::query::SelectQueryBuilder<'a, #ty>
+            {
+                canyon_sql::query::SelectQueryBuilder::new(#deleted_rows_source, datasource_name)
+            }
+        },
+    ));
+
+    soft_delete_quotes.push((
+        quote! {
+            fn force_delete_query<'a>() -> canyon_sql::query::DeleteQueryBuilder<'a, #ty>;
+            fn force_delete_query_datasource<'a>(datasource_name: &'a str)
+                -> canyon_sql::query::DeleteQueryBuilder<'a, #ty>;
+        },
+        quote! {
+            /// Generates a [`canyon_sql::query::DeleteQueryBuilder`] that permanently
+            /// deletes the matching rows from the database
+            fn force_delete_query<'a>() -> canyon_sql::query::DeleteQueryBuilder<'a, #ty> {
+                canyon_sql::query::DeleteQueryBuilder::new(#table_schema_data, "")
+            }
+
+            /// Generates a [`canyon_sql::query::DeleteQueryBuilder`] that permanently
+            /// deletes the matching rows from the database, with the specified datasource
+            fn force_delete_query_datasource<'a>(datasource_name: &'a str)
+                -> canyon_sql::query::DeleteQueryBuilder<'a, #ty>
+            {
+                canyon_sql::query::DeleteQueryBuilder::new(#table_schema_data, datasource_name)
+            }
+        },
+    ));
+
+    let restore_signature = quote! {
+        async fn restore(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>>
+    };
+    let restore_datasource_signature = quote! {
+        async fn restore_datasource<'a>(&self, datasource_name: &'a str)
+            -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>>
+    };
+    let force_delete_signature = quote! {
+        async fn force_delete(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>>
+    };
+    let force_delete_datasource_signature = quote! {
+        async fn force_delete_datasource<'a>(&self, datasource_name: &'a str)
+            -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>>
+    };
+
+    let (restore_impl, force_delete_impl) = if macro_data.type_has_primary_key() {
+        let pk_fields = macro_data
+            .get_primary_key_fields()
+            .into_iter()
+            .map(|field| field.ident.as_ref().unwrap());
+        let pk_field_value = quote! {
+            #(&self.#pk_fields as &dyn canyon_sql::crud::bounds::QueryParameter<'_>),*
+        };
+        let pk_condition = macro_data.primary_key_condition(1);
+        let restore_stmt = format!(
+            "UPDATE {table_schema_data} SET {soft_delete_column} = NULL WHERE {pk_condition}"
+        );
+        let force_delete_stmt = format!("DELETE FROM {table_schema_data} WHERE {pk_condition}");
+
+        (
+            quote! {
+                /// Removes the deleted mark of the row that matches the current
+                /// instance of a T type, making it available again
+                #restore_signature {
+                    <#ty as canyon_sql::crud::Transaction<#ty>>::query(
+                        #restore_stmt, &[#pk_field_value], ""
+                    ).await?;
+
+                    Ok(())
+                }
+
+                /// Removes the deleted mark of the row that matches the current
+                /// instance of a T type, making it available again, with the specified datasource
+                #restore_datasource_signature {
+                    <#ty as canyon_sql::crud::Transaction<#ty>>::query(
+                        #restore_stmt, &[#pk_field_value], datasource_name
+                    ).await?;
+
+                    Ok(())
+                }
+            },
+            quote! {
+                /// Permanently deletes from the database the row that matches
+                /// the current instance of a T type
+                #force_delete_signature {
+                    <#ty as canyon_sql::crud::Transaction<#ty>>::query(
+                        #force_delete_stmt, &[#pk_field_value], ""
+                    ).await?;
+
+                    Ok(())
+                }
+
+                /// Permanently deletes from the database the row that matches
+                /// the current instance of a T type, with the specified datasource
+                #force_delete_datasource_signature {
+                    <#ty as canyon_sql::crud::Transaction<#ty>>::query(
+                        #force_delete_stmt, &[#pk_field_value], datasource_name
+                    ).await?;
+
+                    Ok(())
+                }
+            },
+        )
+    } else {
+        // As the delete operation, restoring or removing an instance requires a primary key
+        let unsupported = |method: &str| {
+            let msg = format!(
+                "You can't use the '{method}' method on a \
+                CanyonEntity that does not have a #[primary_key] annotation. \
+                If you need to perform an specific operation, use the Querybuilder instead."
+            );
+            quote! {
+                Err(std::io::Error::new(std::io::ErrorKind::Unsupported, #msg).into_inner().unwrap())
+            }
+        };
+        let (restore_err, restore_ds_err) =
+            (unsupported("restore"), unsupported("restore_datasource"));
+        let (force_delete_err, force_delete_ds_err) = (
+            unsupported("force_delete"),
+            unsupported("force_delete_datasource"),
+        );
+
+        (
+            quote! {
+                #restore_signature { #restore_err }
+                #restore_datasource_signature { #restore_ds_err }
+            },
+            quote! {
+                #force_delete_signature { #force_delete_err }
+                #force_delete_datasource_signature { #force_delete_ds_err }
+            },
+        )
+    };
+
+    soft_delete_quotes.push((
+        quote! { #restore_signature; #restore_datasource_signature; },
+        restore_impl,
+    ));
+    soft_delete_quotes.push((
+        quote! { #force_delete_signature; #force_delete_datasource_signature; },
+        force_delete_impl,
+    ));
+
+    soft_delete_quotes
+}
@@ -1,6 +1,12 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 
+use canyon_entities::field_annotation::PrimaryKeyGeneration;
+
+use crate::utils::helpers::{
+    column_name, embedded_columns, extend_with_embedded_parameters, query_parameter_for_field,
+    timestamp_for_field,
+};
 use crate::utils::macro_tokens::MacroTokens;
 
 /// Generates the TokenStream for the _insert_result() CRUD operation
@@ -14,94 +20,89 @@ pub fn generate_insert_tokens(macro_data: &MacroTokens, table_schema_data: &Stri
     // Returns a String with the generic $x placeholder for the query parameters.
     let placeholders = macro_data.placeholders_generator();
 
-    // Retrieves the fields of the Struct
-    let fields = macro_data.get_struct_fields();
+    // Retrieves the fields of the Struct whose values are inserted
+    let insert_fields = macro_data.get_insert_fields();
 
-    let insert_values = fields.iter().map(|ident| {
-        quote! { &self.#ident }
-    });
+    let insert_values = insert_fields
+        .iter()
+        .map(|field| query_parameter_for_field(&quote! { self }, field));
     let insert_values_cloned = insert_values.clone();
 
-    let primary_key = macro_data.get_primary_key_annotation();
-
-    let remove_pk_value_from_fn_entry = if let Some(pk_index) = macro_data.get_pk_index() {
-        quote! { values.remove(#pk_index) }
+    // The columns of the `#[embedded]` fields are appended after the other ones
+    let embedded_fields = macro_data.get_embedded_fields();
+    let (values_mutability, extend_embedded_values) = if embedded_fields.is_empty() {
+        (quote! {}, quote! {})
     } else {
-        quote! {}
+        (
+            quote! { mut },
+            extend_with_embedded_parameters(&quote! { values }, &quote! { self }, &embedded_fields),
+        )
     };
-
-    let pk_ident_type = macro_data
-        ._fields_with_types()
-        .into_iter()
-        .find(|(i, _t)| Some(i.to_string()) == primary_key);
-    let insert_transaction = if let Some(pk_data) = &pk_ident_type {
-        let pk_ident = &pk_data.0;
-        let pk_type = &pk_data.1;
-
-        let postgres_enabled = cfg!(feature = "postgres");
-        let mssql_enabled = cfg!(feature = "mssql");
-
-        let match_rows = if postgres_enabled && mssql_enabled {
+    let (insert_columns, placeholders) = if embedded_fields.is_empty() {
+        (quote! { #insert_columns }, quote! { #placeholders })
+    } else {
+        let embedded_columns = embedded_columns(&embedded_fields);
+        let first_placeholder = insert_fields.len() + 1;
+        (
             quote! {
-                canyon_sql::crud::CanyonRows::Postgres(mut v) => {
-                    self.#pk_ident = v
-                        .get(0)
-                        .ok_or("Failed getting the returned IDs for an insert")?
-                        .get::<&str, #pk_type>(#primary_key);
-                    Ok(())
-                }
-                canyon_sql::crud::CanyonRows::Tiberius(mut v) => {
-                    self.#pk_ident = v
-                        .get(0)
-                        .ok_or("Failed getting the returned IDs for a multi insert")?
-                        .get::<#pk_type, &str>(#primary_key)
-                        .ok_or("SQL Server primary key type failed to be set as value")?;
-                    Ok(())
-                }
-            }
-        } else if postgres_enabled {
+                canyon_sql::crud::embedded::append_columns(#insert_columns, &#embedded_columns)
+            },
             quote! {
-                canyon_sql::crud::CanyonRows::Postgres(mut v) => {
-                    self.#pk_ident = v
-                        .get(0)
-                        .ok_or("Failed getting the returned IDs for an insert")?
-                        .get::<&str, #pk_type>(#primary_key);
-                    Ok(())
-                }
-            }
-        } else if mssql_enabled {
+                canyon_sql::crud::embedded::append_placeholders(
+                    #placeholders,
+                    #first_placeholder,
+                    #embedded_columns.len()
+                )
+            },
+        )
+    };
+
+    // The keys created by Canyon are generated, and the `#[created_at]` and
+    // `#[updated_at]` fields are filled with the current time
+    let fill_generated_keys = generate_fill_generated_keys_tokens(macro_data, &quote! { self });
+    let fill_timestamps = generate_fill_timestamps_tokens(macro_data, &quote! { self });
+
+    let insert_transaction = if macro_data.type_has_primary_key() {
+        // Every column of the primary key is returned, and assigned back to the instance
+        let primary_keys = macro_data.primary_key_columns_list();
+        let (postgres_assignments, mssql_assignments, mock_assignments) =
+            generate_returned_pk_assignments(macro_data, &quote! { self });
+        let match_rows = generate_rows_match_arms(
             quote! {
-                canyon_sql::crud::CanyonRows::Tiberius(mut v) => {
-                    self.#pk_ident = v
-                        .get(0)
-                        .ok_or("Failed getting the returned IDs for a multi insert")?
-                        .get::<#pk_type, &str>(#primary_key)
-                        .ok_or("SQL Server primary key type failed to be set as value")?;
-                    Ok(())
-                }
-            }
-        } else {
+                let row = v
+                    .get(0)
+                    .ok_or("Failed getting the returned IDs for an insert")?;
+                #postgres_assignments
+                Ok(())
+            },
             quote! {
-                panic!(
-                    "Reached a branch in the implementation of the Row Mapper macro that should never be reached.\
-                    This is a severe bug of Canyon-SQL. Please, open us an issue at \
-                    https://github.com/zerodaycode/Canyon-SQL/issues and let us know about that failure."
-                )
-            }
-        };
+                let row = v
+                    .get(0)
+                    .ok_or("Failed getting the returned IDs for an insert")?;
+                #mssql_assignments
+                Ok(())
+            },
+            quote! {
+                let returned = v
+                    .into_iter()
+                    .next()
+                    .ok_or("Failed getting the returned IDs for an insert")?
+                    .into_value::<#ty>()?;
+                #mock_assignments
+                Ok(())
+            },
+        );
 
         quote! {
-            #remove_pk_value_from_fn_entry;
-
             let stmt = format!(
                 "INSERT INTO {} ({}) VALUES ({}) RETURNING {}",
                 #table_schema_data,
                 #insert_columns,
                 #placeholders,
-                #primary_key
+                #primary_keys
             );
 
-            let rows = <#ty as canyon_sql::crud::Transaction<#ty>>::query(
+            let rows = <#ty as canyon_sql::crud::Transaction<#ty>>::query_returning(
                 stmt,
                 values,
                 datasource_name
@@ -118,8 +119,7 @@ pub fn generate_insert_tokens(macro_data: &MacroTokens, table_schema_data: &Stri
                 "INSERT INTO {} ({}) VALUES ({})",
                 #table_schema_data,
                 #insert_columns,
-                #placeholders,
-                #primary_key
+                #placeholders
             );
 
             <#ty as canyon_sql::crud::Transaction<#ty>>::query(
@@ -175,8 +175,17 @@ pub fn generate_insert_tokens(macro_data: &MacroTokens, table_schema_data: &Stri
             -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>>
         {
             let datasource_name = "";
-            let mut values: Vec<&dyn canyon_sql::crud::bounds::QueryParameter<'_>> = vec![#(#insert_values),*];
-            #insert_transaction
+            canyon_sql::crud::EntityHooks::before_insert(self).await?;
+            canyon_sql::crud::Validate::validate(self)?;
+            #fill_generated_keys
+            #fill_timestamps
+            let #values_mutability values: Vec<&dyn canyon_sql::crud::bounds::QueryParameter<'_>> = vec![#(#insert_values),*];
+            #extend_embedded_values
+            let inserted: Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>> = {
+                #insert_transaction
+            };
+            inserted?;
+            canyon_sql::crud::EntityHooks::after_insert(self).await
         }
 
         /// Inserts into a database entity the current data in `self`, generating a new
@@ -220,8 +229,17 @@ pub fn generate_insert_tokens(macro_data: &MacroTokens, table_schema_data: &Stri
         async fn insert_datasource<'a>(&mut self, datasource_name: &'a str)
             -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>>
         {
-            let mut values: Vec<&dyn canyon_sql::crud::bounds::QueryParameter<'_>> = vec![#(#insert_values_cloned),*];
-            #insert_transaction
+            canyon_sql::crud::EntityHooks::before_insert(self).await?;
+            canyon_sql::crud::Validate::validate(self)?;
+            #fill_generated_keys
+            #fill_timestamps
+            let #values_mutability values: Vec<&dyn canyon_sql::crud::bounds::QueryParameter<'_>> = vec![#(#insert_values_cloned),*];
+            #extend_embedded_values
+            let inserted: Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>> = {
+                #insert_transaction
+            };
+            inserted?;
+            canyon_sql::crud::EntityHooks::after_insert(self).await
         }
 
     }
@@ -239,226 +257,151 @@ pub fn generate_multiple_insert_tokens(
 ) -> TokenStream {
     let ty = macro_data.ty;
 
-    // Retrieves the fields of the Struct as continuous String
-    let column_names = macro_data.get_struct_fields_as_strings();
-
-    // Retrieves the fields of the Struct
-    let fields = macro_data.get_struct_fields();
+    // Retrieves the columns and the fields of the Struct whose values are inserted
+    let insert_columns = macro_data.get_column_names_pk_parsed().join(", ");
+    let insert_fields = macro_data.get_insert_fields();
 
-    let macro_fields = fields.iter().map(|field| quote! { &instance.#field });
+    let macro_fields = insert_fields
+        .iter()
+        .map(|field| query_parameter_for_field(&quote! { instance }, field));
     let macro_fields_cloned = macro_fields.clone();
 
-    let pk = macro_data.get_primary_key_annotation().unwrap_or_default();
-
-    let pk_ident_type = macro_data
-        ._fields_with_types()
-        .into_iter()
-        .find(|(i, _t)| *i == pk);
-
-    let multi_insert_transaction = if let Some(pk_data) = &pk_ident_type {
-        let pk_ident = &pk_data.0;
-        let pk_type = &pk_data.1;
-
-        let postgres_enabled = cfg!(feature = "postgres");
-        let mssql_enabled = cfg!(feature = "mssql");
+    // The columns of the `#[embedded]` fields are appended after the other ones
+    let embedded_fields = macro_data.get_embedded_fields();
+    let insert_columns = if embedded_fields.is_empty() {
+        quote! { #insert_columns }
+    } else {
+        let embedded_columns = embedded_columns(&embedded_fields);
+        quote! { canyon_sql::crud::embedded::append_columns(#insert_columns, &#embedded_columns) }
+    };
+    let extend_embedded_values = extend_with_embedded_parameters(
+        &quote! { longer_lived },
+        &quote! { instance },
+        &embedded_fields,
+    );
+
+    // The `before_insert` hook and the validation of every instance are done before
+    // generating the keys created by Canyon and filling the `#[created_at]` and
+    // `#[updated_at]` fields with the current time
+    let fill_instance_generated_keys =
+        generate_fill_generated_keys_tokens(macro_data, &quote! { instance });
+    let fill_instance_timestamps =
+        generate_fill_timestamps_tokens(macro_data, &quote! { instance });
+    let fill_timestamps = quote! {
+        for instance in instances.iter_mut() {
+            canyon_sql::crud::EntityHooks::before_insert(&mut **instance).await?;
+            canyon_sql::crud::Validate::validate(&**instance)?;
+            #fill_instance_generated_keys
+            #fill_instance_timestamps
+        }
+    };
 
-        let match_multi_insert_rows = if postgres_enabled && mssql_enabled {
+    // With a primary key, every column of it is returned and assigned back to the instances
+    let (returning, rows, rows_handling) = if macro_data.type_has_primary_key() {
+        let (postgres_assignments, mssql_assignments, mock_assignments) =
+            generate_returned_pk_assignments(macro_data, &quote! { instance });
+        let match_multi_insert_rows = generate_rows_match_arms(
             quote! {
-                canyon_sql::crud::CanyonRows::Postgres(mut v) => {
-                    for (idx, instance) in instances.iter_mut().enumerate() {
-                        instance.#pk_ident = v
-                            .get(idx)
-                            .expect("Failed getting the returned IDs for a multi insert")
-                            .get::<&str, #pk_type>(#pk);
-                    }
-
-                    Ok(())
+                for (idx, instance) in instances.iter_mut().enumerate() {
+                    let row = v
+                        .get(idx)
+                        .ok_or("Failed getting the returned IDs for a multi insert")?;
+                    #postgres_assignments
                 }
-                canyon_sql::crud::CanyonRows::Tiberius(mut v) => {
-                    for (idx, instance) in instances.iter_mut().enumerate() {
-                        instance.#pk_ident = v
-                            .get(idx)
-                            .expect("Failed getting the returned IDs for a multi insert")
-                            .get::<#pk_type, &str>(#pk)
-                            .expect("SQL Server primary key type failed to be set as value");
-                    }
 
-                    Ok(())
-                }
-            }
-        } else if postgres_enabled {
+                Ok(())
+            },
             quote! {
-                canyon_sql::crud::CanyonRows::Postgres(mut v) => {
-                    for (idx, instance) in instances.iter_mut().enumerate() {
-                        instance.#pk_ident = v
-                            .get(idx)
-                            .expect("Failed getting the returned IDs for a multi insert")
-                            .get::<&str, #pk_type>(#pk);
-                    }
-
-                    Ok(())
+                for (idx, instance) in instances.iter_mut().enumerate() {
+                    let row = v
+                        .get(idx)
+                        .ok_or("Failed getting the returned IDs for a multi insert")?;
+                    #mssql_assignments
                 }
-            }
-        } else if mssql_enabled {
-            quote! {
-                canyon_sql::crud::CanyonRows::Tiberius(mut v) => {
-                    for (idx, instance) in instances.iter_mut().enumerate() {
-                        instance.#pk_ident = v
-                            .get(idx)
-                            .expect("Failed getting the returned IDs for a multi insert")
-                            .get::<#pk_type, &str>(#pk)
-                            .expect("SQL Server primary key type failed to be set as value");
-                    }
 
-                    Ok(())
-                }
-            }
-        } else {
+                Ok(())
+            },
             quote! {
-                panic!(
-                    "Reached a branch in the implementation of the Row Mapper macro that should never be reached.\
-                    This is a severe bug of Canyon-SQL. Please, open us an issue at \
-                    https://github.com/zerodaycode/Canyon-SQL/issues and let us know about that failure."
-                )
-            }
-        };
+                let mut rows = v.into_iter();
+                for instance in instances.iter_mut() {
+                    let returned = rows
+                        .next()
+                        .ok_or("Failed getting the returned IDs for a multi insert")?
+                        .into_value::<#ty>()?;
+                    #mock_assignments
+                }
 
-        quote! {
-            mapped_fields = #column_names
-                .split(", ")
-                .map( |column_name| format!("\"{}\"", column_name))
-                .collect::<Vec<String>>()
-                .join(", ");
-
-            let mut split = mapped_fields.split(", ")
-                .collect::<Vec<&str>>();
-
-            let pk_value_index = split.iter()
-                .position(|pk| *pk == format!("\"{}\"", #pk).as_str())
-                .expect("Error. No primary key found when should be there");
-            split.retain(|pk| *pk != format!("\"{}\"", #pk).as_str());
-            mapped_fields = split.join(", ").to_string();
-
-            let mut fields_placeholders = String::new();
-
-            let mut elements_counter = 0;
-            let mut values_counter = 1;
-            let values_arr_len = final_values.len();
-
-            for vector in final_values.iter_mut() {
-                let mut inner_counter = 0;
-                fields_placeholders.push('(');
-                vector.remove(pk_value_index);
-
-                for _value in vector.iter() {
-                    if inner_counter < vector.len() - 1 {
-                        fields_placeholders.push_str(&("$".to_owned() + &values_counter.to_string() + ","));
-                    } else {
-                        fields_placeholders.push_str(&("$".to_owned() + &values_counter.to_string()));
-                    }
+                Ok(())
+            },
+        );
 
-                    inner_counter += 1;
-                    values_counter += 1;
+        (
+            format!(" RETURNING {}", macro_data.primary_key_columns_list()),
+            quote! { multi_insert_result },
+            quote! {
+                match multi_insert_result {
+                    #match_multi_insert_rows
+                    _ => panic!() // TODO remove when the generics will be refactored
                 }
+            },
+        )
+    } else {
+        (String::new(), quote! { _ }, quote! { Ok(()) })
+    };
 
-                elements_counter += 1;
+    let multi_insert_transaction = quote! {
+        let mut fields_placeholders = String::new();
 
-                if elements_counter < values_arr_len {
-                    fields_placeholders.push_str("), ");
-                } else {
-                    fields_placeholders.push(')');
-                }
-            }
+        let mut elements_counter = 0;
+        let mut values_counter = 1;
+        let values_arr_len = final_values.len();
 
-            let stmt = format!(
-                "INSERT INTO {} ({}) VALUES {} RETURNING {}",
-                #table_schema_data,
-                mapped_fields,
-                fields_placeholders,
-                #pk
-            );
+        for vector in final_values.iter() {
+            let mut inner_counter = 0;
+            fields_placeholders.push('(');
 
-            let mut v_arr = Vec::new();
-            for arr in final_values.iter() {
-                for value in arr {
-                    v_arr.push(*value)
+            for _value in vector.iter() {
+                if inner_counter < vector.len() - 1 {
+                    fields_placeholders.push_str(&("$".to_owned() + &values_counter.to_string() + ","));
+                } else {
+                    fields_placeholders.push_str(&("$".to_owned() + &values_counter.to_string()));
                 }
-            }
-
-            let multi_insert_result = <#ty as canyon_sql::crud::Transaction<#ty>>::query(
-                stmt,
-                v_arr,
-                datasource_name
-            ).await?;
 
-            match multi_insert_result {
-                #match_multi_insert_rows
-                _ => panic!() // TODO remove when the generics will be refactored
+                inner_counter += 1;
+                values_counter += 1;
             }
-        }
-    } else {
-        quote! {
-            mapped_fields = #column_names
-                .split(", ")
-                .map( |column_name| format!("\"{}\"", column_name))
-                .collect::<Vec<String>>()
-                .join(", ");
-
-            let mut split = mapped_fields.split(", ")
-                .collect::<Vec<&str>>();
-
-            let mut fields_placeholders = String::new();
-
-            let mut elements_counter = 0;
-            let mut values_counter = 1;
-            let values_arr_len = final_values.len();
-
-            for vector in final_values.iter_mut() {
-                let mut inner_counter = 0;
-                fields_placeholders.push('(');
-
-                for _value in vector.iter() {
-                    if inner_counter < vector.len() - 1 {
-                        fields_placeholders.push_str(&("$".to_owned() + &values_counter.to_string() + ","));
-                    } else {
-                        fields_placeholders.push_str(&("$".to_owned() + &values_counter.to_string()));
-                    }
 
-                    inner_counter += 1;
-                    values_counter += 1;
-                }
+            elements_counter += 1;
 
-                elements_counter += 1;
-
-                if elements_counter < values_arr_len {
-                    fields_placeholders.push_str("), ");
-                } else {
-                    fields_placeholders.push(')');
-                }
+            if elements_counter < values_arr_len {
+                fields_placeholders.push_str("), ");
+            } else {
+                fields_placeholders.push(')');
             }
+        }
 
-            let stmt = format!(
-                "INSERT INTO {} ({}) VALUES {}",
-                #table_schema_data,
-                mapped_fields,
-                fields_placeholders
-            );
-
-            let mut v_arr = Vec::new();
-            for arr in final_values.iter() {
-                for value in arr {
-                    v_arr.push(*value)
-                }
+        let stmt = format!(
+            "INSERT INTO {} ({}) VALUES {}{}",
+            #table_schema_data,
+            #insert_columns,
+            fields_placeholders,
+            #returning
+        );
+
+        let mut v_arr = Vec::new();
+        for arr in final_values.iter() {
+            for value in arr {
+                v_arr.push(*value)
             }
+        }
 
-            <#ty as canyon_sql::crud::Transaction<#ty>>::query(
-                stmt,
-                v_arr,
-                datasource_name
-            ).await?;
+        let #rows = <#ty as canyon_sql::crud::Transaction<#ty>>::query_returning(
+            stmt,
+            v_arr,
+            datasource_name
+        ).await?;
 
-            Ok(())
-        }
+        #rows_handling
     };
 
     quote! {
@@ -500,6 +443,7 @@ pub fn generate_multiple_insert_tokens(
         ) {
             use canyon_sql::crud::bounds::QueryParameter;
             let datasource_name = "";
+            #fill_timestamps
 
             let mut final_values: Vec<Vec<&dyn QueryParameter<'_>>> = Vec::new();
             for instance in instances.iter() {
@@ -509,13 +453,20 @@ pub fn generate_multiple_insert_tokens(
                 for value in intermediate.into_iter() {
                     longer_lived.push(*value)
                 }
+                #extend_embedded_values
 
                 final_values.push(longer_lived)
             }
 
-            let mut mapped_fields: String = String::new();
+            let inserted: Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>> = {
+                #multi_insert_transaction
+            };
+            inserted?;
+            for instance in instances.iter_mut() {
+                canyon_sql::crud::EntityHooks::after_insert(&mut **instance).await?;
+            }
 
-            #multi_insert_transaction
+            Ok(())
         }
 
         /// Inserts multiple instances of some type `T` into its related table with the specified
@@ -556,6 +507,7 @@ pub fn generate_multiple_insert_tokens(
             Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>>
         ) {
             use canyon_sql::crud::bounds::QueryParameter;
+            #fill_timestamps
 
             let mut final_values: Vec<Vec<&dyn QueryParameter<'_>>> = Vec::new();
             for instance in instances.iter() {
@@ -565,13 +517,154 @@ pub fn generate_multiple_insert_tokens(
                 for value in intermediate.into_iter() {
                     longer_lived.push(*value)
                 }
+                #extend_embedded_values
 
                 final_values.push(longer_lived)
             }
 
-            let mut mapped_fields: String = String::new();
+            let inserted: Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>> = {
+                #multi_insert_transaction
+            };
+            inserted?;
+            for instance in instances.iter_mut() {
+                canyon_sql::crud::EntityHooks::after_insert(&mut **instance).await?;
+            }
 
-            #multi_insert_transaction
+            Ok(())
+        }
+    }
+}
+
+/// Generates the assignments of the current time to the fields annotated with `#[created_at]`
+/// and `#[updated_at]` of the `instance` that is about to be inserted
+fn generate_fill_timestamps_tokens(
+    macro_data: &MacroTokens,
+    instance: &TokenStream,
+) -> TokenStream {
+    let timestamp_fields = macro_data.get_insert_timestamp_fields();
+    if timestamp_fields.is_empty() {
+        return quote! {};
+    }
+
+    let now = quote! { now };
+    let assignments = timestamp_fields.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        let timestamp = timestamp_for_field(&now, field);
+        quote! { #instance.#ident = #timestamp; }
+    });
+
+    quote! {
+        let #now = canyon_sql::date_time::Utc::now();
+        #(#assignments)*
+    }
+}
+
+/// Generates the new values of the primary keys declared with
+/// `#[primary_key(generated = "uuid_v4" | "uuid_v7")]` for the `instance` that is about
+/// to be inserted. The keys that already have a value different from the default one are kept
+fn generate_fill_generated_keys_tokens(
+    macro_data: &MacroTokens,
+    instance: &TokenStream,
+) -> TokenStream {
+    let assignments =
+        macro_data
+            .get_client_generated_keys()
+            .into_iter()
+            .map(|(field, generated)| {
+                let ident = field.ident.as_ref().unwrap();
+                let ty = &field.ty;
+                let new_uuid = match generated {
+                    PrimaryKeyGeneration::UuidV7 => quote! { canyon_sql::uuid::Uuid::now_v7() },
+                    _ => quote! { canyon_sql::uuid::Uuid::new_v4() },
+                };
+                quote! {
+                    if #instance.#ident == <#ty as Default>::default() {
+                        #instance.#ident = #new_uuid.into();
+                    }
+                }
+            });
+
+    quote! { #(#assignments)* }
+}
+
+/// Generates the assignments of the primary key values of a returned `row` (already
+/// bound to a variable called `row`) to the fields of the `instance` just inserted,
+/// for the PostgreSQL and the SQL Server rows respectively, and of the primary key
+/// fields of the `returned` instance of the rows of the mock datasources
+fn generate_returned_pk_assignments(
+    macro_data: &MacroTokens,
+    instance: &TokenStream,
+) -> (TokenStream, TokenStream, TokenStream) {
+    let primary_key_fields = macro_data.get_primary_key_fields();
+
+    let postgres_assignments = primary_key_fields.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let column = column_name(field);
+        quote! {
+            #instance.#ident =
+                <#ty as canyon_sql::crud::types::FieldValue>::from_postgres_row(row, #column)?;
+        }
+    });
+    let mssql_assignments = primary_key_fields.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let column = column_name(field);
+        quote! {
+            #instance.#ident =
+                <#ty as canyon_sql::crud::types::FieldValue>::from_sqlserver_row(row, #column)?;
+        }
+    });
+
+    let mock_assignments = primary_key_fields.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        quote! { #instance.#ident = returned.#ident; }
+    });
+
+    (
+        quote! { #(#postgres_assignments)* },
+        quote! { #(#mssql_assignments)* },
+        quote! { #(#mock_assignments)* },
+    )
+}
+
+/// Generates the match arms over the [`CanyonRows`] returned by the database, with
+/// the handling for the rows of every enabled database (bound to a variable called `v`),
+/// and for the rows of the mock datasources, if enabled
+fn generate_rows_match_arms(
+    postgres: TokenStream,
+    mssql: TokenStream,
+    mock: TokenStream,
+) -> TokenStream {
+    let postgres_enabled = cfg!(feature = "postgres");
+    let mssql_enabled = cfg!(feature = "mssql");
+    let mock_arm = cfg!(feature = "mock").then(|| {
+        quote! { canyon_sql::crud::CanyonRows::Mock(v) => { #mock } }
+    });
+
+    if postgres_enabled && mssql_enabled {
+        quote! {
+            canyon_sql::crud::CanyonRows::Postgres(v) => { #postgres }
+            canyon_sql::crud::CanyonRows::Tiberius(v) => { #mssql }
+            #mock_arm
+        }
+    } else if postgres_enabled {
+        quote! {
+            canyon_sql::crud::CanyonRows::Postgres(v) => { #postgres }
+            #mock_arm
+        }
+    } else if mssql_enabled {
+        quote! {
+            canyon_sql::crud::CanyonRows::Tiberius(v) => { #mssql }
+            #mock_arm
+        }
+    } else {
+        quote! {
+            panic!(
+                "Reached a branch in the implementation of the Row Mapper macro that should never be reached.\
+                This is a severe bug of Canyon-SQL. Please, open us an issue at \
+                https://github.com/zerodaycode/Canyon-SQL/issues and let us know about that failure."
+            )
         }
     }
 }
@@ -1,4 +1,6 @@
 pub mod delete;
 pub mod insert;
+pub mod relations;
 pub mod select;
 pub mod update;
+pub mod validate;
@@ -0,0 +1,266 @@
+use canyon_entities::{
+    entity_relation::{EntityRelation, EntityRelationKind},
+    field_annotation::EntityFieldAnnotation,
+};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+
+use super::select::soft_delete_clause;
+use crate::utils::helpers::{column_name, database_table_name_to_struct_ident};
+use crate::utils::macro_tokens::MacroTokens;
+
+/// Generates the `<Type>Relation` type, with a constant for every field annotated with
+/// `#[foreign_key]` that describes the relation with its parent entity, used to eager
+/// load the parents of the entities (or the children of the parents) with one query,
+/// and a constant for every `#[many_to_many]` relation of the entity.
+///
+/// Also, the accessors of the entities related by the `#[has_many]` and `#[many_to_many]`
+/// annotations, and the implementation of the `RelatedEntity` trait for the entities
+/// with a single column primary key
+pub fn generate_relations_tokens(
+    macro_data: &MacroTokens<'_>,
+    table_schema_data: &String,
+) -> TokenStream {
+    let ty = macro_data.ty;
+    let vis = macro_data.vis;
+    let soft_delete_clause = soft_delete_clause(macro_data);
+
+    let relations = macro_data
+        .get_fk_annotations()
+        .into_iter()
+        .filter_map(|(field, fk_annot)| match fk_annot {
+            EntityFieldAnnotation::ForeignKey(table, column) => Some((field, table, column)),
+            _ => None,
+        })
+        .map(|(field, table, column)| {
+            let field_ident = field.ident.as_ref().unwrap();
+            let field_ty = &field.ty;
+            let fk_ty = database_table_name_to_struct_ident(&table);
+            let fk_column = format!("\"{}\"", column_name(field));
+
+            quote! {
+                /// The relation with the parent entity referenced by the foreign key
+                /// of the field with the same name
+                pub const #field_ident: canyon_sql::crud::Relation<#ty, #fk_ty, #field_ty> =
+                    canyon_sql::crud::Relation::new(
+                        #table,
+                        #column,
+                        #table_schema_data,
+                        #fk_column,
+                        #soft_delete_clause,
+                        |child| &child.#field_ident,
+                    );
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let entity_relations = match EntityRelation::from_attributes(ty, macro_data.attrs) {
+        Ok(entity_relations) => entity_relations,
+        Err(error) => return error.into_compile_error(),
+    };
+    let many_to_many_relations = entity_relations
+        .iter()
+        .filter_map(|relation| match &relation.kind {
+            EntityRelationKind::ManyToMany {
+                through,
+                foreign_key,
+                related_key,
+            } => {
+                let name = &relation.name;
+                let related = &relation.related;
+                Some(quote! {
+                    /// The many to many relation with the related entity through the join table
+                    pub const #name: canyon_sql::crud::ManyToMany<#ty, #related> =
+                        canyon_sql::crud::ManyToMany::new(#through, #foreign_key, #related_key);
+                })
+            }
+            EntityRelationKind::HasMany { .. } => None,
+        })
+        .collect::<Vec<_>>();
+
+    let relation_ty = Ident::new(&format!("{ty}Relation"), Span::call_site());
+    let relation_ty_tokens = if relations.is_empty() && many_to_many_relations.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            /// Auto-generated type that holds the foreign key relations of the related
+            /// type with its parents, used to eager load them with the `with` and the
+            /// `with_children` methods of the [`SelectQueryBuilder`], and its many to
+            /// many relations
+            #vis struct #relation_ty;
+
+            #[allow(non_upper_case_globals)]
+            impl #relation_ty {
+                #(#relations)*
+                #(#many_to_many_relations)*
+            }
+        }
+    };
+
+    let accessors = entity_relations
+        .iter()
+        .map(|relation| generate_relation_accessors_tokens(&relation_ty, relation))
+        .collect::<Vec<_>>();
+    let accessors_tokens = if accessors.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            impl #ty {
+                #(#accessors)*
+            }
+        }
+    };
+
+    let related_entity_tokens =
+        generate_related_entity_tokens(macro_data, table_schema_data, &soft_delete_clause);
+
+    quote! {
+        #relation_ty_tokens
+        #accessors_tokens
+        #related_entity_tokens
+    }
+}
+
+/// Generates the accessors of the entities related by a `#[has_many]` or a
+/// `#[many_to_many]` annotation, along with the operations over the join table
+/// of the latter
+fn generate_relation_accessors_tokens(
+    relation_ty: &Ident,
+    relation: &EntityRelation,
+) -> TokenStream {
+    let name = &relation.name;
+    let related = &relation.related;
+    let name_datasource = Ident::new(&format!("{name}_datasource"), Span::call_site());
+
+    match &relation.kind {
+        EntityRelationKind::HasMany { foreign_key } => {
+            let related_relation_ty = relation.related_type_with_suffix("Relation");
+            quote! {
+                /// Retrieves the related entities whose foreign key references this entity
+                pub async fn #name(&self) ->
+                    Result<Vec<#related>, Box<dyn std::error::Error + Sync + Send + 'static>>
+                {
+                    self.#name_datasource("").await
+                }
+
+                /// Retrieves the related entities whose foreign key references this entity,
+                /// with the specified datasource
+                pub async fn #name_datasource<'a>(&self, datasource_name: &'a str) ->
+                    Result<Vec<#related>, Box<dyn std::error::Error + Sync + Send + 'static>>
+                {
+                    #related_relation_ty::#foreign_key
+                        .search_children(self, datasource_name)
+                        .await
+                }
+            }
+        }
+        EntityRelationKind::ManyToMany { .. } => {
+            let operation = |prefix: &str| {
+                (
+                    Ident::new(&format!("{prefix}_{name}"), Span::call_site()),
+                    Ident::new(&format!("{prefix}_{name}_datasource"), Span::call_site()),
+                )
+            };
+            let (attach, attach_datasource) = operation("attach");
+            let (detach, detach_datasource) = operation("detach");
+            let (sync, sync_datasource) = operation("sync");
+
+            quote! {
+                /// Retrieves the entities related with this one on the join table
+                pub async fn #name(&self) ->
+                    Result<Vec<#related>, Box<dyn std::error::Error + Sync + Send + 'static>>
+                {
+                    self.#name_datasource("").await
+                }
+
+                /// Retrieves the entities related with this one on the join table,
+                /// with the specified datasource
+                pub async fn #name_datasource<'a>(&self, datasource_name: &'a str) ->
+                    Result<Vec<#related>, Box<dyn std::error::Error + Sync + Send + 'static>>
+                {
+                    #relation_ty::#name.load(self, datasource_name).await
+                }
+
+                /// Relates the given entities with this one on the join table
+                pub async fn #attach(&self, related: &[&#related]) ->
+                    Result<(), Box<dyn std::error::Error + Sync + Send + 'static>>
+                {
+                    self.#attach_datasource(related, "").await
+                }
+
+                /// Relates the given entities with this one on the join table,
+                /// with the specified datasource
+                pub async fn #attach_datasource<'a>(&self, related: &[&#related], datasource_name: &'a str) ->
+                    Result<(), Box<dyn std::error::Error + Sync + Send + 'static>>
+                {
+                    #relation_ty::#name.attach(self, related, datasource_name).await
+                }
+
+                /// Removes the relations of the given entities with this one from the join table
+                pub async fn #detach(&self, related: &[&#related]) ->
+                    Result<(), Box<dyn std::error::Error + Sync + Send + 'static>>
+                {
+                    self.#detach_datasource(related, "").await
+                }
+
+                /// Removes the relations of the given entities with this one from the join
+                /// table, with the specified datasource
+                pub async fn #detach_datasource<'a>(&self, related: &[&#related], datasource_name: &'a str) ->
+                    Result<(), Box<dyn std::error::Error + Sync + Send + 'static>>
+                {
+                    #relation_ty::#name.detach(self, related, datasource_name).await
+                }
+
+                /// Replaces the relations of this entity on the join table with the given entities
+                pub async fn #sync(&self, related: &[&#related]) ->
+                    Result<(), Box<dyn std::error::Error + Sync + Send + 'static>>
+                {
+                    self.#sync_datasource(related, "").await
+                }
+
+                /// Replaces the relations of this entity on the join table with the given
+                /// entities, with the specified datasource
+                pub async fn #sync_datasource<'a>(&self, related: &[&#related], datasource_name: &'a str) ->
+                    Result<(), Box<dyn std::error::Error + Sync + Send + 'static>>
+                {
+                    #relation_ty::#name.sync(self, related, datasource_name).await
+                }
+            }
+        }
+    }
+}
+
+/// Generates the implementation of the `RelatedEntity` trait, only for the entities
+/// with a single column primary key
+fn generate_related_entity_tokens(
+    macro_data: &MacroTokens<'_>,
+    table_schema_data: &String,
+    soft_delete_clause: &str,
+) -> TokenStream {
+    let ty = macro_data.ty;
+    let primary_key = match macro_data.get_primary_key_fields().as_slice() {
+        [primary_key] => *primary_key,
+        _ => return quote! {},
+    };
+    let pk_ident = primary_key.ident.as_ref().unwrap();
+    let pk_ty = &primary_key.ty;
+    let pk_column = column_name(primary_key);
+
+    quote! {
+        impl canyon_sql::crud::RelatedEntity for #ty {
+            type PrimaryKey = #pk_ty;
+
+            const TABLE: &'static str = #table_schema_data;
+            const PRIMARY_KEY: &'static str = #pk_column;
+            const FILTER: &'static str = #soft_delete_clause;
+
+            fn primary_key(&self) -> &dyn canyon_sql::crud::bounds::QueryParameter<'_> {
+                &self.#pk_ident
+            }
+
+            fn primary_key_value(&self) -> &Self::PrimaryKey {
+                &self.#pk_ident
+            }
+        }
+    }
+}
@@ -13,7 +13,10 @@ pub fn generate_find_all_unchecked_tokens(
     table_schema_data: &String,
 ) -> TokenStream {
     let ty = macro_data.ty;
-    let stmt = format!("SELECT * FROM {table_schema_data}");
+    let stmt = match macro_data.soft_delete_filter() {
+        Some(filter) => format!("SELECT * FROM {table_schema_data} WHERE {filter}"),
+        None => format!("SELECT * FROM {table_schema_data}"),
+    };
 
     quote! {
         /// Performs a `SELECT * FROM table_name`, where `table_name` it's
@@ -21,13 +24,17 @@ pub fn generate_find_all_unchecked_tokens(
         /// database convention. P.ej. PostgreSQL prefers table names declared
         /// with snake_case identifiers.
         async fn find_all_unchecked<'a>() -> Vec<#ty> {
-            <#ty as canyon_sql::crud::Transaction<#ty>>::query(
-                #stmt,
-                &[],
-                ""
+            canyon_sql::crud::run_after_load_hooks(
+                <#ty as canyon_sql::crud::Transaction<#ty>>::query(
+                    #stmt,
+                    &[],
+                    ""
+                ).await
+                .unwrap()
+                .into_results::<#ty>()
+                .unwrap()
             ).await
             .unwrap()
-            .into_results::<#ty>()
         }
 
         /// Performs a `SELECT * FROM table_name`, where `table_name` it's
@@ -39,13 +46,17 @@ pub fn generate_find_all_unchecked_tokens(
         /// described in the configuration file, and selected with the [`&str`]
         /// passed as parameter.
         async fn find_all_unchecked_datasource<'a>(datasource_name: &'a str) -> Vec<#ty> {
-            <#ty as canyon_sql::crud::Transaction<#ty>>::query(
-                #stmt,
-                &[],
-                datasource_name
+            canyon_sql::crud::run_after_load_hooks(
+                <#ty as canyon_sql::crud::Transaction<#ty>>::query(
+                    #stmt,
+                    &[],
+                    datasource_name
+                ).await
+                .unwrap()
+                .into_results::<#ty>()
+                .unwrap()
             ).await
             .unwrap()
-            .into_results::<#ty>()
         }
     }
 }
@@ -57,7 +68,10 @@ pub fn generate_find_all_tokens(
     table_schema_data: &String,
 ) -> TokenStream {
     let ty = macro_data.ty;
-    let stmt = format!("SELECT * FROM {table_schema_data}");
+    let stmt = match macro_data.soft_delete_filter() {
+        Some(filter) => format!("SELECT * FROM {table_schema_data} WHERE {filter}"),
+        None => format!("SELECT * FROM {table_schema_data}"),
+    };
 
     quote! {
         /// Performs a `SELECT * FROM table_name`, where `table_name` it's
@@ -67,14 +81,14 @@ pub fn generate_find_all_tokens(
         async fn find_all<'a>() ->
             Result<Vec<#ty>, Box<(dyn std::error::Error + Send + Sync + 'static)>>
         {
-            Ok(
+            canyon_sql::crud::run_after_load_hooks(
                 <#ty as canyon_sql::crud::Transaction<#ty>>::query(
                     #stmt,
                     &[],
                     ""
                 ).await?
-                .into_results::<#ty>()
-            )
+                .into_results::<#ty>()?
+            ).await
         }
 
         /// Performs a `SELECT * FROM table_name`, where `table_name` it's
@@ -92,14 +106,14 @@ pub fn generate_find_all_tokens(
         async fn find_all_datasource<'a>(datasource_name: &'a str) ->
             Result<Vec<#ty>, Box<(dyn std::error::Error + Send + Sync + 'static)>>
         {
-            Ok(
+            canyon_sql::crud::run_after_load_hooks(
                 <#ty as canyon_sql::crud::Transaction<#ty>>::query(
                     #stmt,
                     &[],
                     datasource_name
                 ).await?
-                .into_results::<#ty>()
-            )
+                .into_results::<#ty>()?
+            ).await
         }
     }
 }
@@ -110,6 +124,11 @@ pub fn generate_find_all_query_tokens(
     table_schema_data: &String,
 ) -> TokenStream {
     let ty = macro_data.ty;
+    // The rows marked as deleted are discarded by querying a derived table
+    let select_source = match macro_data.soft_delete_filter() {
+        Some(filter) => filtered_table_source(table_schema_data, &filter),
+        None => table_schema_data.to_owned(),
+    };
 
     quote! {
         /// Generates a [`canyon_sql::query::SelectQueryBuilder`]
@@ -120,7 +139,7 @@ pub fn generate_find_all_query_tokens(
         /// unless concrete values are set on the available parameters of the
         /// `canyon_macro(table_name = "table_name", schema = "schema")`
         fn select_query<'a>() -> canyon_sql::query::SelectQueryBuilder<'a, #ty> {
-            canyon_sql::query::SelectQueryBuilder::new(#table_schema_data, "")
+            canyon_sql::query::SelectQueryBuilder::new(#select_source, "")
         }
 
         /// Generates a [`canyon_sql::query::SelectQueryBuilder`]
@@ -135,7 +154,7 @@ pub fn generate_find_all_query_tokens(
         /// described in the configuration file, and selected with the [`&str`]
         /// passed as parameter.
         fn select_query_datasource<'a>(datasource_name: &'a str) -> canyon_sql::query::SelectQueryBuilder<'a, #ty> {
-            canyon_sql::query::SelectQueryBuilder::new(#table_schema_data, datasource_name)
+            canyon_sql::query::SelectQueryBuilder::new(#select_source, datasource_name)
         }
     }
 }
@@ -148,7 +167,10 @@ pub fn generate_count_tokens(
 ) -> TokenStream {
     let ty = macro_data.ty;
     let ty_str = &ty.to_string();
-    let stmt = format!("SELECT COUNT (*) FROM {table_schema_data}");
+    let stmt = match macro_data.soft_delete_filter() {
+        Some(filter) => format!("SELECT COUNT (*) FROM {table_schema_data} WHERE {filter}"),
+        None => format!("SELECT COUNT (*) FROM {table_schema_data}"),
+    };
 
     let postgres_enabled = cfg!(feature = "postgres");
     let mssql_enabled = cfg!(feature = "mssql");
@@ -193,6 +215,18 @@ pub fn generate_count_tokens(
         }
     };
 
+    // The mock datasources returns the count as the `i64` value of the row
+    let mock_handling = cfg!(feature = "mock").then(|| {
+        quote! {
+            canyon_sql::crud::CanyonRows::Mock(v) => Ok(
+                v.into_iter()
+                    .next()
+                    .ok_or(format!("Failure in the COUNT query for the mock datasource for: {}", #ty_str))?
+                    .into_value::<i64>()?
+            ),
+        }
+    });
+
     quote! {
         /// Performs a COUNT(*) query over some table, returning a [`Result`] rather than panicking,
         /// wrapping a possible success or error coming from the database
@@ -204,6 +238,7 @@ pub fn generate_count_tokens(
             ).await?;
 
             match count {
+                #mock_handling
                 #result_handling
             }
         }
@@ -218,6 +253,7 @@ pub fn generate_count_tokens(
             ).await?;
 
             match count {
+                #mock_handling
                 #result_handling
             }
         }
@@ -230,13 +266,16 @@ pub fn generate_find_by_pk_tokens(
     table_schema_data: &String,
 ) -> TokenStream {
     let ty = macro_data.ty;
-    let pk = macro_data.get_primary_key_annotation().unwrap_or_default();
-    let stmt = format!("SELECT * FROM {table_schema_data} WHERE {pk} = $1");
+    let soft_delete_clause = soft_delete_clause(macro_data);
+    let stmt = format!(
+        "SELECT * FROM {table_schema_data} WHERE {}{soft_delete_clause}",
+        macro_data.primary_key_condition(1)
+    );
 
     // Disabled if there's no `primary_key` annotation
-    if pk.is_empty() {
+    if !macro_data.type_has_primary_key() {
         return quote! {
-            async fn find_by_pk<'a>(value: &'a dyn canyon_sql::crud::bounds::QueryParameter<'a>)
+            async fn find_by_pk<'a>(value: &'a dyn canyon_sql::crud::bounds::PrimaryKeyValues<'a>)
                 -> Result<Option<#ty>, Box<(dyn std::error::Error + Send + Sync + 'static)>>
             {
                 Err(
@@ -250,7 +289,7 @@ pub fn generate_find_by_pk_tokens(
             }
 
             async fn find_by_pk_datasource<'a>(
-                value: &'a dyn canyon_sql::crud::bounds::QueryParameter<'a>,
+                value: &'a dyn canyon_sql::crud::bounds::PrimaryKeyValues<'a>,
                 datasource_name: &'a str
             ) -> Result<Option<#ty>, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
                 Err(
@@ -265,12 +304,15 @@ pub fn generate_find_by_pk_tokens(
         };
     }
 
+    let pk_values = generate_pk_values_tokens(macro_data, &quote! { value });
     let result_handling = quote! {
         match result {
             n if n.len() == 0 => Ok(None),
-            _ => Ok(
-                Some(result.into_results::<#ty>().remove(0))
-            )
+            _ => {
+                let mut entity = result.into_results::<#ty>()?.remove(0);
+                canyon_sql::crud::EntityHooks::after_load(&mut entity).await?;
+                Ok(Some(entity))
+            }
         }
     };
 
@@ -280,6 +322,9 @@ pub fn generate_find_by_pk_tokens(
         /// filtering by the column that it's declared as the primary
         /// key on the database.
         ///
+        /// For the entities with a composite primary key, `value` must be a tuple
+        /// with the values of every key column, in the order they are declared.
+        ///
         /// This operation it's only available if the [`CanyonEntity`] contains
         /// some field declared as primary key.
         ///
@@ -287,12 +332,13 @@ pub fn generate_find_by_pk_tokens(
         /// querying the database, or, if no errors happens, a success containing
         /// and Option<T> with the data found wrapped in the Some(T) variant,
         /// or None if the value isn't found on the table.
-        async fn find_by_pk<'a>(value: &'a dyn canyon_sql::crud::bounds::QueryParameter<'a>) ->
+        async fn find_by_pk<'a>(value: &'a dyn canyon_sql::crud::bounds::PrimaryKeyValues<'a>) ->
             Result<Option<#ty>, Box<(dyn std::error::Error + Send + Sync + 'static)>>
         {
+            #pk_values
             let result = <#ty as canyon_sql::crud::Transaction<#ty>>::query(
                 #stmt,
-                vec![value],
+                pk_values,
                 ""
             ).await?;
 
@@ -304,6 +350,9 @@ pub fn generate_find_by_pk_tokens(
         /// filtering by the column that it's declared as the primary
         /// key on the database.
         ///
+        /// For the entities with a composite primary key, `value` must be a tuple
+        /// with the values of every key column, in the order they are declared.
+        ///
         /// The query it's made against the database with the configured datasource
         /// described in the configuration file, and selected with the [`&str`]
         /// passed as parameter.
@@ -316,13 +365,14 @@ pub fn generate_find_by_pk_tokens(
         /// and Option<T> with the data found wrapped in the Some(T) variant,
         /// or None if the value isn't found on the table.
         async fn find_by_pk_datasource<'a>(
-            value: &'a dyn canyon_sql::crud::bounds::QueryParameter<'a>,
+            value: &'a dyn canyon_sql::crud::bounds::PrimaryKeyValues<'a>,
             datasource_name: &'a str
         ) -> Result<Option<#ty>, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
 
+            #pk_values
             let result = <#ty as canyon_sql::crud::Transaction<#ty>>::query(
                 #stmt,
-                vec![value],
+                pk_values,
                 datasource_name
             ).await?;
 
@@ -331,6 +381,277 @@ pub fn generate_find_by_pk_tokens(
     }
 }
 
+/// Generates the TokenStream for build the __find_by_pks() CRUD operation, that retrieves
+/// in a single round trip all the rows whose primary key matches any of the provided values
+pub fn generate_find_by_pks_tokens(
+    macro_data: &MacroTokens<'_>,
+    table_schema_data: &String,
+) -> TokenStream {
+    let ty = macro_data.ty;
+    let primary_keys = macro_data.get_primary_keys();
+    let soft_delete_clause = soft_delete_clause(macro_data);
+
+    // Disabled if there's no `primary_key` annotation
+    if primary_keys.is_empty() {
+        return quote! {
+            async fn find_by_pks<'a>(
+                values: &'a [&'a dyn canyon_sql::crud::bounds::PrimaryKeyValues<'a>],
+                preserve_order: bool
+            ) -> Result<Vec<#ty>, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
+                Err(
+                    std::io::Error::new(
+                        std::io::ErrorKind::Unsupported,
+                        "You can't use the 'find_by_pks' associated function on a \
+                        CanyonEntity that does not have a #[primary_key] annotation. \
+                        If you need to perform an specific search, use the Querybuilder instead."
+                    ).into_inner().unwrap()
+                )
+            }
+
+            async fn find_by_pks_datasource<'a>(
+                values: &'a [&'a dyn canyon_sql::crud::bounds::PrimaryKeyValues<'a>],
+                preserve_order: bool,
+                datasource_name: &'a str
+            ) -> Result<Vec<#ty>, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
+                Err(
+                    std::io::Error::new(
+                        std::io::ErrorKind::Unsupported,
+                        "You can't use the 'find_by_pks_datasource' associated function on a \
+                        CanyonEntity that does not have a #[primary_key] annotation. \
+                        If you need to perform an specific search, use the Querybuilder instead."
+                    ).into_inner().unwrap()
+                )
+            }
+        };
+    }
+
+    // The placeholders are generated at runtime, since they depends on the number of values
+    // received. The `ORDER BY CASE` clause is the portable way (PostgreSQL and SQL Server)
+    // of sorting the results in the same order as the requested keys
+    let pk_values = generate_pk_values_tokens(macro_data, &quote! { value });
+    let where_clause = if let [pk] = primary_keys.as_slice() {
+        quote! {
+            let placeholders = (1..=values.len())
+                .map(|idx| format!("${idx}"))
+                .collect::<Vec<String>>()
+                .join(", ");
+            let mut stmt = format!(
+                "SELECT * FROM {} WHERE \"{}\" IN ({}){}",
+                #table_schema_data, #pk, placeholders, #soft_delete_clause
+            );
+
+            if preserve_order {
+                let cases = (1..=values.len())
+                    .map(|idx| format!("WHEN ${} THEN {}", idx, idx - 1))
+                    .collect::<Vec<String>>()
+                    .join(" ");
+                stmt.push_str(&format!(" ORDER BY CASE \"{}\" {} END", #pk, cases));
+            }
+        }
+    } else {
+        // The composite keys are matched column by column, since SQL Server
+        // doesn't support the row values on the `IN` clause
+        let pk_count = primary_keys.len();
+        quote! {
+            let conditions = (0..values.len())
+                .map(|value_idx| {
+                    let condition = [#(#primary_keys),*]
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, column)| {
+                            format!("\"{}\" = ${}", column, value_idx * #pk_count + idx + 1)
+                        })
+                        .collect::<Vec<String>>()
+                        .join(" AND ");
+                    format!("({condition})")
+                })
+                .collect::<Vec<String>>();
+            let mut stmt = format!(
+                "SELECT * FROM {} WHERE ({}){}",
+                #table_schema_data, conditions.join(" OR "), #soft_delete_clause
+            );
+
+            if preserve_order {
+                let cases = conditions
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, condition)| format!("WHEN {} THEN {}", condition, idx))
+                    .collect::<Vec<String>>()
+                    .join(" ");
+                stmt.push_str(&format!(" ORDER BY CASE {} END", cases));
+            }
+        }
+    };
+    let stmt_builder = quote! {
+        if values.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut params: Vec<&dyn canyon_sql::crud::bounds::QueryParameter<'_>> = Vec::new();
+        for value in values.iter() {
+            #pk_values
+            params.extend(pk_values);
+        }
+
+        #where_clause
+    };
+
+    quote! {
+        /// Finds all the elements on the queried table whose primary key matches
+        /// any of the provided `values`, performing just one query against the
+        /// database (`WHERE pk IN ($1, $2, ...)`). The composite primary keys
+        /// are matched column by column.
+        ///
+        /// If `preserve_order` is true, the returned entities are sorted in the
+        /// same order as the requested keys. Values without a matching row are
+        /// just ignored.
+        ///
+        /// This operation it's only available if the [`CanyonEntity`] contains
+        /// some field declared as primary key.
+        async fn find_by_pks<'a>(
+            values: &'a [&'a dyn canyon_sql::crud::bounds::PrimaryKeyValues<'a>],
+            preserve_order: bool
+        ) -> Result<Vec<#ty>, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
+            #stmt_builder
+
+            canyon_sql::crud::run_after_load_hooks(
+                <#ty as canyon_sql::crud::Transaction<#ty>>::query(
+                    stmt,
+                    params,
+                    ""
+                ).await?
+                .into_results::<#ty>()?
+            ).await
+        }
+
+        /// Finds all the elements on the queried table whose primary key matches
+        /// any of the provided `values`, performing just one query against the
+        /// database (`WHERE pk IN ($1, $2, ...)`). The composite primary keys
+        /// are matched column by column.
+        ///
+        /// If `preserve_order` is true, the returned entities are sorted in the
+        /// same order as the requested keys. Values without a matching row are
+        /// just ignored.
+        ///
+        /// The query it's made against the database with the configured datasource
+        /// described in the configuration file, and selected with the [`&str`]
+        /// passed as parameter.
+        ///
+        /// This operation it's only available if the [`CanyonEntity`] contains
+        /// some field declared as primary key.
+        async fn find_by_pks_datasource<'a>(
+            values: &'a [&'a dyn canyon_sql::crud::bounds::PrimaryKeyValues<'a>],
+            preserve_order: bool,
+            datasource_name: &'a str
+        ) -> Result<Vec<#ty>, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
+            #stmt_builder
+
+            canyon_sql::crud::run_after_load_hooks(
+                <#ty as canyon_sql::crud::Transaction<#ty>>::query(
+                    stmt,
+                    params,
+                    datasource_name
+                ).await?
+                .into_results::<#ty>()?
+            ).await
+        }
+    }
+}
+
+/// Generates the TokenStream for build the __exists_by_pk() CRUD operation
+pub fn generate_exists_by_pk_tokens(
+    macro_data: &MacroTokens<'_>,
+    table_schema_data: &String,
+) -> TokenStream {
+    let ty = macro_data.ty;
+    let soft_delete_clause = soft_delete_clause(macro_data);
+    let stmt = format!(
+        "SELECT 1 FROM {table_schema_data} WHERE {}{soft_delete_clause}",
+        macro_data.primary_key_condition(1)
+    );
+
+    // Disabled if there's no `primary_key` annotation
+    if !macro_data.type_has_primary_key() {
+        return quote! {
+            async fn exists_by_pk<'a>(value: &'a dyn canyon_sql::crud::bounds::PrimaryKeyValues<'a>)
+                -> Result<bool, Box<(dyn std::error::Error + Send + Sync + 'static)>>
+            {
+                Err(
+                    std::io::Error::new(
+                        std::io::ErrorKind::Unsupported,
+                        "You can't use the 'exists_by_pk' associated function on a \
+                        CanyonEntity that does not have a #[primary_key] annotation. \
+                        If you need to perform an specific search, use the Querybuilder instead."
+                    ).into_inner().unwrap()
+                )
+            }
+
+            async fn exists_by_pk_datasource<'a>(
+                value: &'a dyn canyon_sql::crud::bounds::PrimaryKeyValues<'a>,
+                datasource_name: &'a str
+            ) -> Result<bool, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
+                Err(
+                    std::io::Error::new(
+                        std::io::ErrorKind::Unsupported,
+                        "You can't use the 'exists_by_pk_datasource' associated function on a \
+                        CanyonEntity that does not have a #[primary_key] annotation. \
+                        If you need to perform an specific search, use the Querybuilder instead."
+                    ).into_inner().unwrap()
+                )
+            }
+        };
+    }
+
+    let pk_values = generate_pk_values_tokens(macro_data, &quote! { value });
+
+    quote! {
+        /// Checks if there's some row on the queried table whose primary key
+        /// matches the provided `value`, without retrieving and mapping
+        /// the entity. Composite primary keys are given as a tuple.
+        ///
+        /// This operation it's only available if the [`CanyonEntity`] contains
+        /// some field declared as primary key.
+        async fn exists_by_pk<'a>(value: &'a dyn canyon_sql::crud::bounds::PrimaryKeyValues<'a>) ->
+            Result<bool, Box<(dyn std::error::Error + Send + Sync + 'static)>>
+        {
+            #pk_values
+            Ok(
+                !<#ty as canyon_sql::crud::Transaction<#ty>>::query(
+                    #stmt,
+                    pk_values,
+                    ""
+                ).await?
+                .is_empty()
+            )
+        }
+
+        /// Checks if there's some row on the queried table whose primary key
+        /// matches the provided `value`, without retrieving and mapping
+        /// the entity. Composite primary keys are given as a tuple.
+        ///
+        /// The query it's made against the database with the configured datasource
+        /// described in the configuration file, and selected with the [`&str`]
+        /// passed as parameter.
+        ///
+        /// This operation it's only available if the [`CanyonEntity`] contains
+        /// some field declared as primary key.
+        async fn exists_by_pk_datasource<'a>(
+            value: &'a dyn canyon_sql::crud::bounds::PrimaryKeyValues<'a>,
+            datasource_name: &'a str
+        ) -> Result<bool, Box<(dyn std::error::Error + Send + Sync + 'static)>> {
+            #pk_values
+            Ok(
+                !<#ty as canyon_sql::crud::Transaction<#ty>>::query(
+                    #stmt,
+                    pk_values,
+                    datasource_name
+                ).await?
+                .is_empty()
+            )
+        }
+    }
+}
+
 /// Generates the TokenStream for build the search by foreign key feature, also as a method instance
 /// of a T type of as an associated function of same T type, but wrapped as a Result<T, Err>, representing
 /// a possible failure querying the database, a bad or missing FK annotation or a missed ForeignKeyable
@@ -340,8 +661,9 @@ pub fn generate_find_by_foreign_key_tokens(
 ) -> Vec<(TokenStream, TokenStream)> {
     let mut fk_quotes: Vec<(TokenStream, TokenStream)> = Vec::new();
 
-    for (field_ident, fk_annot) in macro_data.get_fk_annotations().iter() {
+    for (field, fk_annot) in macro_data.get_fk_annotations().iter() {
         if let EntityFieldAnnotation::ForeignKey(table, column) = fk_annot {
+            let field_ident = field.ident.as_ref().unwrap();
             let method_name = "search_".to_owned() + table;
 
             // TODO this is not a good implementation. We must try to capture the
@@ -373,9 +695,11 @@ pub fn generate_find_by_foreign_key_tokens(
             let result_handler = quote! {
                 match result {
                     n if n.len() == 0 => Ok(None),
-                    _ => Ok(Some(
-                        result.into_results::<#fk_ty>().remove(0)
-                    ))
+                    _ => {
+                        let mut entity = result.into_results::<#fk_ty>()?.remove(0);
+                        canyon_sql::crud::EntityHooks::after_load(&mut entity).await?;
+                        Ok(Some(entity))
+                    }
                 }
             };
 
@@ -426,8 +750,9 @@ pub fn generate_find_by_reverse_foreign_key_tokens(
 ) -> Vec<(TokenStream, TokenStream)> {
     let mut rev_fk_quotes: Vec<(TokenStream, TokenStream)> = Vec::new();
     let ty = macro_data.ty;
+    let soft_delete_clause = soft_delete_clause(macro_data);
 
-    for (field_ident, fk_annot) in macro_data.get_fk_annotations().iter() {
+    for (field, fk_annot) in macro_data.get_fk_annotations().iter() {
         if let EntityFieldAnnotation::ForeignKey(table, column) = fk_annot {
             let method_name = format!("search_{table}_childrens");
 
@@ -449,7 +774,7 @@ pub fn generate_find_by_reverse_foreign_key_tokens(
                     Result<Vec<#ty>, Box<(dyn std::error::Error + Send + Sync + 'static)>>
             };
 
-            let f_ident = field_ident.to_string();
+            let f_ident = column_name(field);
 
             rev_fk_quotes.push((
                 quote! { #quoted_method_signature; },
@@ -464,16 +789,17 @@ pub fn generate_find_by_reverse_foreign_key_tokens(
                             ).as_str());
 
                         let stmt = format!(
-                            "SELECT * FROM {} WHERE {} = $1",
+                            "SELECT * FROM {} WHERE {} = $1{}",
                             #table_schema_data,
-                            format!("\"{}\"", #f_ident).as_str()
+                            format!("\"{}\"", #f_ident).as_str(),
+                            #soft_delete_clause
                         );
 
-                        Ok(<#ty as canyon_sql::crud::Transaction<#ty>>::query(
+                        canyon_sql::crud::run_after_load_hooks(<#ty as canyon_sql::crud::Transaction<#ty>>::query(
                             stmt,
                             &[lookage_value],
                             ""
-                        ).await?.into_results::<#ty>())
+                        ).await?.into_results::<#ty>()?).await
                     }
                 },
             ));
@@ -492,16 +818,17 @@ pub fn generate_find_by_reverse_foreign_key_tokens(
                             ).as_str());
 
                         let stmt = format!(
-                            "SELECT * FROM {} WHERE {} = $1",
+                            "SELECT * FROM {} WHERE {} = $1{}",
                             #table_schema_data,
-                            format!("\"{}\"", #f_ident).as_str()
+                            format!("\"{}\"", #f_ident).as_str(),
+                            #soft_delete_clause
                         );
 
-                        Ok(<#ty as canyon_sql::crud::Transaction<#ty>>::query(
+                        canyon_sql::crud::run_after_load_hooks(<#ty as canyon_sql::crud::Transaction<#ty>>::query(
                             stmt,
                             &[lookage_value],
                             datasource_name
-                        ).await?.into_results::<#ty>())
+                        ).await?.into_results::<#ty>()?).await
                     }
                 },
             ));
@@ -510,3 +837,34 @@ pub fn generate_find_by_reverse_foreign_key_tokens(
 
     rev_fk_quotes
 }
+
+/// Returns the `AND` condition that must be appended to the filters of a query
+/// for discard the rows marked as deleted, or an empty [`String`] if the type
+/// does not contains a `#[soft_delete]` annotation
+pub fn soft_delete_clause(macro_data: &MacroTokens<'_>) -> String {
+    macro_data
+        .soft_delete_filter()
+        .map(|filter| format!(" AND {filter}"))
+        .unwrap_or_default()
+}
+
+/// Generates the retrieval of the query parameters of the primary key `value` received by the
+/// operations that searches by primary key, as a variable called `pk_values`. An error is
+/// returned if the number of values doesn't match the number of primary key columns
+pub fn generate_pk_values_tokens(macro_data: &MacroTokens<'_>, value: &TokenStream) -> TokenStream {
+    let ty = macro_data.ty;
+    let pk_count = macro_data.get_primary_keys().len();
+
+    quote! {
+        let pk_values = #value.pk_values();
+        if pk_values.len() != #pk_count {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "The primary key of {} has {} column(s), but {} value(s) were provided",
+                    stringify!(#ty), #pk_count, pk_values.len()
+                )
+            ).into());
+        }
+    }
+}
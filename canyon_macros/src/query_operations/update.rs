@@ -1,51 +1,158 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 
+use crate::query_operations::select::generate_pk_values_tokens;
+use crate::utils::helpers::{
+    column_identifier, column_name, embedded_columns, extend_with_embedded_parameters,
+    has_time_zone, query_parameter_for_field, timestamp_for_field,
+};
 use crate::utils::macro_tokens::MacroTokens;
 
 /// Generates the TokenStream for the __update() CRUD operation
 pub fn generate_update_tokens(macro_data: &MacroTokens, table_schema_data: &String) -> TokenStream {
     let ty = macro_data.ty;
 
-    let update_columns = macro_data.get_column_names_pk_parsed();
+    // Retrieves the persisted fields of the Struct
+    let fields = macro_data.get_persisted_fields();
+    let primary_keys = macro_data.get_primary_keys();
 
-    // Retrieves the fields of the Struct
-    let fields = macro_data.get_struct_fields();
+    // The `#[version]` column is incremented by the database, and its current value
+    // is used instead to check that the row wasn't modified since it was loaded
+    let version_column = macro_data.get_version_annotation();
+    let mut version_condition = String::new();
 
+    // Every field is passed as a query parameter in declaration order, so the placeholder
+    // of a column is given by the position of its field. The primary key columns are
+    // only used in the `WHERE` clause
     let mut vec_columns_values: Vec<String> = Vec::new();
-    for (i, column_name) in update_columns.iter().enumerate() {
-        let column_equal_value = format!("{} = ${}", column_name.to_owned(), i + 2);
-        vec_columns_values.push(column_equal_value)
+    let mut pk_conditions: Vec<String> = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        let column_name = column_name(field);
+        if primary_keys.contains(&column_name) {
+            pk_conditions.push(format!("\"{column_name}\" = ${}", i + 1));
+        } else if Some(&column_name) == version_column.as_ref() {
+            version_condition = format!(" AND \"{column_name}\" = ${}", i + 1);
+            vec_columns_values.push(format!("\"{column_name}\" = \"{column_name}\" + 1"));
+        } else {
+            vec_columns_values.push(format!("\"{column_name}\" = ${}", i + 1));
+        }
     }
 
     let str_columns_values = vec_columns_values.join(", ");
 
-    let update_values = fields.iter().map(|ident| {
-        quote! { &self.#ident }
+    // The `#[updated_at]` field is updated with the current time, instead of
+    // the value of the instance
+    let updated_at_field = macro_data.get_annotated_field("updated_at");
+    let updated_at_ident = updated_at_field.and_then(|field| field.ident.as_ref());
+    let updated_at_now = updated_at_now_tokens(macro_data);
+
+    let update_values = fields.iter().map(|field| {
+        if field.ident.as_ref() == updated_at_ident {
+            quote! { &updated_at_now }
+        } else {
+            query_parameter_for_field(&quote! { self }, field)
+        }
     });
     let update_values_cloned = update_values.clone();
 
-    if let Some(primary_key) = macro_data.get_primary_key_annotation() {
-        let pk_index = macro_data
-            .get_pk_index()
-            .expect("Update method failed to retrieve the index of the primary key");
+    // The columns of the `#[embedded]` fields are set after the other ones
+    let embedded_fields = macro_data.get_embedded_fields();
+    let (set_clause, update_values, update_values_cloned) = if embedded_fields.is_empty() {
+        (
+            quote! { #str_columns_values },
+            quote! {
+                let update_values: &[&dyn canyon_sql::crud::bounds::QueryParameter<'_>] = &[#(#update_values),*];
+            },
+            quote! {
+                let update_values: &[&dyn canyon_sql::crud::bounds::QueryParameter<'_>] = &[#(#update_values_cloned),*];
+            },
+        )
+    } else {
+        let embedded_columns = embedded_columns(&embedded_fields);
+        let first_placeholder = fields.len() + 1;
+        let extend_embedded_values = extend_with_embedded_parameters(
+            &quote! { update_values },
+            &quote! { self },
+            &embedded_fields,
+        );
+        (
+            quote! {
+                canyon_sql::crud::embedded::append_assignments(
+                    #str_columns_values,
+                    &#embedded_columns,
+                    #first_placeholder
+                )
+            },
+            quote! {
+                let mut update_values: Vec<&dyn canyon_sql::crud::bounds::QueryParameter<'_>> = vec![#(#update_values),*];
+                #extend_embedded_values
+            },
+            quote! {
+                let mut update_values: Vec<&dyn canyon_sql::crud::bounds::QueryParameter<'_>> = vec![#(#update_values_cloned),*];
+                #extend_embedded_values
+            },
+        )
+    };
+
+    if !primary_keys.is_empty() && vec_columns_values.is_empty() && embedded_fields.is_empty() {
+        // Every column is part of the primary key (like the ones of the join tables),
+        // so there's nothing to update
+        quote! {
+            /// The columns of the entity are all part of its primary key, so there's
+            /// nothing to update, but the hooks and the validations are still run
+            async fn update(&mut self) -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>> {
+                canyon_sql::crud::EntityHooks::before_update(self).await?;
+                canyon_sql::crud::Validate::validate(self)?;
+                canyon_sql::crud::EntityHooks::after_update(self).await
+            }
+
+            /// The columns of the entity are all part of its primary key, so there's
+            /// nothing to update, but the hooks and the validations are still run
+            async fn update_datasource<'a>(&mut self, datasource_name: &'a str)
+                -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>>
+            {
+                canyon_sql::crud::EntityHooks::before_update(self).await?;
+                canyon_sql::crud::Validate::validate(self)?;
+                canyon_sql::crud::EntityHooks::after_update(self).await
+            }
+        }
+    } else if !primary_keys.is_empty() {
+        let (rows, returning, stale_check) = stale_check_tokens(macro_data);
+        let write_back = write_back_tokens(macro_data);
+        let where_clause = format!(
+            "{}{version_condition}{returning}",
+            pk_conditions.join(" AND ")
+        );
 
         quote! {
             /// Updates a database record that matches
             /// the current instance of a T type, returning a result
             /// indicating a possible failure querying the database.
-            async fn update(&self) -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>> {
+            ///
+            /// The column of the field annotated with `#[updated_at]` (if any)
+            /// is set to the current time, that is also assigned to the field.
+            ///
+            /// If the entity has a field annotated with `#[version]`, the row is only
+            /// updated if its version still matches the one of the instance, and
+            /// the version is incremented, both in the row and in the instance. Otherwise,
+            /// a [`canyon_sql::crud::StaleEntityError`] is returned.
+            async fn update(&mut self) -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>> {
+                canyon_sql::crud::EntityHooks::before_update(self).await?;
+                canyon_sql::crud::Validate::validate(self)?;
+                #updated_at_now
                 let stmt = format!(
-                    "UPDATE {} SET {} WHERE {} = ${:?}",
-                    #table_schema_data, #str_columns_values, #primary_key, #pk_index + 1
+                    "UPDATE {} SET {} WHERE {}",
+                    #table_schema_data, #set_clause, #where_clause
                 );
-                let update_values: &[&dyn canyon_sql::crud::bounds::QueryParameter<'_>] = &[#(#update_values),*];
+                #update_values
 
-                <#ty as canyon_sql::crud::Transaction<#ty>>::query(
+                let #rows = <#ty as canyon_sql::crud::Transaction<#ty>>::query_returning(
                     stmt, update_values, ""
                 ).await?;
+                #stale_check
+                #write_back
 
-                Ok(())
+                canyon_sql::crud::EntityHooks::after_update(self).await
             }
 
 
@@ -53,20 +160,33 @@ pub fn generate_update_tokens(macro_data: &MacroTokens, table_schema_data: &Stri
             /// the current instance of a T type, returning a result
             /// indicating a possible failure querying the database with the
             /// specified datasource
-            async fn update_datasource<'a>(&self, datasource_name: &'a str)
+            ///
+            /// The column of the field annotated with `#[updated_at]` (if any)
+            /// is set to the current time, that is also assigned to the field.
+            ///
+            /// If the entity has a field annotated with `#[version]`, the row is only
+            /// updated if its version still matches the one of the instance, and
+            /// the version is incremented, both in the row and in the instance. Otherwise,
+            /// a [`canyon_sql::crud::StaleEntityError`] is returned.
+            async fn update_datasource<'a>(&mut self, datasource_name: &'a str)
                 -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>>
             {
+                canyon_sql::crud::EntityHooks::before_update(self).await?;
+                canyon_sql::crud::Validate::validate(self)?;
+                #updated_at_now
                 let stmt = format!(
-                    "UPDATE {} SET {} WHERE {} = ${:?}",
-                    #table_schema_data, #str_columns_values, #primary_key, #pk_index + 1
+                    "UPDATE {} SET {} WHERE {}",
+                    #table_schema_data, #set_clause, #where_clause
                 );
-                let update_values: &[&dyn canyon_sql::crud::bounds::QueryParameter<'_>] = &[#(#update_values_cloned),*];
+                #update_values_cloned
 
-                <#ty as canyon_sql::crud::Transaction<#ty>>::query(
+                let #rows = <#ty as canyon_sql::crud::Transaction<#ty>>::query_returning(
                     stmt, update_values, datasource_name
                 ).await?;
+                #stale_check
+                #write_back
 
-                Ok(())
+                canyon_sql::crud::EntityHooks::after_update(self).await
             }
         }
     } else {
@@ -75,7 +195,7 @@ pub fn generate_update_tokens(macro_data: &MacroTokens, table_schema_data: &Stri
 
         // TODO Returning an error should be a provisional way of doing this
         quote! {
-            async fn update(&self)
+            async fn update(&mut self)
                 -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>>
             {
                 Err(
@@ -88,7 +208,7 @@ pub fn generate_update_tokens(macro_data: &MacroTokens, table_schema_data: &Stri
                 )
             }
 
-            async fn update_datasource<'a>(&self, datasource_name: &'a str)
+            async fn update_datasource<'a>(&mut self, datasource_name: &'a str)
                 -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>>
             {
                 Err(
@@ -104,6 +224,404 @@ pub fn generate_update_tokens(macro_data: &MacroTokens, table_schema_data: &Stri
     }
 }
 
+/// The tokens that checks that the update of an entity with a `#[version]` column
+/// affected exactly one row, or the instance is stale: the binding of the returned
+/// rows, the `RETURNING` clause of the statement and the check itself
+fn stale_check_tokens(macro_data: &MacroTokens) -> (TokenStream, String, TokenStream) {
+    let ty = macro_data.ty;
+    if macro_data.get_version_annotation().is_none() {
+        return (quote! { _ }, String::new(), quote! {});
+    }
+
+    let pk_fields = macro_data
+        .get_primary_key_fields()
+        .into_iter()
+        .map(|field| field.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let pk_debug = if let [pk_ident] = pk_fields.as_slice() {
+        quote! { self.#pk_ident }
+    } else {
+        quote! { (#(&self.#pk_fields),*) }
+    };
+
+    (
+        quote! { rows },
+        format!(" RETURNING {}", macro_data.primary_key_columns_list()),
+        quote! {
+            if rows.is_empty() {
+                return Err(Box::new(canyon_sql::crud::StaleEntityError::new(
+                    stringify!(#ty), format!("{:?}", #pk_debug)
+                )));
+            }
+        },
+    )
+}
+
+/// The statements that writes back into the instance the values set by a successful
+/// update, that increments the `#[version]` field (if any), and assigns the current
+/// time sent to the database to the `#[updated_at]` field (if any)
+fn write_back_tokens(macro_data: &MacroTokens) -> TokenStream {
+    let version_ident = macro_data
+        .get_annotated_field("version")
+        .and_then(|field| field.ident.as_ref());
+    let version = version_ident.map(|ident| quote! { self.#ident += 1; });
+    let updated_at_ident = macro_data
+        .get_annotated_field("updated_at")
+        .and_then(|field| field.ident.as_ref());
+    let updated_at = updated_at_ident.map(|ident| quote! { self.#ident = updated_at_now; });
+
+    quote! {
+        #version
+        #updated_at
+    }
+}
+
+/// The statement that sets the current time to the `#[updated_at]` field (if any),
+/// to be used as a query parameter
+fn updated_at_now_tokens(macro_data: &MacroTokens) -> TokenStream {
+    match macro_data.get_annotated_field("updated_at") {
+        Some(field) => {
+            let timestamp =
+                timestamp_for_field(&quote! { canyon_sql::date_time::Utc::now() }, field);
+            quote! { let updated_at_now = #timestamp; }
+        }
+        None => quote! {},
+    }
+}
+
+/// Generates the TokenStream for the __patch() CRUD operation, that updates just the
+/// given columns of the row with the given primary key
+pub fn generate_patch_tokens(macro_data: &MacroTokens, table_schema_data: &String) -> TokenStream {
+    let ty = macro_data.ty;
+    let primary_keys = macro_data.get_primary_keys();
+
+    // Disabled if there's no `primary_key` annotation
+    if primary_keys.is_empty() {
+        return quote! {
+            async fn patch<'a, Z>(
+                pk: &'a dyn canyon_sql::crud::bounds::PrimaryKeyValues<'a>,
+                changes: Vec<Z>
+            ) -> Result<bool, Box<dyn std::error::Error + Sync + std::marker::Send>>
+                where Z: canyon_sql::crud::bounds::FieldValueIdentifier<'a, #ty> + std::marker::Send + 'a
+            {
+                Err(
+                    std::io::Error::new(
+                        std::io::ErrorKind::Unsupported,
+                        "You can't use the 'patch' associated function on a \
+                        CanyonEntity that does not have a #[primary_key] annotation. \
+                        If you need to perform an specific update, use the Querybuilder instead."
+                    ).into_inner().unwrap()
+                )
+            }
+
+            async fn patch_datasource<'a, Z>(
+                pk: &'a dyn canyon_sql::crud::bounds::PrimaryKeyValues<'a>,
+                changes: Vec<Z>,
+                datasource_name: &'a str
+            ) -> Result<bool, Box<dyn std::error::Error + Sync + std::marker::Send>>
+                where Z: canyon_sql::crud::bounds::FieldValueIdentifier<'a, #ty> + std::marker::Send + 'a
+            {
+                Err(
+                    std::io::Error::new(
+                        std::io::ErrorKind::Unsupported,
+                        "You can't use the 'patch_datasource' associated function on a \
+                        CanyonEntity that does not have a #[primary_key] annotation. \
+                        If you need to perform an specific update, use the Querybuilder instead."
+                    ).into_inner().unwrap()
+                )
+            }
+        };
+    }
+
+    let pk_values = generate_pk_values_tokens(macro_data, &quote! { pk });
+    let updated_at_now = updated_at_now_tokens(macro_data);
+
+    // The `#[updated_at]` and `#[version]` columns are updated like in `update()`,
+    // unless they're explicitly changed
+    let updated_at_set = match macro_data.get_annotated_field("updated_at") {
+        Some(field) => {
+            let column = column_name(field);
+            quote! {
+                if !changes.iter().any(|(column, _)| *column == #column) {
+                    values.push(&updated_at_now);
+                    columns.push(format!("\"{}\" = ${}", #column, values.len()));
+                }
+            }
+        }
+        None => quote! {},
+    };
+    let version_set = match macro_data.get_version_annotation() {
+        Some(column) => {
+            let increment = format!("\"{column}\" = \"{column}\" + 1");
+            quote! {
+                if !changes.iter().any(|(column, _)| *column == #column) {
+                    columns.push(String::from(#increment));
+                }
+            }
+        }
+        None => quote! {},
+    };
+    let returning = format!(" RETURNING {}", macro_data.primary_key_columns_list());
+
+    // The changes are already erased as trait objects, so they're passed by reference
+    // along with the rest of the parameters
+    let patch_body = quote! {
+        #pk_values
+        if changes.is_empty() {
+            return Ok(false);
+        }
+        #updated_at_now
+
+        // The renamed columns are already quote escaped by the `FieldValue` enums
+        let changes = changes
+            .into_iter()
+            .map(|change| {
+                let (column, value) = change.value();
+                (column.trim_matches('"'), value)
+            })
+            .collect::<Vec<_>>();
+        let mut columns: Vec<String> = Vec::new();
+        let mut values: Vec<&dyn canyon_sql::crud::bounds::QueryParameter<'_>> = Vec::new();
+        for (column, value) in changes.iter() {
+            values.push(value);
+            columns.push(format!("\"{}\" = ${}", column, values.len()));
+        }
+        #updated_at_set
+        #version_set
+
+        let mut conditions: Vec<String> = Vec::new();
+        for (column, value) in [#(#primary_keys),*].iter().zip(pk_values.iter()) {
+            values.push(value);
+            conditions.push(format!("\"{}\" = ${}", column, values.len()));
+        }
+
+        let stmt = format!(
+            "UPDATE {} SET {} WHERE {}{}",
+            #table_schema_data, columns.join(", "), conditions.join(" AND "), #returning
+        );
+        let rows = <#ty as canyon_sql::crud::Transaction<#ty>>::query_returning(
+            stmt, values, datasource_name
+        ).await?;
+
+        Ok(!rows.is_empty())
+    };
+
+    quote! {
+        /// Updates just the columns of the given fields of the row with the given
+        /// primary key, without loading the entity. The changes are the variants of
+        /// the enum generated by `#[derive(Fields)]` with the values of the fields:
+        ///
+        /// ```ignore
+        /// League::patch(&1, vec![LeagueFieldValue::name(&name)]).await?;
+        /// ```
+        ///
+        /// The column of the field annotated with `#[updated_at]` (if any) is set to
+        /// the current time, and the one of the `#[version]` field (if any) is
+        /// incremented, unless they're part of the changes. The lifecycle hooks and
+        /// the validations aren't run, since there's no instance.
+        ///
+        /// Returns true if a row was updated, and false if there's no row with the
+        /// primary key or if there's no change, in which case nothing is sent to
+        /// the database.
+        async fn patch<'a, Z>(
+            pk: &'a dyn canyon_sql::crud::bounds::PrimaryKeyValues<'a>,
+            changes: Vec<Z>
+        ) -> Result<bool, Box<dyn std::error::Error + Sync + std::marker::Send>>
+            where Z: canyon_sql::crud::bounds::FieldValueIdentifier<'a, #ty> + std::marker::Send + 'a
+        {
+            let datasource_name = "";
+            #patch_body
+        }
+
+        /// Updates just the columns of the given fields of the row with the given
+        /// primary key, without loading the entity, with the specified datasource.
+        ///
+        /// The column of the field annotated with `#[updated_at]` (if any) is set to
+        /// the current time, and the one of the `#[version]` field (if any) is
+        /// incremented, unless they're part of the changes. The lifecycle hooks and
+        /// the validations aren't run, since there's no instance.
+        ///
+        /// Returns true if a row was updated, and false if there's no row with the
+        /// primary key or if there's no change, in which case nothing is sent to
+        /// the database.
+        async fn patch_datasource<'a, Z>(
+            pk: &'a dyn canyon_sql::crud::bounds::PrimaryKeyValues<'a>,
+            changes: Vec<Z>,
+            datasource_name: &'a str
+        ) -> Result<bool, Box<dyn std::error::Error + Sync + std::marker::Send>>
+            where Z: canyon_sql::crud::bounds::FieldValueIdentifier<'a, #ty> + std::marker::Send + 'a
+        {
+            #patch_body
+        }
+    }
+}
+
+/// Generates the implementation of the `Trackable` trait for the entities annotated
+/// with `#[tracked]`, whose updates only write the columns of the modified fields
+pub fn generate_trackable_tokens(
+    macro_data: &MacroTokens,
+    table_schema_data: &String,
+) -> TokenStream {
+    let ty = macro_data.ty;
+    let primary_keys = macro_data.get_primary_keys();
+    if primary_keys.is_empty() {
+        return syn::Error::new_spanned(
+            ty,
+            "Only the entities with a #[primary_key] annotation can be annotated with #[tracked]",
+        )
+        .to_compile_error();
+    }
+
+    // The primary key, version and update timestamp are never set from the values of
+    // the instance, so they aren't tracked, like the `#[transient]` fields
+    let version_column = macro_data.get_version_annotation();
+    let updated_at_field = macro_data.get_annotated_field("updated_at");
+    let updated_at_column = updated_at_field.map(column_name);
+    let tracked_fields = macro_data
+        .get_persisted_fields()
+        .into_iter()
+        .filter(|field| {
+            let column_name = column_name(field);
+            !primary_keys.contains(&column_name)
+                && Some(&column_name) != version_column.as_ref()
+                && Some(&column_name) != updated_at_column.as_ref()
+        })
+        .collect::<Vec<_>>();
+
+    let comparisons = tracked_fields.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        let name = ident.to_string();
+        quote! {
+            if self.#ident != original.#ident {
+                changed.push(#name);
+            }
+        }
+    });
+    let assignments = tracked_fields.iter().map(|field| {
+        let name = field.ident.as_ref().unwrap().to_string();
+        let column = column_name(field);
+        let value = query_parameter_for_field(&quote! { self }, field);
+        quote! {
+            if fields.contains(&#name) {
+                values.push(#value);
+                columns.push(format!("\"{}\" = ${}", #column, values.len()));
+            }
+        }
+    });
+
+    // The `#[embedded]` fields are tracked as a whole, setting every one of their columns
+    let embedded_fields = macro_data.get_embedded_fields();
+    let embedded_comparisons = embedded_fields.iter().map(|(field, _)| {
+        let ident = field.ident.as_ref().unwrap();
+        let name = ident.to_string();
+        quote! {
+            if self.#ident != original.#ident {
+                changed.push(#name);
+            }
+        }
+    });
+    let embedded_assignments = embedded_fields.iter().map(|(field, prefix)| {
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let name = ident.to_string();
+        quote! {
+            if fields.contains(&#name) {
+                let embedded_columns =
+                    <#ty as canyon_sql::crud::embedded::CanyonEmbeddable>::prefixed_columns(#prefix);
+                let embedded_values =
+                    canyon_sql::crud::embedded::CanyonEmbeddable::parameters(&self.#ident);
+                for (column, value) in embedded_columns.iter().zip(embedded_values) {
+                    values.push(value);
+                    columns.push(format!("\"{}\" = ${}", column, values.len()));
+                }
+            }
+        }
+    });
+
+    let updated_at_now = updated_at_now_tokens(macro_data);
+    let updated_at_set = match updated_at_column {
+        Some(column) => {
+            quote! {
+                values.push(&updated_at_now);
+                columns.push(format!("\"{}\" = ${}", #column, values.len()));
+            }
+        }
+        None => quote! {},
+    };
+
+    let pk_conditions = macro_data
+        .get_primary_key_fields()
+        .into_iter()
+        .map(|field| {
+            let ident = field.ident.as_ref().unwrap();
+            let column = column_name(field);
+            quote! {
+                values.push(&self.#ident);
+                conditions.push(format!("\"{}\" = ${}", #column, values.len()));
+            }
+        });
+    let version_tokens = match version_column {
+        Some(column) => {
+            let ident = macro_data
+                .get_annotated_field("version")
+                .and_then(|field| field.ident.as_ref());
+            let increment = format!("\"{column}\" = \"{column}\" + 1");
+            quote! {
+                columns.push(String::from(#increment));
+                values.push(&self.#ident);
+                conditions.push(format!("\"{}\" = ${}", #column, values.len()));
+            }
+        }
+        None => quote! {},
+    };
+    let (rows, returning, stale_check) = stale_check_tokens(macro_data);
+    let write_back = write_back_tokens(macro_data);
+
+    quote! {
+        #[canyon_sql::macros::async_trait]
+        impl canyon_sql::crud::Trackable for #ty {
+            fn changed_fields(&self, original: &Self) -> Vec<&'static str> {
+                let mut changed = Vec::new();
+                #(#comparisons)*
+                #(#embedded_comparisons)*
+                changed
+            }
+
+            async fn update_fields<'a>(&mut self, fields: &'a [&'static str], datasource_name: &'a str)
+                -> Result<(), Box<dyn std::error::Error + Sync + std::marker::Send>>
+            {
+                if fields.is_empty() {
+                    return Ok(());
+                }
+                canyon_sql::crud::EntityHooks::before_update(self).await?;
+                canyon_sql::crud::Validate::validate(self)?;
+                #updated_at_now
+
+                let mut columns: Vec<String> = Vec::new();
+                let mut conditions: Vec<String> = Vec::new();
+                let mut values: Vec<&dyn canyon_sql::crud::bounds::QueryParameter<'_>> = Vec::new();
+                #(#assignments)*
+                #(#embedded_assignments)*
+                #updated_at_set
+                #(#pk_conditions)*
+                #version_tokens
+
+                let stmt = format!(
+                    "UPDATE {} SET {} WHERE {}{}",
+                    #table_schema_data, columns.join(", "), conditions.join(" AND "), #returning
+                );
+                let #rows = <#ty as canyon_sql::crud::Transaction<#ty>>::query_returning(
+                    stmt, values, datasource_name
+                ).await?;
+                #stale_check
+                #write_back
+
+                canyon_sql::crud::EntityHooks::after_update(self).await
+            }
+        }
+    }
+}
+
 /// Generates the TokenStream for the __update() CRUD operation
 /// being the query generated with the [`QueryBuilder`]
 pub fn generate_update_query_tokens(
@@ -112,6 +630,16 @@ pub fn generate_update_query_tokens(
 ) -> TokenStream {
     let ty = macro_data.ty;
 
+    // The `SET` clause of the builder will also update the `#[updated_at]` column (if any)
+    let updated_at = match macro_data.get_annotated_field("updated_at") {
+        Some(field) => {
+            let column = column_identifier(field);
+            let with_time_zone = has_time_zone(field);
+            quote! { .with_updated_at(#column, #with_time_zone) }
+        }
+        None => quote! {},
+    };
+
     quote! {
         /// Generates a [`canyon_sql::query::UpdateQueryBuilder`]
         /// that allows you to customize the query by adding parameters and constrains dynamically.
@@ -121,7 +649,7 @@ pub fn generate_update_query_tokens(
         /// unless concrete values are set on the available parameters of the
         /// `canyon_macro(table_name = "table_name", schema = "schema")`
         fn update_query<'a>() -> canyon_sql::query::UpdateQueryBuilder<'a, #ty> {
-            canyon_sql::query::UpdateQueryBuilder::new(#table_schema_data, "")
+            canyon_sql::query::UpdateQueryBuilder::new(#table_schema_data, "")#updated_at
         }
 
         /// Generates a [`canyon_sql::query::UpdateQueryBuilder`]
@@ -136,7 +664,7 @@ pub fn generate_update_query_tokens(
         /// described in the configuration file, and selected with the [`&str`]
         /// passed as parameter.
         fn update_query_datasource<'a>(datasource_name: &'a str) -> canyon_sql::query::UpdateQueryBuilder<'a, #ty> {
-            canyon_sql::query::UpdateQueryBuilder::new(#table_schema_data, datasource_name)
+            canyon_sql::query::UpdateQueryBuilder::new(#table_schema_data, datasource_name)#updated_at
         }
     }
 }
@@ -0,0 +1,80 @@
+use canyon_entities::field_annotation::ValidationRule;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::utils::macro_tokens::MacroTokens;
+
+/// Generates the TokenStream for the implementation of the `Validate` trait, that
+/// checks the rules declared with the `#[validate(...)]` field annotations
+pub fn generate_validate_tokens(macro_data: &MacroTokens) -> TokenStream {
+    let ty = macro_data.ty;
+
+    let checks = macro_data
+        .get_validation_rules()
+        .into_iter()
+        .map(|(field, rule)| {
+            let field_name = field.to_string();
+            let check = match rule {
+                ValidationRule::Length(min, max) => {
+                    let (min, max) = (option_tokens(min), option_tokens(max));
+                    quote! {
+                        canyon_sql::crud::validation::validate_length(#field_name, &self.#field, #min, #max)
+                    }
+                }
+                ValidationRule::Range(min, max) => {
+                    let (min, max) = (option_tokens(min), option_tokens(max));
+                    quote! {
+                        canyon_sql::crud::validation::validate_range(#field_name, &self.#field, #min, #max)
+                    }
+                }
+                ValidationRule::Regex(pattern) => quote! {
+                    {
+                        // The pattern is compiled once, the first time that it's used
+                        static PATTERN: std::sync::OnceLock<canyon_sql::crud::validation::Regex> =
+                            std::sync::OnceLock::new();
+                        let pattern = PATTERN.get_or_init(|| {
+                            canyon_sql::crud::validation::Regex::new(#pattern)
+                                .expect("The pattern was already validated at compile time")
+                        });
+                        canyon_sql::crud::validation::validate_regex(#field_name, &self.#field, pattern)
+                    }
+                },
+                ValidationRule::Custom(path) => {
+                    let path = syn::parse_str::<syn::Path>(&path)
+                        .expect("The path was already validated by the entity parser");
+                    quote! {
+                        #path(&self.#field).map_err(|message| {
+                            canyon_sql::crud::FieldError::new(#field_name, "custom", message)
+                        })
+                    }
+                }
+            };
+
+            quote! {
+                if let Err(error) = #check {
+                    errors.push(error);
+                }
+            }
+        });
+
+    quote! {
+        impl canyon_sql::crud::Validate for #ty {
+            /// Checks the rules declared with the `#[validate(...)]` annotations,
+            /// returning every rule that isn't satisfied by the current values
+            fn validate(&self) -> Result<(), canyon_sql::crud::ValidationErrors> {
+                #[allow(unused_mut)]
+                let mut errors = canyon_sql::crud::ValidationErrors::new();
+                #(#checks)*
+                errors.into_result()
+            }
+        }
+    }
+}
+
+/// Generates the tokens of an [`Option`] literal with the value of a validation bound
+fn option_tokens<T: quote::ToTokens>(value: Option<T>) -> TokenStream {
+    match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
@@ -1,5 +1,10 @@
 use proc_macro2::{Ident, Span, TokenStream};
-use syn::{punctuated::Punctuated, MetaNameValue, Token};
+use quote::{quote, ToTokens};
+use syn::{punctuated::Punctuated, Field, MetaNameValue, Token, Type};
+
+use std::convert::TryFrom;
+
+use canyon_entities::field_annotation::EntityFieldAnnotation;
 
 use super::macro_tokens::MacroTokens;
 
@@ -77,6 +82,180 @@ pub fn table_schema_parser(macro_data: &MacroTokens<'_>) -> Result<String, Token
     Ok(macro_data.ty.to_string())
 }
 
+/// Builds the source of a `SELECT` statement as a derived table that only contains
+/// the rows of `table_schema_data` that matches the given `condition`.
+///
+/// The derived table is aliased with the name of the table, so the columns can
+/// still be qualified with it on the joins added by the [`QueryBuilder`]
+pub fn filtered_table_source(table_schema_data: &str, condition: &str) -> String {
+    let alias = table_schema_data
+        .rsplit('.')
+        .next()
+        .unwrap_or(table_schema_data);
+    format!("(SELECT * FROM {table_schema_data} WHERE {condition}) AS {alias}")
+}
+
+/// Generates the tokens that converts `now`, an expression that evaluates to the current
+/// [`DateTime<Utc>`], into the type of a field annotated with `#[created_at]` or
+/// `#[updated_at]`.
+///
+/// Only `NaiveDateTime` and `DateTime<Utc>` (or their optional variants) are allowed,
+/// so a compile error is generated for any other type
+pub fn timestamp_for_field(now: &TokenStream, field: &Field) -> TokenStream {
+    let field_type = field.ty.to_token_stream().to_string().replace(' ', "");
+    let (inner_type, is_optional) = match field_type
+        .strip_prefix("Option<")
+        .and_then(|t| t.strip_suffix('>'))
+    {
+        Some(inner_type) => (inner_type, true),
+        None => (field_type.as_str(), false),
+    };
+
+    let unqualified_type = inner_type
+        .replace("canyon_sql::date_time::", "")
+        .replace("chrono::", "");
+
+    let timestamp = match unqualified_type.as_str() {
+        "NaiveDateTime" => quote! { #now.naive_utc() },
+        "DateTime<Utc>" => quote! { #now },
+        _ => {
+            return syn::Error::new_spanned(
+                &field.ty,
+                "Only `NaiveDateTime` and `DateTime<Utc>` fields can be annotated \
+                with `#[created_at]` or `#[updated_at]`",
+            )
+            .to_compile_error()
+        }
+    };
+
+    if is_optional {
+        quote! { Some(#timestamp) }
+    } else {
+        timestamp
+    }
+}
+
+/// Returns the name of the column mapped by the field, declared with
+/// `#[column(name = "...")]`, or the name of the field otherwise
+pub fn column_name(field: &Field) -> String {
+    field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("column"))
+        .find_map(|attr| match EntityFieldAnnotation::try_from(&attr) {
+            Ok(EntityFieldAnnotation::Column(options)) => options.name,
+            _ => None,
+        })
+        .unwrap_or_else(|| field.ident.as_ref().unwrap().to_string())
+}
+
+/// Returns the column of the field as it's written on the statements whose columns
+/// aren't quote escaped, like the ones of the query builders. The names declared with
+/// `#[column(name = "...")]` are quote escaped, so they're matched verbatim
+pub fn column_identifier(field: &Field) -> String {
+    let column = column_name(field);
+    if *field.ident.as_ref().unwrap() == column {
+        column
+    } else {
+        format!("\"{column}\"")
+    }
+}
+
+/// Returns true if the timestamp of the field stores the offset along with it
+/// (`DateTime<Utc>`), instead of being a `NaiveDateTime` assumed to be in UTC
+pub fn has_time_zone(field: &Field) -> bool {
+    !field
+        .ty
+        .to_token_stream()
+        .to_string()
+        .contains("NaiveDateTime")
+}
+
+/// Returns true if the field is annotated with `#[transient]` (or `#[canyon(skip)]`),
+/// so it isn't persisted, and it's filled with its `Default` value when loaded
+pub fn is_transient(field: &Field) -> bool {
+    field.attrs.iter().any(|attr| {
+        (attr.path.is_ident("transient") || attr.path.is_ident("canyon"))
+            && matches!(
+                EntityFieldAnnotation::try_from(&attr),
+                Ok(EntityFieldAnnotation::Transient)
+            )
+    })
+}
+
+/// Returns the prefix of the columns of the field if it's annotated with `#[embedded]`,
+/// which is the name of the field followed by an underscore if it isn't declared
+/// with `#[embedded(prefix = "...")]`
+pub fn embedded_prefix(field: &Field) -> Option<String> {
+    field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("embedded"))
+        .find_map(|attr| match EntityFieldAnnotation::try_from(&attr) {
+            Ok(EntityFieldAnnotation::Embedded(prefix)) => {
+                Some(prefix.unwrap_or_else(|| format!("{}_", field.ident.as_ref().unwrap())))
+            }
+            _ => None,
+        })
+}
+
+/// Returns true if the field is annotated with `#[json]`, so its value is stored
+/// as a JSON document
+pub fn is_json_field(field: &Field) -> bool {
+    field.attrs.iter().any(|attr| attr.path.is_ident("json"))
+}
+
+/// Returns true if the type is a `Vec<T>` or an `Option<Vec<T>>` mapped to a PostgreSQL
+/// array column. The `Vec<u8>` ones are mapped to the binary columns instead
+pub fn is_array_type(ty: &Type) -> bool {
+    let ty = ty.to_token_stream().to_string().replace(' ', "");
+    let ty = ty
+        .strip_prefix("Option<")
+        .and_then(|inner| inner.strip_suffix('>'))
+        .unwrap_or(&ty);
+
+    ty.starts_with("Vec<") && ty != "Vec<u8>"
+}
+
+/// Generates the reference to the value of the `field` of the `instance` that's
+/// sent as a query parameter. The `#[json]` fields are wrapped, so they're
+/// serialized as JSON documents
+pub fn query_parameter_for_field(instance: &TokenStream, field: &Field) -> TokenStream {
+    let ident = field.ident.as_ref().unwrap();
+    if is_json_field(field) {
+        quote! { canyon_sql::json::Json::from_ref(&#instance.#ident) }
+    } else {
+        quote! { &#instance.#ident }
+    }
+}
+
+/// Generates the expression with the prefixed columns of the given `#[embedded]` fields,
+/// along with their prefixes, as a `Vec<String>`
+pub fn embedded_columns(embedded_fields: &[(&Field, String)]) -> TokenStream {
+    let types = embedded_fields.iter().map(|(field, _)| &field.ty);
+    let prefixes = embedded_fields.iter().map(|(_, prefix)| prefix);
+    quote! {
+        [#(<#types as canyon_sql::crud::embedded::CanyonEmbeddable>::prefixed_columns(#prefixes)),*]
+            .concat()
+    }
+}
+
+/// Generates the statements that extends the `values` sent as query parameters with
+/// the values of the given `#[embedded]` fields of the `instance`, in the order of
+/// their columns
+pub fn extend_with_embedded_parameters(
+    values: &TokenStream,
+    instance: &TokenStream,
+    embedded_fields: &[(&Field, String)],
+) -> TokenStream {
+    let idents = embedded_fields
+        .iter()
+        .map(|(field, _)| field.ident.as_ref().unwrap());
+    quote! {
+        #(#values.extend(canyon_sql::crud::embedded::CanyonEmbeddable::parameters(&#instance.#idents));)*
+    }
+}
+
 /// Parses a syn::Identifier to get a snake case database name from the type identifier
 pub fn _database_table_name_from_struct(ty: &Ident) -> String {
     let struct_name: String = ty.to_string();
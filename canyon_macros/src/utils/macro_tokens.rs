@@ -1,8 +1,12 @@
 use std::convert::TryFrom;
 
-use canyon_entities::field_annotation::EntityFieldAnnotation;
+use canyon_entities::field_annotation::{
+    EntityFieldAnnotation, PrimaryKeyGeneration, ValidationRule,
+};
 use proc_macro2::Ident;
-use syn::{Attribute, DeriveInput, Fields, Generics, Type, Visibility};
+use syn::{Attribute, DeriveInput, Field, Fields, Generics, Type, Visibility};
+
+use super::helpers;
 
 /// Provides a convenient way of store the data for the TokenStream
 /// received on a macro
@@ -52,99 +56,138 @@ impl<'a> MacroTokens<'a> {
             .collect::<Vec<_>>()
     }
 
-    /// Gives a Vec of Ident with the fields of a Struct
-    pub fn get_struct_fields(&self) -> Vec<Ident> {
+    /// Returns the fields mapped to a column of the table, so the ones that
+    /// aren't `#[transient]` nor `#[embedded]`
+    pub fn get_persisted_fields(&self) -> Vec<&Field> {
         self.fields
             .iter()
-            .map(|field| field.ident.as_ref().unwrap().clone())
-            .collect::<Vec<_>>()
+            .filter(|field| !helpers::is_transient(field))
+            .filter(|field| helpers::embedded_prefix(field).is_none())
+            .collect()
     }
 
-    /// Gives a Vec populated with the name of the fields of the struct
+    /// Returns the fields annotated with `#[embedded]`, along with the prefix of the
+    /// columns where the fields of their value objects are stored
+    pub fn get_embedded_fields(&self) -> Vec<(&Field, String)> {
+        self.fields
+            .iter()
+            .filter_map(|field| helpers::embedded_prefix(field).map(|prefix| (field, prefix)))
+            .collect()
+    }
+
+    /// Gives a Vec populated with the name of the persisted fields of the struct
     pub fn _get_struct_fields_as_collection_strings(&self) -> Vec<String> {
-        self.get_struct_fields()
+        self.get_persisted_fields()
             .iter()
-            .map(|ident| ident.to_owned().to_string())
+            .map(|field| field.ident.as_ref().unwrap().to_string())
             .collect::<Vec<String>>()
     }
 
     /// Returns a Vec populated with the name of the fields of the struct
     /// already quote scaped for avoid the upper case column name mangling.
     ///
-    /// If the type contains a `#[primary_key]` annotation (and), returns the
-    /// name of the columns without the fields that maps against the column designed as
-    /// primary key (if its present and its autoincremental attribute is set to true)
-    /// (autoincremental = true) or its without the autoincremental attribute, which leads
-    /// to the same behaviour.
+    /// If the type contains a single `#[primary_key]` annotation whose value is generated
+    /// by the database (autoincremental, or `generated = "db"`), returns the name of the
+    /// columns without the field that maps against the column designed as primary key.
     ///
-    /// Returns every field if there's no PK, or if it's present but autoincremental = false
+    /// Returns every field if there's no PK, if the PK is a composite one, or if the
+    /// value of the PK is provided by the entity or created by Canyon
     pub fn get_column_names_pk_parsed(&self) -> Vec<String> {
-        self.fields
+        self.get_insert_fields()
             .iter()
-            .filter(|field| {
-                if !field.attrs.is_empty() {
-                    field.attrs.iter().any(|attr| {
-                        let a = attr.path.segments[0].clone().ident;
-                        let b = attr.tokens.to_string();
-                        !(a == "primary_key" || b.contains("false"))
-                    })
-                } else {
-                    true
+            .map(|field| format!("\"{}\"", helpers::column_name(field)))
+            .collect::<Vec<String>>()
+    }
+
+    /// Returns the fields whose values are sent on the `INSERT` statements.
+    ///
+    /// See [`MacroTokens::get_column_names_pk_parsed`]
+    pub fn get_insert_fields(&self) -> Vec<&Field> {
+        let primary_keys = self.get_primary_key_fields();
+        let db_generated_pk = primary_keys.len() == 1
+            && matches!(
+                Self::primary_key_annotation(primary_keys[0]),
+                Some(EntityFieldAnnotation::PrimaryKey(true, _))
+            );
+        self.get_persisted_fields()
+            .into_iter()
+            .filter(|field| !db_generated_pk || !Self::is_primary_key(field))
+            .collect()
+    }
+
+    /// Returns the primary keys whose values are created by Canyon before the insert,
+    /// declared with `#[primary_key(generated = "uuid_v4" | "uuid_v7")]`
+    pub fn get_client_generated_keys(&self) -> Vec<(&Field, PrimaryKeyGeneration)> {
+        self.get_primary_key_fields()
+            .into_iter()
+            .filter_map(|field| match Self::primary_key_annotation(field) {
+                Some(EntityFieldAnnotation::PrimaryKey(_, Some(generated)))
+                    if generated.is_client_side() =>
+                {
+                    Some((field, generated))
                 }
+                _ => None,
             })
-            .map(|c| format!("\"{}\"", c.ident.as_ref().unwrap()))
-            .collect::<Vec<String>>()
+            .collect()
     }
 
-    /// Retrieves the fields of the Struct as continuous String, comma separated
-    pub fn get_struct_fields_as_strings(&self) -> String {
-        let column_names: String = self
-            .get_struct_fields()
-            .iter()
-            .map(|ident| ident.to_owned().to_string())
-            .collect::<Vec<String>>()
+    /// Returns the fields annotated with `#[primary_key]` in declaration order.
+    /// There's more than one when the entity has a composite primary key
+    pub fn get_primary_key_fields(&self) -> Vec<&Field> {
+        self.fields
             .iter()
-            .map(|column| column.to_owned() + ", ")
-            .collect::<String>();
+            .filter(|field| Self::is_primary_key(field))
+            .collect()
+    }
 
-        let mut column_names_as_chars = column_names.chars();
-        column_names_as_chars.next_back();
-        column_names_as_chars.next_back();
+    /// Utility for find the column names of every field annotated with `#[primary_key]`
+    pub fn get_primary_keys(&self) -> Vec<String> {
+        self.get_primary_key_fields()
+            .iter()
+            .map(|field| helpers::column_name(field))
+            .collect()
+    }
 
-        column_names_as_chars.as_str().to_owned()
+    /// Returns the comma separated list of the primary key columns, already quote
+    /// scaped, as it's written on the `RETURNING` clauses
+    pub fn primary_key_columns_list(&self) -> String {
+        self.get_primary_keys()
+            .iter()
+            .map(|column| format!("\"{column}\""))
+            .collect::<Vec<String>>()
+            .join(", ")
     }
 
-    ///
-    pub fn get_pk_index(&self) -> Option<usize> {
-        let mut pk_index = None;
-        for (idx, field) in self.fields.iter().enumerate() {
-            for attr in &field.attrs {
-                if attr.path.segments[0].clone().ident == "primary_key" {
-                    pk_index = Some(idx);
-                }
-            }
-        }
-        pk_index
+    /// Returns the SQL condition that matches every primary key column against
+    /// consecutive placeholders, starting at `$first_placeholder`
+    pub fn primary_key_condition(&self, first_placeholder: usize) -> String {
+        self.get_primary_keys()
+            .iter()
+            .enumerate()
+            .map(|(idx, column)| format!("\"{column}\" = ${}", first_placeholder + idx))
+            .collect::<Vec<String>>()
+            .join(" AND ")
     }
 
-    /// Utility for find the primary key attribute (if exists) and the
-    /// column name (field) which belongs
-    pub fn get_primary_key_annotation(&self) -> Option<String> {
-        let f = self.fields.iter().find(|field| {
-            field
-                .attrs
-                .iter()
-                .map(|attr| attr.path.segments[0].clone().ident)
-                .map(|ident| ident.to_string())
-                .find(|a| a == "primary_key")
-                == Some("primary_key".to_string())
-        });
+    /// Parses the `#[primary_key]` annotation of the field (if exists)
+    fn primary_key_annotation(field: &Field) -> Option<EntityFieldAnnotation> {
+        field
+            .attrs
+            .iter()
+            .find(|attr| attr.path.segments[0].ident == "primary_key")
+            .and_then(|attr| EntityFieldAnnotation::try_from(&attr).ok())
+    }
 
-        f.map(|v| v.ident.clone().unwrap().to_string())
+    fn is_primary_key(field: &Field) -> bool {
+        field
+            .attrs
+            .iter()
+            .any(|attr| attr.path.segments[0].ident == "primary_key")
     }
 
-    /// Utility for find the `foreign_key` attributes (if exists)
-    pub fn get_fk_annotations(&self) -> Vec<(&Ident, EntityFieldAnnotation)> {
+    /// Utility for find the `foreign_key` attributes (if exists), along with the
+    /// field where they're declared
+    pub fn get_fk_annotations(&self) -> Vec<(&Field, EntityFieldAnnotation)> {
         let mut foreign_key_annotations = Vec::new();
 
         self.fields.iter().for_each(|field| {
@@ -155,7 +198,7 @@ impl<'a> MacroTokens<'a> {
             attrs.for_each(|attr| {
                 let fk_parse = EntityFieldAnnotation::try_from(&attr);
                 if let Ok(fk_annotation) = fk_parse {
-                    foreign_key_annotations.push((field.ident.as_ref().unwrap(), fk_annotation))
+                    foreign_key_annotations.push((field, fk_annotation))
                 }
             });
         });
@@ -163,45 +206,93 @@ impl<'a> MacroTokens<'a> {
         foreign_key_annotations
     }
 
-    /// Boolean that returns true if the type contains a `#[primary_key]`
-    /// annotation. False otherwise.
-    pub fn type_has_primary_key(&self) -> bool {
-        self.fields.iter().any(|field| {
+    /// Utility for find the rules declared on the `validate` attributes (if exists),
+    /// along with the field where they're declared
+    pub fn get_validation_rules(&self) -> Vec<(&Ident, ValidationRule)> {
+        let mut validation_rules = Vec::new();
+
+        self.fields.iter().for_each(|field| {
+            let attrs = field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path.segments[0].clone().ident == "validate");
+            attrs.for_each(|attr| {
+                if let Ok(EntityFieldAnnotation::Validate(rules)) =
+                    EntityFieldAnnotation::try_from(&attr)
+                {
+                    for rule in rules {
+                        validation_rules.push((field.ident.as_ref().unwrap(), rule))
+                    }
+                }
+            });
+        });
+
+        validation_rules
+    }
+
+    /// Utility for find the first field annotated with the attribute
+    /// identified by `annotation` (if exists)
+    pub fn get_annotated_field(&self, annotation: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| {
             field
                 .attrs
                 .iter()
-                .map(|attr| attr.path.segments[0].clone().ident)
-                .map(|ident| ident.to_string())
-                .find(|a| a == "primary_key")
-                == Some("primary_key".to_string())
+                .any(|attr| attr.path.segments[0].ident == annotation)
         })
     }
 
+    /// Utility for find the column name of the field annotated with
+    /// the `version` attribute (if exists)
+    pub fn get_version_annotation(&self) -> Option<String> {
+        self.get_annotated_field("version")
+            .map(helpers::column_name)
+    }
+
+    /// Returns the fields annotated with `#[created_at]` and `#[updated_at]`, which
+    /// must be filled by Canyon with the current time when the entity is inserted
+    pub fn get_insert_timestamp_fields(&self) -> Vec<&Field> {
+        self.get_annotated_field("created_at")
+            .into_iter()
+            .chain(self.get_annotated_field("updated_at"))
+            .collect()
+    }
+
+    /// Returns the SQL condition that discards the rows marked as deleted,
+    /// if the type contains a `#[soft_delete]` annotation
+    pub fn soft_delete_filter(&self) -> Option<String> {
+        self.get_annotated_field("soft_delete")
+            .map(|field| format!("{} IS NULL", helpers::column_identifier(field)))
+    }
+
+    /// Returns true if the type is annotated with `#[entity_hooks]`, so it provides
+    /// its own implementation of the `EntityHooks` trait
+    pub fn has_entity_hooks(&self) -> bool {
+        self.attrs
+            .iter()
+            .any(|attr| attr.path.is_ident("entity_hooks"))
+    }
+
+    /// Returns true if the type is annotated with `#[tracked]`, so it implements
+    /// the `Trackable` trait
+    pub fn is_tracked(&self) -> bool {
+        self.attrs.iter().any(|attr| attr.path.is_ident("tracked"))
+    }
+
+    /// Boolean that returns true if the type contains a `#[primary_key]`
+    /// annotation. False otherwise.
+    pub fn type_has_primary_key(&self) -> bool {
+        self.fields.iter().any(Self::is_primary_key)
+    }
+
     /// Returns an String ready to be inserted on the VALUES Sql clause
     /// representing generic query parameters ($x).
     ///
-    /// Already returns the correct number of placeholders, skipping one
-    /// entry in the type contains a `#[primary_key]`
+    /// Already returns the correct number of placeholders, one for
+    /// every field returned by [`MacroTokens::get_insert_fields`]
     pub fn placeholders_generator(&self) -> String {
-        let mut placeholders = String::new();
-        if self.type_has_primary_key() {
-            for num in 1..self.fields.len() {
-                if num < self.fields.len() - 1 {
-                    placeholders.push_str(&("$".to_owned() + &(num).to_string() + ", "));
-                } else {
-                    placeholders.push_str(&("$".to_owned() + &(num).to_string()));
-                }
-            }
-        } else {
-            for num in 1..self.fields.len() + 1 {
-                if num < self.fields.len() {
-                    placeholders.push_str(&("$".to_owned() + &(num).to_string() + ", "));
-                } else {
-                    placeholders.push_str(&("$".to_owned() + &(num).to_string()));
-                }
-            }
-        }
-
-        placeholders
+        (1..=self.get_insert_fields().len())
+            .map(|num| format!("${num}"))
+            .collect::<Vec<String>>()
+            .join(", ")
     }
 }
@@ -57,7 +57,9 @@ pub mod mssql_queries {
             fk.foreign_key_info,
             fk.foreign_key_name,
             pk.CONSTRAINT_NAME as primary_key_info,
-            pk.CONSTRAINT_NAME as primary_key_name
+            pk.CONSTRAINT_NAME as primary_key_name,
+            ck.check_constraint_info,
+            ck.check_constraint_name
             FROM INFORMATION_SCHEMA.COLUMNS gi
             LEFT JOIN (
                 SELECT
@@ -87,6 +89,21 @@ pub mod mssql_queries {
                 WHERE OBJECTPROPERTY(OBJECT_ID(kcu.CONSTRAINT_SCHEMA + '.' + QUOTENAME(kcu.CONSTRAINT_NAME)), 'IsPrimaryKey') = 1
             ) AS pk
                 ON pk.TABLE_NAME = gi.TABLE_NAME AND pk.CONSTRAINT_SCHEMA = gi.TABLE_SCHEMA AND pk.COLUMN_NAME = gi.COLUMN_NAME
+            LEFT JOIN (
+                SELECT
+                    SCHEMA_NAME(t.schema_id) schemaName,
+                    OBJECT_NAME(cc.parent_object_id) ConstrainedTable,
+                    COL_NAME(cc.parent_object_id, cc.parent_column_id) ConstrainedColumn,
+                    cc.name check_constraint_name,
+                    cc.definition check_constraint_info
+                FROM
+                    sys.check_constraints AS cc
+                INNER JOIN
+                    sys.tables t
+                ON t.OBJECT_ID = cc.parent_object_id
+                WHERE cc.parent_column_id > 0
+            ) AS ck
+                ON ck.ConstrainedTable = gi.TABLE_NAME AND ck.ConstrainedColumn = gi.COLUMN_NAME AND gi.TABLE_SCHEMA = ck.schemaName
             WHERE gi.TABLE_SCHEMA = 'dbo'";
 }
 
@@ -140,6 +157,18 @@ pub mod rust_type {
 
     pub const NAIVE_DATE_TIME: &str = "NaiveDateTime";
     pub const OPT_NAIVE_DATE_TIME: &str = "Option<NaiveDateTime>";
+
+    pub const DATE_TIME_UTC: &str = "DateTime<Utc>";
+    pub const OPT_DATE_TIME_UTC: &str = "Option<DateTime<Utc>>";
+
+    pub const UUID: &str = "Uuid";
+    pub const OPT_UUID: &str = "Option<Uuid>";
+
+    pub const DECIMAL: &str = "Decimal";
+    pub const OPT_DECIMAL: &str = "Option<Decimal>";
+
+    pub const BYTES: &str = "Vec<u8>";
+    pub const OPT_BYTES: &str = "Option<Vec<u8>>";
 }
 
 #[cfg(feature = "postgres")]
@@ -153,6 +182,19 @@ pub mod postgresql_type {
     pub const DATE: &str = "date";
     pub const TIME: &str = "time";
     pub const DATETIME: &str = "timestamp without time zone";
+    pub const DATETIME_TZ: &str = "timestamp with time zone";
+    pub const UUID: &str = "uuid";
+    pub const NUMERIC: &str = "numeric";
+    pub const JSONB: &str = "jsonb";
+    pub const BYTEA: &str = "bytea";
+    pub const REAL: &str = "real";
+    pub const DOUBLE_PRECISION: &str = "double precision";
+    pub const ARRAY: &str = "array";
+    pub const USER_DEFINED: &str = "user-defined";
+
+    pub const CURRENT_UTC_DATETIME: &str = "(CURRENT_TIMESTAMP AT TIME ZONE 'UTC')";
+    pub const CURRENT_DATETIME_TZ: &str = "CURRENT_TIMESTAMP";
+    pub const RANDOM_UUID: &str = "gen_random_uuid()";
 }
 
 #[cfg(feature = "mssql")]
@@ -167,6 +209,17 @@ pub mod sqlserver_type {
     pub const DATE: &str = "DATE";
     pub const TIME: &str = "TIME";
     pub const DATETIME: &str = "DATETIME2";
+    pub const DATETIME_TZ: &str = "DATETIMEOFFSET";
+    pub const UNIQUEIDENTIFIER: &str = "UNIQUEIDENTIFIER";
+    pub const DECIMAL: &str = "DECIMAL";
+    pub const VARBINARY: &str = "varbinary(max)";
+    /// SQL Server always requires the precision of the `DECIMAL` columns, so this one is
+    /// used for the fields without a `#[column(precision = x, scale = y)]` annotation
+    pub const DEFAULT_DECIMAL_PRECISION_AND_SCALE: (u32, u32) = (38, 10);
+
+    pub const CURRENT_UTC_DATETIME: &str = "SYSUTCDATETIME()";
+    pub const CURRENT_DATETIME_TZ: &str = "SYSDATETIMEOFFSET()";
+    pub const RANDOM_UUID: &str = "NEWID()";
 }
 
 pub mod mocked_data {
@@ -188,7 +241,9 @@ pub mod mocked_data {
                     primary_key_info: Some("PK__league__3213E83FBDA92571".to_owned()),
                     primary_key_name: Some("PK__league__3213E83FBDA92571".to_owned()),
                     is_identity: false,
-                    identity_generation: None
+                    identity_generation: None,
+                    check_constraint_info: None,
+                    check_constraint_name: None
                 },
                 ColumnMetadata {
                     column_name: "ext_id".to_owned(),
@@ -201,7 +256,9 @@ pub mod mocked_data {
                     primary_key_info: None,
                     primary_key_name: None,
                     is_identity: false,
-                    identity_generation: None
+                    identity_generation: None,
+                    check_constraint_info: None,
+                    check_constraint_name: None
                 },
                 ColumnMetadata {
                     column_name: "slug".to_owned(),
@@ -214,7 +271,9 @@ pub mod mocked_data {
                     primary_key_info: None,
                     primary_key_name: None,
                     is_identity: false,
-                    identity_generation: None
+                    identity_generation: None,
+                    check_constraint_info: None,
+                    check_constraint_name: None
                 },
                 ColumnMetadata {
                     column_name: "name".to_owned(),
@@ -227,7 +286,9 @@ pub mod mocked_data {
                     primary_key_info: None,
                     primary_key_name: None,
                     is_identity: false,
-                    identity_generation: None
+                    identity_generation: None,
+                    check_constraint_info: None,
+                    check_constraint_name: None
                 },
                 ColumnMetadata {
                     column_name: "region".to_owned(),
@@ -240,7 +301,9 @@ pub mod mocked_data {
                     primary_key_info: None,
                     primary_key_name: None,
                     is_identity: false,
-                    identity_generation: None
+                    identity_generation: None,
+                    check_constraint_info: None,
+                    check_constraint_name: None
                 },
                 ColumnMetadata {
                     column_name: "image_url".to_owned(),
@@ -253,7 +316,9 @@ pub mod mocked_data {
                     primary_key_info: None,
                     primary_key_name: None,
                     is_identity: false,
-                    identity_generation: None
+                    identity_generation: None,
+                    check_constraint_info: None,
+                    check_constraint_name: None
                 }
             ]
         };
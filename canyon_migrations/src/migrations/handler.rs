@@ -1,6 +1,6 @@
 use canyon_connection::{datasources::Migrations as MigrationsStatus, DATASOURCES};
 use canyon_crud::rows::CanyonRows;
-use canyon_entities::CANYON_REGISTER_ENTITIES;
+use canyon_entities::{CANYON_REGISTER_ENTITIES, CANYON_REGISTER_JOIN_TABLES};
 use partialdebug::placeholder::PartialDebug;
 
 use crate::{
@@ -40,6 +40,13 @@ impl Migrations {
                 );
                 continue;
             }
+            if datasource.is_mock() {
+                println!(
+                    "Skipped datasource: {:?} for being a mock datasource",
+                    datasource.name
+                );
+                continue;
+            }
             println!(
                 "Processing migrations for datasource: {:?}",
                 datasource.name
@@ -47,9 +54,20 @@ impl Migrations {
 
             let mut migrations_processor = MigrationsProcessor::default();
 
-            let canyon_entities = CANYON_REGISTER_ENTITIES.lock().unwrap().to_vec();
+            let mut canyon_entities = CANYON_REGISTER_ENTITIES.lock().unwrap().to_vec();
+            canyon_entities
+                .iter_mut()
+                .for_each(|entity| entity.flatten_embedded_fields());
             let canyon_memory = CanyonMemory::remember(datasource, &canyon_entities).await;
 
+            // The join tables of the `#[many_to_many]` relations are managed along with
+            // the entities, although they aren't tracked by the memory
+            let join_tables = CANYON_REGISTER_JOIN_TABLES.lock().unwrap().to_vec();
+            let join_table_entities = join_tables
+                .iter()
+                .filter_map(|join_table| join_table.as_entity(&canyon_entities))
+                .collect::<Vec<_>>();
+
             // Tracked entities that must be migrated whenever Canyon starts
             let schema_status =
                 Self::fetch_database(&datasource.name, datasource.get_db_type()).await;
@@ -67,11 +85,15 @@ impl Migrations {
                         .renamed_entities
                         .values()
                         .any(|f| *f == parsed_table.table_name)
+                    || join_table_entities
+                        .iter()
+                        .any(|e| e.entity_db_table_name == parsed_table.table_name)
                 {
                     user_database_tables.append(&mut vec![parsed_table]);
                 }
             }
 
+            canyon_entities.extend(join_table_entities);
             migrations_processor
                 .process(
                     canyon_memory,
@@ -199,6 +221,14 @@ impl Migrations {
             if let ColumnMetadataTypeValue::StringValue(value) = &column_value {
                 dest.identity_generation = value.to_owned()
             }
+        } else if column_identifier == "check_constraint_info" {
+            if let ColumnMetadataTypeValue::StringValue(value) = &column_value {
+                dest.check_constraint_info = value.to_owned()
+            }
+        } else if column_identifier == "check_constraint_name" {
+            if let ColumnMetadataTypeValue::StringValue(value) = &column_value {
+                dest.check_constraint_name = value.to_owned()
+            }
         };
     }
 
@@ -29,6 +29,9 @@ pub struct ColumnMetadata {
     pub primary_key_name: Option<String>,
     pub is_identity: bool, // Care, postgres type is varchar
     pub identity_generation: Option<String>,
+    // The column level `CHECK` constraint (SQL Server only)
+    pub check_constraint_info: Option<String>,
+    pub check_constraint_name: Option<String>,
 }
 
 /// Represents the relation between a real value stored inside a [`ColumnMetadata`]
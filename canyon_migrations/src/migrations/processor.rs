@@ -6,17 +6,26 @@ use regex::Regex;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::ops::Not;
+use tracing::Instrument;
 
 use crate::canyon_crud::{crud::Transaction, DatasourceConfig};
+#[cfg(feature = "postgres")]
+use crate::constants::postgresql_type;
 use crate::constants::regex_patterns;
 use crate::save_migrations_query_to_execute;
 
 use super::information_schema::{ColumnMetadata, TableMetadata};
 use super::memory::CanyonMemory;
+#[cfg(feature = "mssql")]
+use crate::migrations::transforms::{
+    sqlserver_enum_check, sqlserver_enum_literals, to_sqlserver_alter_syntax, to_sqlserver_syntax,
+};
 #[cfg(feature = "postgres")]
 use crate::migrations::transforms::{to_postgres_alter_syntax, to_postgres_syntax};
 #[cfg(feature = "mssql")]
-use crate::migrations::transforms::{to_sqlserver_alter_syntax, to_sqlserver_syntax};
+use canyon_entities::register_types::CanyonRegisterEnum;
+#[cfg(any(feature = "postgres", all(test, feature = "mssql")))]
+use canyon_entities::register_types::CanyonRegisterEnumRepresentation;
 use canyon_entities::register_types::{CanyonRegisterEntity, CanyonRegisterEntityField};
 
 /// Responsible of generating the queries to sync the database status with the
@@ -27,6 +36,8 @@ pub struct MigrationsProcessor {
     set_primary_key_operations: Vec<Box<dyn DatabaseOperation>>,
     drop_primary_key_operations: Vec<Box<dyn DatabaseOperation>>,
     constraints_operations: Vec<Box<dyn DatabaseOperation>>,
+    // The fields of the entity being processed whose primary key must be set on the database
+    pending_primary_key_fields: Vec<CanyonRegisterEntityField>,
 }
 impl Transaction<Self> for MigrationsProcessor {}
 
@@ -40,6 +51,13 @@ impl MigrationsProcessor {
     ) {
         // The database type formally represented in Canyon
         let db_type = datasource.get_db_type();
+
+        // The native enum types must exist before the tables whose columns uses them
+        #[cfg(feature = "postgres")]
+        if db_type == DatabaseType::PostgreSql {
+            self.create_enum_types(&canyon_entities);
+        }
+
         // For each entity (table) on the register (Rust structs)
         for canyon_register_entity in canyon_entities {
             let entity_name = canyon_register_entity.entity_db_table_name;
@@ -67,7 +85,7 @@ impl MigrationsProcessor {
             );
 
             // For each field (column) on the this canyon register entity
-            for canyon_register_field in canyon_register_entity.entity_fields {
+            for canyon_register_field in canyon_register_entity.entity_fields.clone() {
                 let current_column_metadata = MigrationsHelper::get_current_column_metadata(
                     canyon_register_field.field_name.clone(),
                     current_table_metadata,
@@ -103,6 +121,12 @@ impl MigrationsProcessor {
                     )
                 }
             }
+
+            self.set_primary_key(
+                entity_name,
+                &canyon_register_entity.entity_fields,
+                current_table_metadata,
+            );
         }
 
         for operation in &self.operations {
@@ -122,6 +146,30 @@ impl MigrationsProcessor {
         // Self::from_query_register(datasource_name).await;
     }
 
+    /// Creates the native PostgreSQL enum types of the `CanyonEnum` fields of the entities
+    #[cfg(feature = "postgres")]
+    fn create_enum_types(&mut self, canyon_entities: &[CanyonRegisterEntity<'_>]) {
+        let mut created_types = Vec::new();
+        for field in canyon_entities
+            .iter()
+            .flat_map(|entity| entity.entity_fields.iter())
+        {
+            if let Some(canyon_enum) = field.canyon_enum() {
+                if let CanyonRegisterEnumRepresentation::Native(type_name) =
+                    canyon_enum.representation
+                {
+                    if !created_types.contains(&type_name) {
+                        created_types.push(type_name.clone());
+                        self.operations.push(Box::new(TypeOperation::CreateEnumType(
+                            type_name,
+                            canyon_enum.values,
+                        )));
+                    }
+                }
+            }
+        }
+    }
+
     /// The operation that checks if an entity must be update is name in the database
     fn create_or_rename_tables<'a>(
         &mut self,
@@ -232,6 +280,26 @@ impl MigrationsProcessor {
             )
         }
 
+        // SQL Server restricts the values of the enums with a `CHECK` constraint, that
+        // must be replaced when the variants of the enum change
+        #[cfg(feature = "mssql")]
+        if let Some(column_metadata) = current_column_metadata {
+            if let Some(canyon_enum) = canyon_register_entity_field.canyon_enum() {
+                if db_type == DatabaseType::SqlServer
+                    && !MigrationsHelper::is_same_enum_check(
+                        &canyon_enum,
+                        column_metadata.check_constraint_info.as_deref(),
+                    )
+                {
+                    self.replace_enum_check(
+                        entity_name.to_string(),
+                        canyon_register_entity_field.clone(),
+                        column_metadata.check_constraint_name.clone(),
+                    )
+                }
+            }
+        }
+
         if let Some(column_metadata) = current_column_metadata {
             if canyon_register_entity_field.is_nullable() != column_metadata.is_nullable {
                 if column_metadata.is_nullable {
@@ -277,6 +345,21 @@ impl MigrationsProcessor {
             )));
     }
 
+    #[cfg(feature = "mssql")]
+    fn replace_enum_check(
+        &mut self,
+        table_name: String,
+        field: CanyonRegisterEntityField,
+        constraint_name: Option<String>,
+    ) {
+        self.operations
+            .push(Box::new(ColumnOperation::ReplaceEnumCheck(
+                table_name,
+                field,
+                constraint_name,
+            )));
+    }
+
     fn set_not_null(&mut self, table_name: String, field: CanyonRegisterEntityField) {
         self.operations
             .push(Box::new(ColumnOperation::AlterColumnSetNotNull(
@@ -320,7 +403,7 @@ impl MigrationsProcessor {
                 );
             }
             if attr.starts_with("Annotation: PrimaryKey") {
-                Self::add_primary_key(self, entity_name, canyon_register_entity_field.clone());
+                Self::add_primary_key(self, canyon_register_entity_field.clone());
 
                 #[cfg(feature = "postgres")]
                 {
@@ -350,15 +433,56 @@ impl MigrationsProcessor {
             )));
     }
 
-    fn add_primary_key(
+    /// The primary key isn't added until every field of the entity has been processed,
+    /// see [`MigrationsProcessor::set_primary_key`]
+    fn add_primary_key(&mut self, canyon_register_entity_field: CanyonRegisterEntityField) {
+        self.pending_primary_key_fields
+            .push(canyon_register_entity_field);
+    }
+
+    /// Generates the operation that sets the primary key of the table once every field of
+    /// the entity has been processed, so a composite primary key is added as a single
+    /// constraint over all the columns annotated with `#[primary_key]`.
+    ///
+    /// If some of them already belongs to a primary key on the database, that
+    /// constraint is dropped to be replaced by the new one
+    fn set_primary_key(
         &mut self,
         entity_name: &str,
-        canyon_register_entity_field: CanyonRegisterEntityField,
+        entity_fields: &[CanyonRegisterEntityField],
+        current_table_metadata: Option<&TableMetadata>,
     ) {
+        if self.pending_primary_key_fields.is_empty() {
+            return;
+        }
+        self.pending_primary_key_fields.clear();
+
+        let primary_key_fields = entity_fields
+            .iter()
+            .filter(|field| {
+                field
+                    .annotations
+                    .iter()
+                    .any(|anno| anno.starts_with("Annotation: PrimaryKey"))
+            })
+            .cloned()
+            .collect::<Vec<CanyonRegisterEntityField>>();
+
+        let current_primary_key = primary_key_fields.iter().find_map(|field| {
+            MigrationsHelper::get_current_column_metadata(
+                field.field_name.clone(),
+                current_table_metadata,
+            )
+            .and_then(|column| column.primary_key_name.clone())
+        });
+        if let Some(primary_key_name) = current_primary_key {
+            Self::drop_primary_key(self, entity_name, primary_key_name);
+        }
+
         self.set_primary_key_operations
             .push(Box::new(TableOperation::AddTablePrimaryKey(
                 entity_name.to_string(),
-                canyon_register_entity_field,
+                primary_key_fields,
             )));
     }
 
@@ -396,7 +520,7 @@ impl MigrationsProcessor {
         // ------------ PRIMARY KEY ---------------
         // Case when field contains a primary key annotation, and it's not already on database, add it to constrains_operations
         if field_is_primary_key && current_column_metadata.primary_key_info.is_none() {
-            Self::add_primary_key(self, entity_name, canyon_register_entity_field.clone());
+            Self::add_primary_key(self, canyon_register_entity_field.clone());
 
             #[cfg(feature = "postgres")]
             {
@@ -569,22 +693,52 @@ impl MigrationsProcessor {
             )));
     }
 
-    /// Make the detected migrations for the next Canyon-SQL run
+    /// Make the detected migrations for the next Canyon-SQL run.
+    ///
+    /// The statements of every datasource are executed inside a `migrations` span of
+    /// the `canyon_sql::migrations` target, emitting an event with the outcome of each one
     #[allow(clippy::await_holding_lock)]
     pub async fn from_query_register(queries_to_execute: &HashMap<&str, Vec<&str>>) {
         for datasource in queries_to_execute.iter() {
+            let span = tracing::info_span!(
+                target: "canyon_sql::migrations",
+                "migrations",
+                datasource = %datasource.0,
+                statements = datasource.1.len(),
+            );
             for query_to_execute in datasource.1 {
-                let res = Self::query(query_to_execute, [], datasource.0).await;
+                let res = Self::query(query_to_execute, [], datasource.0)
+                    .instrument(span.clone())
+                    .await;
 
                 match res {
-                    Ok(_) => println!(
-                        "\t[OK] - {:?} - Query: {:?}",
-                        datasource.0, &query_to_execute
-                    ),
-                    Err(e) => println!(
-                        "\t[ERR] - {:?} - Query: {:?}\nCause: {:?}",
-                        datasource.0, &query_to_execute, e
-                    ),
+                    Ok(_) => {
+                        span.in_scope(|| {
+                            tracing::info!(
+                                target: "canyon_sql::migrations",
+                                statement = %query_to_execute,
+                                "Migration applied"
+                            )
+                        });
+                        println!(
+                            "\t[OK] - {:?} - Query: {:?}",
+                            datasource.0, &query_to_execute
+                        )
+                    }
+                    Err(e) => {
+                        span.in_scope(|| {
+                            tracing::error!(
+                                target: "canyon_sql::migrations",
+                                statement = %query_to_execute,
+                                error = %e,
+                                "Migration failed"
+                            )
+                        });
+                        println!(
+                            "\t[ERR] - {:?} - Query: {:?}\nCause: {:?}",
+                            datasource.0, &query_to_execute, e
+                        )
+                    }
                 }
                 // TODO Ask for user input?
             }
@@ -662,18 +816,43 @@ impl MigrationsHelper {
         canyon_register_entity_field: &CanyonRegisterEntityField,
         current_column_metadata: &ColumnMetadata,
     ) -> bool {
+        // Only the datatype is compared, without its parameters (like the precision
+        // and the scale of "NUMERIC(18,4)", or the length of "NVARCHAR(MAX)")
+        let without_parameters = |datatype: String| {
+            datatype
+                .split('(')
+                .next()
+                .unwrap_or_default()
+                .to_lowercase()
+        };
+
         #[cfg(feature = "postgres")]
         {
             if db_type == DatabaseType::PostgreSql {
-                return to_postgres_alter_syntax(canyon_register_entity_field).to_lowercase()
+                // The information schema reports every array column just as an "ARRAY"
+                if canyon_register_entity_field.array_element_type().is_some() {
+                    return current_column_metadata
+                        .datatype
+                        .eq_ignore_ascii_case(postgresql_type::ARRAY);
+                }
+                // And the columns of the native enum types as "USER-DEFINED"
+                if let Some(CanyonRegisterEnumRepresentation::Native(_)) =
+                    canyon_register_entity_field
+                        .canyon_enum()
+                        .map(|canyon_enum| canyon_enum.representation)
+                {
+                    return current_column_metadata
+                        .datatype
+                        .eq_ignore_ascii_case(postgresql_type::USER_DEFINED);
+                }
+                return without_parameters(to_postgres_alter_syntax(canyon_register_entity_field))
                     == current_column_metadata.datatype;
             }
         }
         #[cfg(feature = "mssql")]
         {
             if db_type == DatabaseType::SqlServer {
-                // TODO Search a better way to get the datatype without useless info (like "VARCHAR(MAX)")
-                return to_sqlserver_alter_syntax(canyon_register_entity_field).to_lowercase()
+                return without_parameters(to_sqlserver_alter_syntax(canyon_register_entity_field))
                     == current_column_metadata.datatype;
             }
         }
@@ -681,6 +860,38 @@ impl MigrationsHelper {
         false
     }
 
+    /// Checks if the `CHECK` constraint of a SQL Server column allows the same values as the
+    /// variants of the `CanyonEnum` stored on it. SQL Server rewrites the `IN` list of the
+    /// constraint as `([column]='A' OR [column]='B')`, or `([column]=(1) OR [column]=(2))`
+    /// for the integer representation, so just the literals of the definition are compared
+    #[cfg(feature = "mssql")]
+    fn is_same_enum_check(
+        canyon_enum: &CanyonRegisterEnum,
+        check_constraint_info: Option<&str>,
+    ) -> bool {
+        let definition = match check_constraint_info {
+            Some(definition) => definition,
+            None => return false,
+        };
+        let literals_regex = Regex::new(r"'(?:[^']|'')*'|\(-?\d+\)").unwrap();
+
+        let mut current_values = literals_regex
+            .find_iter(definition)
+            .map(|literal| {
+                literal
+                    .as_str()
+                    .trim_start_matches('(')
+                    .trim_end_matches(')')
+                    .to_string()
+            })
+            .collect::<Vec<String>>();
+        let mut expected_values = sqlserver_enum_literals(canyon_enum);
+        current_values.sort();
+        expected_values.sort();
+
+        current_values == expected_values
+    }
+
     fn extract_foreign_key_annotation(field_annotations: &[String]) -> (String, String) {
         let opt_fk_annotation = field_annotations
             .iter()
@@ -743,6 +954,44 @@ mod migrations_helper_tests {
         );
         assert!(!mocked_league_entity_on_database)
     }
+
+    #[cfg(feature = "mssql")]
+    #[test]
+    fn test_is_same_enum_check() {
+        let mut canyon_enum = CanyonRegisterEnum {
+            enum_name: "Status".to_string(),
+            representation: CanyonRegisterEnumRepresentation::Text,
+            values: vec!["Active".to_string(), "O'Brien".to_string()],
+        };
+
+        // The definitions are stored by SQL Server with the `IN` list rewritten
+        assert!(MigrationsHelper::is_same_enum_check(
+            &canyon_enum,
+            Some("([status]='O''Brien' OR [status]='Active')")
+        ));
+        assert!(!MigrationsHelper::is_same_enum_check(&canyon_enum, None));
+
+        // A variant added later to the enum isn't allowed by the current constraint
+        canyon_enum.values.push("Retired".to_string());
+        assert!(!MigrationsHelper::is_same_enum_check(
+            &canyon_enum,
+            Some("([status]='Active' OR [status]='O''Brien')")
+        ));
+
+        let canyon_enum = CanyonRegisterEnum {
+            enum_name: "Priority".to_string(),
+            representation: CanyonRegisterEnumRepresentation::Integer,
+            values: vec!["1".to_string(), "-2".to_string()],
+        };
+        assert!(MigrationsHelper::is_same_enum_check(
+            &canyon_enum,
+            Some("([priority]=(1) OR [priority]=(-2))")
+        ));
+        assert!(!MigrationsHelper::is_same_enum_check(
+            &canyon_enum,
+            Some("([priority]=(1))")
+        ));
+    }
 }
 
 /// Trait that enables implementors to generate the migration queries
@@ -762,8 +1011,8 @@ enum TableOperation {
     AddTableForeignKey(String, String, String, String, String),
     // table_with_foreign_key, constraint_name
     DeleteTableForeignKey(String, String),
-    // table_name, entity_field, column_name
-    AddTablePrimaryKey(String, CanyonRegisterEntityField),
+    // table_name, entity_fields (more than one for composite primary keys)
+    AddTablePrimaryKey(String, Vec<CanyonRegisterEntityField>),
     // table_name, constraint_name
     DeleteTablePrimaryKey(String, String),
 }
@@ -863,12 +1112,16 @@ impl DatabaseOperation for TableOperation {
                 }
             }
 
-            TableOperation::AddTablePrimaryKey(_table_name, _entity_field) => {
+            TableOperation::AddTablePrimaryKey(_table_name, _entity_fields) => {
                 match db_type {
                     #[cfg(feature = "postgres")] DatabaseType::PostgreSql =>
                         format!(
-                            "ALTER TABLE \"{_table_name}\" ADD PRIMARY KEY (\"{}\");",
-                            _entity_field.field_name
+                            "ALTER TABLE \"{_table_name}\" ADD PRIMARY KEY ({});",
+                            _entity_fields
+                                .iter()
+                                .map(|entity_field| format!("\"{}\"", entity_field.field_name))
+                                .collect::<Vec<String>>()
+                                .join(", ")
                         ),
                     #[cfg(feature = "mssql")] DatabaseType::SqlServer =>
                         todo!("[MS-SQL -> Operation still won't supported by Canyon for Sql Server]")
@@ -903,6 +1156,9 @@ enum ColumnOperation {
     #[cfg(feature = "mssql")]
     // SQL server specific operation - SQL server can't drop a NOT NULL column
     DropNotNullBeforeDropColumn(String, String, String),
+    #[cfg(feature = "mssql")]
+    // SQL server specific operation - table_name, entity_field, current constraint_name
+    ReplaceEnumCheck(String, CanyonRegisterEntityField, Option<String>),
     #[cfg(feature = "postgres")]
     AlterColumnAddIdentity(String, CanyonRegisterEntityField),
     #[cfg(feature = "postgres")]
@@ -973,6 +1229,17 @@ impl DatabaseOperation for ColumnOperation {
                     EXEC('ALTER TABLE '+@tableName+' DROP CONSTRAINT ' + @ConstraintName);"
             ),
 
+            #[cfg(feature = "mssql")] ColumnOperation::ReplaceEnumCheck(table_name, entity_field, constraint_name) => {
+                let drop_check = constraint_name
+                    .as_ref()
+                    .map(|name| format!("ALTER TABLE {table_name} DROP CONSTRAINT [{name}]; "))
+                    .unwrap_or_default();
+                format!(
+                    "{drop_check}ALTER TABLE {table_name} ADD {};",
+                    sqlserver_enum_check(entity_field).unwrap_or_default()
+                )
+            }
+
             ColumnOperation::AlterColumnSetNotNull(table_name, entity_field) => {
                 match db_type {
                     #[cfg(feature = "postgres")] DatabaseType::PostgreSql => format!(
@@ -999,6 +1266,55 @@ impl DatabaseOperation for ColumnOperation {
     }
 }
 
+/// Helper for the operations over the user defined types of PostgreSQL
+#[cfg(feature = "postgres")]
+#[derive(Debug)]
+enum TypeOperation {
+    // type_name, labels
+    CreateEnumType(String, Vec<String>),
+}
+#[cfg(feature = "postgres")]
+impl Transaction<Self> for TypeOperation {}
+
+#[cfg(feature = "postgres")]
+#[async_trait]
+impl DatabaseOperation for TypeOperation {
+    async fn generate_sql(&self, datasource: &DatasourceConfig) {
+        let stmt = match self {
+            // PostgreSQL doesn't support `CREATE TYPE IF NOT EXISTS`. When the type already
+            // exists, the variants added later to the enum are appended to it, after the
+            // previous one, so the labels keep the order of the variants
+            TypeOperation::CreateEnumType(type_name, labels) => {
+                let labels = labels
+                    .iter()
+                    .map(|label| format!("'{}'", label.replace('\'', "''")))
+                    .collect::<Vec<String>>();
+                let add_values = labels
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, label)| {
+                        let position = match idx {
+                            0 => String::new(),
+                            _ => format!(" AFTER {}", labels[idx - 1]),
+                        };
+                        format!(
+                            "ALTER TYPE \"{type_name}\" ADD VALUE IF NOT EXISTS {label}{position};"
+                        )
+                    })
+                    .collect::<Vec<String>>()
+                    .join(" ");
+
+                format!(
+                    "DO $$ BEGIN CREATE TYPE \"{type_name}\" AS ENUM ({}); \
+                    EXCEPTION WHEN duplicate_object THEN {add_values} END $$;",
+                    labels.join(", ")
+                )
+            }
+        };
+        save_migrations_query_to_execute(stmt, &datasource.name);
+    }
+}
+
 /// Helper for operations involving sequences
 #[cfg(feature = "postgres")]
 #[derive(Debug)]
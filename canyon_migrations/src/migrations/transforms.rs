@@ -4,7 +4,11 @@ use crate::constants::postgresql_type;
 use crate::constants::sqlserver_type;
 use crate::constants::{regex_patterns, rust_type};
 
-use canyon_entities::register_types::CanyonRegisterEntityField;
+#[cfg(feature = "mssql")]
+use canyon_entities::register_types::CanyonRegisterEnum;
+use canyon_entities::register_types::{
+    CanyonRegisterEntityField, CanyonRegisterEnumRepresentation,
+};
 use regex::Regex;
 
 /// Return the postgres datatype and parameters to create a column for a given rust type
@@ -12,7 +16,9 @@ use regex::Regex;
 pub fn to_postgres_syntax(field: &CanyonRegisterEntityField) -> String {
     let rust_type_clean = field.field_type.replace(' ', "");
 
-    match rust_type_clean.as_str() {
+    let mut column_definition = match rust_type_clean.as_str() {
+        _ if field.is_json() && field.is_nullable() => String::from(postgresql_type::JSONB),
+        _ if field.is_json() => format!("{} NOT NULL", postgresql_type::JSONB),
         rust_type::I8 | rust_type::U8 => {
             String::from(&format!("{} NOT NULL", postgresql_type::INTEGER))
         }
@@ -49,8 +55,52 @@ pub fn to_postgres_syntax(field: &CanyonRegisterEntityField) -> String {
             String::from(&format!("{} NOT NULL", postgresql_type::DATETIME))
         }
         rust_type::OPT_NAIVE_DATE_TIME => String::from(postgresql_type::DATETIME),
+
+        rust_type::DATE_TIME_UTC => {
+            String::from(&format!("{} NOT NULL", postgresql_type::DATETIME_TZ))
+        }
+        rust_type::OPT_DATE_TIME_UTC => String::from(postgresql_type::DATETIME_TZ),
+
+        rust_type::UUID => String::from(&format!("{} NOT NULL", postgresql_type::UUID)),
+        rust_type::OPT_UUID => String::from(postgresql_type::UUID),
+
+        rust_type::DECIMAL => format!("{} NOT NULL", postgres_numeric(field)),
+        rust_type::OPT_DECIMAL => postgres_numeric(field),
+
+        rust_type::BYTES => format!("{} NOT NULL", postgresql_type::BYTEA),
+        rust_type::OPT_BYTES => String::from(postgresql_type::BYTEA),
+
+        _ if field.array_element_type().is_some() && field.is_nullable() => postgres_array(field),
+        _ if field.array_element_type().is_some() => {
+            format!("{} NOT NULL", postgres_array(field))
+        }
+
+        _ if field.canyon_enum().is_some() && field.is_nullable() => postgres_enum(field),
+        _ if field.canyon_enum().is_some() => format!("{} NOT NULL", postgres_enum(field)),
+
+        _ if field.canyon_type().is_some() && field.is_nullable() => postgres_canyon_type(field),
+        _ if field.canyon_type().is_some() => {
+            format!("{} NOT NULL", postgres_canyon_type(field))
+        }
         &_ => todo!("Not supported datatype for this migrations version"),
+    };
+
+    // The columns filled with the current time by Canyon also get it as their default value
+    if field.is_auto_timestamp() {
+        let current_timestamp = if rust_type_clean.contains(rust_type::DATE_TIME_UTC) {
+            postgresql_type::CURRENT_DATETIME_TZ
+        } else {
+            postgresql_type::CURRENT_UTC_DATETIME
+        };
+        column_definition.push_str(&format!(" DEFAULT {current_timestamp}"));
+    }
+
+    // The `Uuid` primary keys generated by the database take a random value by default
+    if field.is_db_generated_uuid() {
+        column_definition.push_str(&format!(" DEFAULT {}", postgresql_type::RANDOM_UUID));
     }
+
+    column_definition
 }
 
 /// Return the postgres datatype and parameters to create a column for a given rust type
@@ -59,7 +109,9 @@ pub fn to_postgres_syntax(field: &CanyonRegisterEntityField) -> String {
 pub fn to_sqlserver_syntax(field: &CanyonRegisterEntityField) -> String {
     let rust_type_clean = field.field_type.replace(' ', "");
 
-    match rust_type_clean.as_str() {
+    let mut column_definition = match rust_type_clean.as_str() {
+        _ if field.is_json() && field.is_nullable() => String::from(sqlserver_type::NVARCHAR),
+        _ if field.is_json() => format!("{} NOT NULL", sqlserver_type::NVARCHAR),
         rust_type::I8 | rust_type::U8 => String::from(&format!("{} NOT NULL", sqlserver_type::INT)),
         rust_type::OPT_I8 | rust_type::OPT_U8 => String::from(sqlserver_type::INT),
 
@@ -96,8 +148,52 @@ pub fn to_sqlserver_syntax(field: &CanyonRegisterEntityField) -> String {
             String::from(&format!("{} NOT NULL", sqlserver_type::DATETIME))
         }
         rust_type::OPT_NAIVE_DATE_TIME => String::from(sqlserver_type::DATETIME),
+
+        rust_type::DATE_TIME_UTC => {
+            String::from(&format!("{} NOT NULL", sqlserver_type::DATETIME_TZ))
+        }
+        rust_type::OPT_DATE_TIME_UTC => String::from(sqlserver_type::DATETIME_TZ),
+
+        rust_type::UUID => String::from(&format!("{} NOT NULL", sqlserver_type::UNIQUEIDENTIFIER)),
+        rust_type::OPT_UUID => String::from(sqlserver_type::UNIQUEIDENTIFIER),
+
+        rust_type::DECIMAL => format!("{} NOT NULL", sqlserver_decimal(field)),
+        rust_type::OPT_DECIMAL => sqlserver_decimal(field),
+
+        rust_type::BYTES => format!("{} NOT NULL", sqlserver_type::VARBINARY),
+        rust_type::OPT_BYTES => String::from(sqlserver_type::VARBINARY),
+
+        _ if field.canyon_enum().is_some() && field.is_nullable() => sqlserver_enum(field),
+        _ if field.canyon_enum().is_some() => format!("{} NOT NULL", sqlserver_enum(field)),
+
+        _ if field.canyon_type().is_some() && field.is_nullable() => sqlserver_canyon_type(field),
+        _ if field.canyon_type().is_some() => {
+            format!("{} NOT NULL", sqlserver_canyon_type(field))
+        }
         &_ => todo!("Not supported datatype for this migrations version"),
+    };
+
+    // The columns filled with the current time by Canyon also get it as their default value
+    if field.is_auto_timestamp() {
+        let current_timestamp = if rust_type_clean.contains(rust_type::DATE_TIME_UTC) {
+            sqlserver_type::CURRENT_DATETIME_TZ
+        } else {
+            sqlserver_type::CURRENT_UTC_DATETIME
+        };
+        column_definition.push_str(&format!(" DEFAULT {current_timestamp}"));
+    }
+
+    // The `Uuid` primary keys generated by the database take a random value by default
+    if field.is_db_generated_uuid() {
+        column_definition.push_str(&format!(" DEFAULT {}", sqlserver_type::RANDOM_UUID));
     }
+
+    // SQL Server doesn't have enum types, so the stored values are restricted by a constraint
+    if let Some(check) = sqlserver_enum_check(field) {
+        column_definition.push_str(&format!(" {check}"));
+    }
+
+    column_definition
 }
 
 #[cfg(feature = "postgres")]
@@ -116,6 +212,7 @@ pub fn to_postgres_alter_syntax(field: &CanyonRegisterEntityField) -> String {
     }
 
     match rust_type_clean.as_str() {
+        _ if field.is_json() => String::from(postgresql_type::JSONB),
         rust_type::I8 | rust_type::U8 | rust_type::OPT_I8 | rust_type::OPT_U8 => {
             String::from(postgresql_type::INT_8)
         }
@@ -135,6 +232,15 @@ pub fn to_postgres_alter_syntax(field: &CanyonRegisterEntityField) -> String {
         rust_type::NAIVE_DATE_TIME | rust_type::OPT_NAIVE_DATE_TIME => {
             String::from(postgresql_type::DATETIME)
         }
+        rust_type::DATE_TIME_UTC | rust_type::OPT_DATE_TIME_UTC => {
+            String::from(postgresql_type::DATETIME_TZ)
+        }
+        rust_type::UUID | rust_type::OPT_UUID => String::from(postgresql_type::UUID),
+        rust_type::DECIMAL | rust_type::OPT_DECIMAL => postgres_numeric(field),
+        rust_type::BYTES | rust_type::OPT_BYTES => String::from(postgresql_type::BYTEA),
+        _ if field.array_element_type().is_some() => postgres_array(field),
+        _ if field.canyon_enum().is_some() => postgres_enum(field),
+        _ if field.canyon_type().is_some() => postgres_canyon_type(field),
         &_ => todo!("Not supported datatype for this migrations version"),
     }
 }
@@ -155,6 +261,7 @@ pub fn to_sqlserver_alter_syntax(field: &CanyonRegisterEntityField) -> String {
     }
 
     match rust_type_clean.as_str() {
+        _ if field.is_json() => String::from(sqlserver_type::NVARCHAR),
         rust_type::I8 | rust_type::U8 | rust_type::OPT_I8 | rust_type::OPT_U8 => {
             String::from(sqlserver_type::TINY_INT)
         }
@@ -174,6 +281,132 @@ pub fn to_sqlserver_alter_syntax(field: &CanyonRegisterEntityField) -> String {
         rust_type::NAIVE_DATE_TIME | rust_type::OPT_NAIVE_DATE_TIME => {
             String::from(sqlserver_type::DATETIME)
         }
+        rust_type::DATE_TIME_UTC | rust_type::OPT_DATE_TIME_UTC => {
+            String::from(sqlserver_type::DATETIME_TZ)
+        }
+        rust_type::UUID | rust_type::OPT_UUID => String::from(sqlserver_type::UNIQUEIDENTIFIER),
+        rust_type::DECIMAL | rust_type::OPT_DECIMAL => sqlserver_decimal(field),
+        rust_type::BYTES | rust_type::OPT_BYTES => String::from(sqlserver_type::VARBINARY),
+        _ if field.canyon_enum().is_some() => sqlserver_enum(field),
+        _ if field.canyon_type().is_some() => sqlserver_canyon_type(field),
         &_ => todo!("Not supported datatype for this migrations version"),
     }
 }
+
+/// Return the postgres array datatype for the elements of a `Vec<T>` field
+#[cfg(feature = "postgres")]
+fn postgres_array(field: &CanyonRegisterEntityField) -> String {
+    // The derive macros reject any other type of elements, so their paths just
+    // need to be matched by the last segment (`uuid::Uuid` is a `Uuid`)
+    let element_type = match field
+        .array_element_type()
+        .map(|element| element.rsplit("::").next().unwrap_or(element).trim())
+    {
+        Some(rust_type::BOOL) => postgresql_type::BOOLEAN,
+        Some(rust_type::I16) => postgresql_type::SMALL_INT,
+        Some(rust_type::I32) => postgresql_type::INTEGER,
+        Some(rust_type::I64) => postgresql_type::BIGINT,
+        Some(rust_type::F32) => postgresql_type::REAL,
+        Some(rust_type::F64) => postgresql_type::DOUBLE_PRECISION,
+        Some(rust_type::STRING) => postgresql_type::TEXT,
+        Some(rust_type::UUID) => postgresql_type::UUID,
+        _ => unreachable!(
+            "The type of the elements of the array field {} is rejected by the derive macros",
+            field.field_name
+        ),
+    };
+
+    format!("{element_type}[]")
+}
+
+/// Return the postgres datatype of the column that stores the variants of a `CanyonEnum`
+#[cfg(feature = "postgres")]
+fn postgres_enum(field: &CanyonRegisterEntityField) -> String {
+    match field
+        .canyon_enum()
+        .map(|canyon_enum| canyon_enum.representation)
+    {
+        Some(CanyonRegisterEnumRepresentation::Integer) => String::from(postgresql_type::INTEGER),
+        Some(CanyonRegisterEnumRepresentation::Native(type_name)) => format!("\"{type_name}\""),
+        _ => String::from(postgresql_type::TEXT),
+    }
+}
+
+/// Return the Microsoft SQL Server datatype of the column that stores the variants of
+/// a `CanyonEnum`. The native enums of PostgreSQL are stored as text
+#[cfg(feature = "mssql")]
+fn sqlserver_enum(field: &CanyonRegisterEntityField) -> String {
+    match field
+        .canyon_enum()
+        .map(|canyon_enum| canyon_enum.representation)
+    {
+        Some(CanyonRegisterEnumRepresentation::Integer) => String::from(sqlserver_type::INT),
+        _ => String::from(sqlserver_type::NVARCHAR),
+    }
+}
+
+/// Return the `CHECK` constraint that restricts the values stored on the column of a
+/// `CanyonEnum` field to the variants of the enum, since SQL Server doesn't have enum types
+#[cfg(feature = "mssql")]
+pub fn sqlserver_enum_check(field: &CanyonRegisterEntityField) -> Option<String> {
+    field.canyon_enum().map(|canyon_enum| {
+        format!(
+            "CHECK ([{}] IN ({}))",
+            field.field_name,
+            sqlserver_enum_literals(&canyon_enum).join(", ")
+        )
+    })
+}
+
+/// Return the SQL literals of the values of a `CanyonEnum` in the SQL Server columns
+#[cfg(feature = "mssql")]
+pub fn sqlserver_enum_literals(canyon_enum: &CanyonRegisterEnum) -> Vec<String> {
+    canyon_enum
+        .values
+        .iter()
+        .map(|value| match canyon_enum.representation {
+            CanyonRegisterEnumRepresentation::Integer => value.to_string(),
+            _ => format!("'{}'", value.replace('\'', "''")),
+        })
+        .collect()
+}
+
+/// Return the postgres datatype declared by the implementation of `CanyonType` of the
+/// type of the field
+#[cfg(feature = "postgres")]
+fn postgres_canyon_type(field: &CanyonRegisterEntityField) -> String {
+    field
+        .canyon_type()
+        .map(|canyon_type| canyon_type.postgres_type)
+        .unwrap_or_default()
+}
+
+/// Return the Microsoft SQL Server datatype declared by the implementation of
+/// `CanyonType` of the type of the field
+#[cfg(feature = "mssql")]
+fn sqlserver_canyon_type(field: &CanyonRegisterEntityField) -> String {
+    field
+        .canyon_type()
+        .map(|canyon_type| canyon_type.sqlserver_type)
+        .unwrap_or_default()
+}
+
+/// Return the postgres `NUMERIC` datatype with the precision and the scale declared
+/// on the field, or an unconstrained one if there's no `#[column(...)]` annotation
+#[cfg(feature = "postgres")]
+fn postgres_numeric(field: &CanyonRegisterEntityField) -> String {
+    match field.numeric_precision_and_scale() {
+        Some((precision, scale)) => format!("{}({precision},{scale})", postgresql_type::NUMERIC),
+        None => String::from(postgresql_type::NUMERIC),
+    }
+}
+
+/// Return the Microsoft SQL Server `DECIMAL` datatype with the precision and the scale
+/// declared on the field
+#[cfg(feature = "mssql")]
+fn sqlserver_decimal(field: &CanyonRegisterEntityField) -> String {
+    let (precision, scale) = field
+        .numeric_precision_and_scale()
+        .unwrap_or(sqlserver_type::DEFAULT_DECIMAL_PRECISION_AND_SCALE);
+    format!("{}({precision},{scale})", sqlserver_type::DECIMAL)
+}
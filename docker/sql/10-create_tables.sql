@@ -38,10 +38,169 @@ CREATE TABLE public.team (
 	home_league			INTEGER REFERENCES league(id)
 );
 
+CREATE TABLE public.sponsor (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	name				TEXT NOT NULL,
+	league				INTEGER REFERENCES league(id),
+	deleted_at			TIMESTAMP
+);
+
+CREATE TABLE public.subscriber (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	email				TEXT NOT NULL,
+	name				TEXT NOT NULL,
+	locked				BOOLEAN NOT NULL
+);
+
+CREATE TABLE public.tournament_sponsor (
+    tournament			INTEGER NOT NULL REFERENCES tournament(id),
+	sponsor				INTEGER NOT NULL REFERENCES sponsor(id),
+	amount				BIGINT NOT NULL,
+	PRIMARY KEY (tournament, sponsor)
+);
+
+CREATE TABLE public.caster (
+    id					UUID PRIMARY KEY,
+	nickname			TEXT NOT NULL,
+	region				TEXT NOT NULL
+);
+
+CREATE TABLE public.tournament_caster (
+    tournament			INTEGER NOT NULL REFERENCES tournament(id),
+	caster				UUID NOT NULL REFERENCES caster(id),
+	PRIMARY KEY (tournament, caster)
+);
+
+CREATE TABLE public.coach (
+    "CoachID"			INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	"FullName"			TEXT NOT NULL,
+	"LeagueID"			INTEGER NOT NULL REFERENCES league(id),
+	"order"				INTEGER NOT NULL
+);
+
+CREATE TABLE public.venue (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	name				TEXT NOT NULL,
+	address_street		TEXT NOT NULL,
+	address_city		TEXT NOT NULL,
+	address_zip			TEXT,
+	billing_street		TEXT NOT NULL,
+	billing_city		TEXT NOT NULL,
+	billing_zip			TEXT
+);
+
+CREATE TABLE public.broadcast (
+    id					UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+	caster				UUID NOT NULL REFERENCES caster(id),
+	channel				TEXT NOT NULL
+);
+
+CREATE TABLE public.region (
+    code				TEXT PRIMARY KEY,
+	name				TEXT NOT NULL
+);
+
+CREATE TABLE public.league_region (
+    league				INTEGER NOT NULL REFERENCES league(id),
+	region				TEXT NOT NULL REFERENCES region(code),
+	PRIMARY KEY (league, region)
+);
+
+CREATE TABLE public.prize (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	tournament			INTEGER NOT NULL REFERENCES tournament(id),
+	amount				NUMERIC(18,4) NOT NULL,
+	tax_rate			NUMERIC(5,2)
+);
+
+CREATE TABLE public.replay (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	tournament			INTEGER NOT NULL REFERENCES tournament(id),
+	summary				JSONB NOT NULL,
+	highlights			JSONB
+);
+
+CREATE TABLE public.asset (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	league				INTEGER NOT NULL REFERENCES league(id),
+	checksum			BYTEA NOT NULL,
+	thumbnail			BYTEA
+);
+
+CREATE TABLE public.patch (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	version				TEXT NOT NULL,
+	champions			TEXT[] NOT NULL,
+	item_ids			INTEGER[]
+);
+
+CREATE TYPE public.map_side AS ENUM ('blue', 'red');
+
+CREATE TABLE public.series (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	tournament			INTEGER NOT NULL REFERENCES tournament(id),
+	status				TEXT NOT NULL,
+	format				INTEGER NOT NULL,
+	side				map_side
+);
+
+CREATE TABLE public.ticket (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	tournament			INTEGER NOT NULL REFERENCES tournament(id),
+	holder				TEXT NOT NULL,
+	price				BIGINT NOT NULL,
+	discount			BIGINT
+);
+
+CREATE TABLE public.announcement (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	title				TEXT NOT NULL,
+	created_at			TIMESTAMP NOT NULL DEFAULT (CURRENT_TIMESTAMP AT TIME ZONE 'UTC'),
+	updated_at			TIMESTAMP
+);
+
+CREATE TABLE public.standing (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	team				TEXT NOT NULL,
+	points				INTEGER NOT NULL,
+	version				INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE public.prediction (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	winner				TEXT NOT NULL,
+	confidence			INTEGER NOT NULL
+);
+
+CREATE TABLE public.roster (
+    id					INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+	name				TEXT NOT NULL,
+	captain				TEXT NOT NULL
+);
+
 -- For now, we use for out CI process the default data for postgres instances
 
 -- ALTER TABLE public.league OWNER TO triforce;
 -- ALTER TABLE public.tournament OWNER TO triforce;
 -- ALTER TABLE public.player OWNER TO triforce;
 -- ALTER TABLE public.team OWNER TO triforce;
--- ALTER TABLE public.team_player OWNER TO triforce;
\ No newline at end of file
+-- ALTER TABLE public.team_player OWNER TO triforce;
+-- ALTER TABLE public.sponsor OWNER TO triforce;
+-- ALTER TABLE public.subscriber OWNER TO triforce;
+-- ALTER TABLE public.tournament_sponsor OWNER TO triforce;
+-- ALTER TABLE public.caster OWNER TO triforce;
+-- ALTER TABLE public.tournament_caster OWNER TO triforce;
+-- ALTER TABLE public.broadcast OWNER TO triforce;
+-- ALTER TABLE public.region OWNER TO triforce;
+-- ALTER TABLE public.league_region OWNER TO triforce;
+-- ALTER TABLE public.prize OWNER TO triforce;
+-- ALTER TABLE public.replay OWNER TO triforce;
+-- ALTER TABLE public.asset OWNER TO triforce;
+-- ALTER TABLE public.patch OWNER TO triforce;
+-- ALTER TYPE public.map_side OWNER TO triforce;
+-- ALTER TABLE public.series OWNER TO triforce;
+-- ALTER TABLE public.ticket OWNER TO triforce;
+-- ALTER TABLE public.announcement OWNER TO triforce;
+-- ALTER TABLE public.standing OWNER TO triforce;
+-- ALTER TABLE public.prediction OWNER TO triforce;
+-- ALTER TABLE public.roster OWNER TO triforce;
\ No newline at end of file
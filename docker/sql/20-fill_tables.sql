@@ -274,6 +274,73 @@ INSERT INTO public.tournament OVERRIDING SYSTEM VALUE VALUES (21, 10740583733617
 INSERT INTO public.tournament OVERRIDING SYSTEM VALUE VALUES (22, 107417471555810057, 'lcl_spring_2022', '2022-02-11', '2022-04-16', 41);
 INSERT INTO public.tournament OVERRIDING SYSTEM VALUE VALUES (23, 107418086627198298, 'lcs_academy_2022_spring', '2022-01-19', '2022-05-31', 42);
 
+-- Values for sponsor table
+INSERT INTO public.sponsor OVERRIDING SYSTEM VALUE VALUES (1, 'Red Bull', 1, NULL);
+INSERT INTO public.sponsor OVERRIDING SYSTEM VALUE VALUES (2, 'Logitech', 1, NULL);
+INSERT INTO public.sponsor OVERRIDING SYSTEM VALUE VALUES (3, 'Mastercard', 33, '2022-05-01 12:00:00');
+INSERT INTO public.sponsor OVERRIDING SYSTEM VALUE VALUES (4, 'Secretlab', 33, NULL);
+
+-- Values for subscriber table
+INSERT INTO public.subscriber OVERRIDING SYSTEM VALUE VALUES (1, 'first@canyon.com', '  Padded Name  ', false);
+INSERT INTO public.subscriber OVERRIDING SYSTEM VALUE VALUES (2, 'locked@canyon.com', 'Locked Subscriber', true);
+
+-- Values for tournament_sponsor table
+INSERT INTO public.tournament_sponsor VALUES (18, 1, 1000);
+INSERT INTO public.tournament_sponsor VALUES (18, 2, 2500);
+INSERT INTO public.tournament_sponsor VALUES (19, 4, 750);
+
+-- Values for caster table
+INSERT INTO public.caster VALUES ('0190a3c2-6f1e-7a4b-9c3d-2e5f8a1b4c7d', 'Drakos', 'EUW');
+INSERT INTO public.caster VALUES ('0190a3c2-7b2f-7c5d-8e4f-3a6b9c2d5e8f', 'Vedius', 'EUW');
+
+-- Values for tournament_caster table
+INSERT INTO public.tournament_caster VALUES (18, '0190a3c2-6f1e-7a4b-9c3d-2e5f8a1b4c7d');
+INSERT INTO public.tournament_caster VALUES (18, '0190a3c2-7b2f-7c5d-8e4f-3a6b9c2d5e8f');
+
+-- Values for broadcast table
+INSERT INTO public.broadcast VALUES ('5f1c2d3e-4a5b-4c6d-8e7f-9a0b1c2d3e4f', '0190a3c2-6f1e-7a4b-9c3d-2e5f8a1b4c7d', 'lec');
+
+-- Values for region table
+INSERT INTO public.region VALUES ('EUW', 'Europe West');
+INSERT INTO public.region VALUES ('KR', 'Korea');
+
+-- Values for league_region table
+INSERT INTO public.league_region VALUES (1, 'EUW');
+INSERT INTO public.league_region VALUES (1, 'KR');
+
+-- Values for prize table
+INSERT INTO public.prize (tournament, amount, tax_rate) VALUES (18, 25000.5000, 21.00);
+INSERT INTO public.prize (tournament, amount, tax_rate) VALUES (19, 1234.5678, NULL);
+
+-- Values for replay table
+INSERT INTO public.replay (tournament, summary, highlights) VALUES (18, '{"winner": "blue", "duration": 1834, "patch": "13.10"}', '["pentakill", "baron steal"]');
+INSERT INTO public.replay (tournament, summary, highlights) VALUES (19, '{"winner": "red", "duration": 2210, "patch": "13.11"}', NULL);
+
+-- Values for asset table
+INSERT INTO public.asset (league, checksum, thumbnail) VALUES (1, '\xdeadbeef', '\x89504e47');
+INSERT INTO public.asset (league, checksum, thumbnail) VALUES (2, '\x00ff10', NULL);
+
+-- Values for patch table
+INSERT INTO public.patch (version, champions, item_ids) VALUES ('13.10', '{"Ahri", "Jinx", "Thresh"}', '{3089, 3031}');
+INSERT INTO public.patch (version, champions, item_ids) VALUES ('13.11', '{"Jinx", "Lee Sin"}', NULL);
+
+-- Values for series table
+INSERT INTO public.series (tournament, status, format, side) VALUES (18, 'finished', 5, 'blue');
+INSERT INTO public.series (tournament, status, format, side) VALUES (19, 'in_progress', 3, NULL);
+INSERT INTO public.series (tournament, status, format, side) VALUES (19, 'cancelled', 1, 'red');
+
+-- Values for ticket table
+INSERT INTO public.ticket (tournament, holder, price, discount) VALUES (18, 'faker@lolesports.com', 15000, NULL);
+INSERT INTO public.ticket (tournament, holder, price, discount) VALUES (19, 'caps@lolesports.com', 9900, 1000);
+
+-- Values for coach table
+INSERT INTO public.coach ("FullName", "LeagueID", "order") VALUES ('Mithy', 1, 1);
+INSERT INTO public.coach ("FullName", "LeagueID", "order") VALUES ('YamatoCannon', 1, 2);
+
+-- Values for venue table
+INSERT INTO public.venue (name, address_street, address_city, address_zip, billing_street, billing_city, billing_zip) VALUES ('LEC Studio', 'Rudolf-Diesel-Strasse 1', 'Berlin', '10557', 'Kurfuerstendamm 21', 'Berlin', '10719');
+INSERT INTO public.venue (name, address_street, address_city, address_zip, billing_street, billing_city, billing_zip) VALUES ('Palacio Vistalegre', 'Calle Utebo 1', 'Madrid', NULL, 'Calle Alcala 50', 'Madrid', '28014');
+
 /*We force values on an GENERATED ALWAYS AS IDENTITY column
 so we need to set the current value of each  column on pg_catalog
 */
@@ -286,4 +353,8 @@ SELECT pg_catalog.setval('public.team_id_seq', 642, true);
 
 SELECT pg_catalog.setval('public.team_player_id_seq', 3719, true);
 
-SELECT pg_catalog.setval('public.tournament_id_seq', 23, true);
\ No newline at end of file
+SELECT pg_catalog.setval('public.tournament_id_seq', 23, true);
+
+SELECT pg_catalog.setval('public.sponsor_id_seq', 4, true);
+
+SELECT pg_catalog.setval('public.subscriber_id_seq', 2, true);
\ No newline at end of file
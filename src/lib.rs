@@ -19,6 +19,10 @@ pub mod migrations {
 /// things in `Canyon-SQL`, like the `main` macro, the IT macro.
 pub use canyon_macros::main;
 
+/// The queries whose results aren't mapped into entities, but into any type that
+/// derives `CanyonMapper` or into dynamic rows
+pub use canyon_crud::crud::{query_as, query_rows};
+
 /// Public API for the `Canyon-SQL` proc-macros, and for the external ones
 pub mod macros {
     pub use canyon_crud::async_trait::*;
@@ -40,8 +44,30 @@ pub mod connection {
 pub mod crud {
     pub use canyon_crud::bounds;
     pub use canyon_crud::crud::*;
+    pub use canyon_crud::embedded;
+    pub use canyon_crud::embedded::CanyonEmbeddable;
+    pub use canyon_crud::enums;
+    pub use canyon_crud::enums::{CanyonEnum, EnumRepresentation};
+    pub use canyon_crud::errors::{MappingError, StaleEntityError};
+    pub use canyon_crud::hooks::*;
+    pub use canyon_crud::interceptors;
+    pub use canyon_crud::interceptors::{
+        register_datasource_interceptor, register_interceptor, InterceptedQuery, QueryInterceptor,
+        QueryOutcome,
+    };
     pub use canyon_crud::mapper::*;
-    pub use canyon_crud::rows::CanyonRows;
+    #[cfg(feature = "mock")]
+    pub use canyon_crud::mock;
+    #[cfg(feature = "mock")]
+    pub use canyon_crud::mock::{MockDatasource, MockError};
+    pub use canyon_crud::relations;
+    pub use canyon_crud::relations::{ManyToMany, RelatedEntity, Relation};
+    pub use canyon_crud::rows::{CanyonRows, DynamicRow};
+    pub use canyon_crud::tracked::{Trackable, Tracked};
+    pub use canyon_crud::types;
+    pub use canyon_crud::types::{CanyonType, ColumnValue, FieldValue};
+    pub use canyon_crud::validation;
+    pub use canyon_crud::validation::{FieldError, Validate, ValidationErrors};
     pub use canyon_crud::DatabaseType;
 }
 
@@ -74,3 +100,23 @@ pub mod date_time {
         DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc,
     };
 }
+
+/// Module for reexport the `uuid` crate, used for the `Uuid` fields and the
+/// primary keys generated with `#[primary_key(generated = "uuid_v4" | "uuid_v7")]`
+pub mod uuid {
+    pub use canyon_crud::uuid::Uuid;
+}
+
+/// Module for reexport the support of the fields annotated with `#[json]`, along with
+/// the `Value` type and the `json!` macro of the `serde_json` crate
+pub mod json {
+    pub use canyon_crud::json::*;
+    pub use canyon_crud::serde_json::{json, Value};
+}
+
+/// Module for reexport the `Decimal` type of the `rust_decimal` crate, used for the
+/// `NUMERIC` columns. Available with the `rust_decimal` feature
+#[cfg(feature = "rust_decimal")]
+pub mod decimal {
+    pub use canyon_crud::rust_decimal::Decimal;
+}
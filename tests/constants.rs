@@ -4,6 +4,8 @@
 pub const PSQL_DS: &str = "postgres_docker";
 #[cfg(feature = "mssql")]
 pub const SQL_SERVER_DS: &str = "sqlserver_docker";
+#[cfg(feature = "mock")]
+pub const MOCK_DS: &str = "mock_ds";
 
 #[cfg(all(feature = "postgres", feature = "migrations"))]
 pub static FETCH_PUBLIC_SCHEMA: &str =
@@ -90,6 +92,194 @@ BEGIN
         home_league		    INT REFERENCES league(id)
     );
 END;
+
+IF OBJECT_ID(N'[dbo].[sponsor]', N'U') IS NULL
+BEGIN
+    CREATE TABLE dbo.sponsor (
+        id					INT PRIMARY KEY IDENTITY,
+        name				NVARCHAR(250) NOT NULL,
+        league				INT REFERENCES league(id),
+        deleted_at			DATETIME2
+    );
+END;
+
+IF OBJECT_ID(N'[dbo].[subscriber]', N'U') IS NULL
+BEGIN
+    CREATE TABLE dbo.subscriber (
+        id					INT PRIMARY KEY IDENTITY,
+        email				NVARCHAR(250) NOT NULL,
+        name				NVARCHAR(250) NOT NULL,
+        locked				BIT NOT NULL
+    );
+END;
+
+IF OBJECT_ID(N'[dbo].[tournament_sponsor]', N'U') IS NULL
+BEGIN
+    CREATE TABLE dbo.tournament_sponsor (
+        tournament			INT NOT NULL REFERENCES tournament(id),
+        sponsor				INT NOT NULL REFERENCES sponsor(id),
+        amount				BIGINT NOT NULL,
+        PRIMARY KEY (tournament, sponsor)
+    );
+END;
+
+IF OBJECT_ID(N'[dbo].[caster]', N'U') IS NULL
+BEGIN
+    CREATE TABLE dbo.caster (
+        id					UNIQUEIDENTIFIER PRIMARY KEY,
+        nickname			NVARCHAR(250) NOT NULL,
+        region				NVARCHAR(250) NOT NULL
+    );
+END;
+
+IF OBJECT_ID(N'[dbo].[tournament_caster]', N'U') IS NULL
+BEGIN
+    CREATE TABLE dbo.tournament_caster (
+        tournament			INT NOT NULL REFERENCES tournament(id),
+        caster				UNIQUEIDENTIFIER NOT NULL REFERENCES caster(id),
+        PRIMARY KEY (tournament, caster)
+    );
+END;
+
+IF OBJECT_ID(N'[dbo].[coach]', N'U') IS NULL
+BEGIN
+    CREATE TABLE dbo.coach (
+        CoachID				INT PRIMARY KEY IDENTITY,
+        FullName			NVARCHAR(250) NOT NULL,
+        LeagueID			INT NOT NULL REFERENCES league(id),
+        [order]				INT NOT NULL
+    );
+END;
+
+IF OBJECT_ID(N'[dbo].[venue]', N'U') IS NULL
+BEGIN
+    CREATE TABLE dbo.venue (
+        id					INT PRIMARY KEY IDENTITY,
+        name				NVARCHAR(250) NOT NULL,
+        address_street		NVARCHAR(250) NOT NULL,
+        address_city		NVARCHAR(250) NOT NULL,
+        address_zip			NVARCHAR(250),
+        billing_street		NVARCHAR(250) NOT NULL,
+        billing_city		NVARCHAR(250) NOT NULL,
+        billing_zip			NVARCHAR(250)
+    );
+END;
+
+IF OBJECT_ID(N'[dbo].[broadcast]', N'U') IS NULL
+BEGIN
+    CREATE TABLE dbo.broadcast (
+        id					UNIQUEIDENTIFIER PRIMARY KEY DEFAULT NEWID(),
+        caster				UNIQUEIDENTIFIER NOT NULL REFERENCES caster(id),
+        channel				NVARCHAR(250) NOT NULL
+    );
+END;
+
+IF OBJECT_ID(N'[dbo].[region]', N'U') IS NULL
+BEGIN
+    CREATE TABLE dbo.region (
+        code				NVARCHAR(16) PRIMARY KEY,
+        name				NVARCHAR(250) NOT NULL
+    );
+END;
+
+IF OBJECT_ID(N'[dbo].[league_region]', N'U') IS NULL
+BEGIN
+    CREATE TABLE dbo.league_region (
+        league				INT NOT NULL REFERENCES league(id),
+        region				NVARCHAR(16) NOT NULL REFERENCES region(code),
+        PRIMARY KEY (league, region)
+    );
+END;
+
+IF OBJECT_ID(N'[dbo].[prize]', N'U') IS NULL
+BEGIN
+    CREATE TABLE dbo.prize (
+        id					INT PRIMARY KEY IDENTITY,
+        tournament			INT NOT NULL REFERENCES tournament(id),
+        amount				DECIMAL(18,4) NOT NULL,
+        tax_rate			DECIMAL(5,2)
+    );
+END;
+
+IF OBJECT_ID(N'[dbo].[replay]', N'U') IS NULL
+BEGIN
+    CREATE TABLE dbo.replay (
+        id					INT PRIMARY KEY IDENTITY,
+        tournament			INT NOT NULL REFERENCES tournament(id),
+        summary				NVARCHAR(MAX) NOT NULL,
+        highlights			NVARCHAR(MAX)
+    );
+END;
+
+IF OBJECT_ID(N'[dbo].[asset]', N'U') IS NULL
+BEGIN
+    CREATE TABLE dbo.asset (
+        id					INT PRIMARY KEY IDENTITY,
+        league				INT NOT NULL REFERENCES league(id),
+        checksum			VARBINARY(MAX) NOT NULL,
+        thumbnail			VARBINARY(MAX)
+    );
+END;
+
+IF OBJECT_ID(N'[dbo].[series]', N'U') IS NULL
+BEGIN
+    CREATE TABLE dbo.series (
+        id					INT PRIMARY KEY IDENTITY,
+        tournament			INT NOT NULL REFERENCES tournament(id),
+        status				NVARCHAR(MAX) NOT NULL CHECK (status IN ('scheduled', 'in_progress', 'finished', 'cancelled')),
+        format				INT NOT NULL CHECK (format IN (1, 3, 5)),
+        side				NVARCHAR(MAX) CHECK (side IN ('blue', 'red'))
+    );
+END;
+
+IF OBJECT_ID(N'[dbo].[ticket]', N'U') IS NULL
+BEGIN
+    CREATE TABLE dbo.ticket (
+        id					INT PRIMARY KEY IDENTITY,
+        tournament			INT NOT NULL REFERENCES tournament(id),
+        holder				NVARCHAR(320) NOT NULL,
+        price				BIGINT NOT NULL,
+        discount			BIGINT
+    );
+END;
+
+IF OBJECT_ID(N'[dbo].[announcement]', N'U') IS NULL
+BEGIN
+    CREATE TABLE dbo.announcement (
+        id					INT PRIMARY KEY IDENTITY,
+        title				NVARCHAR(250) NOT NULL,
+        created_at			DATETIME2 NOT NULL DEFAULT SYSUTCDATETIME(),
+        updated_at			DATETIME2
+    );
+END;
+
+IF OBJECT_ID(N'[dbo].[standing]', N'U') IS NULL
+BEGIN
+    CREATE TABLE dbo.standing (
+        id					INT PRIMARY KEY IDENTITY,
+        team				NVARCHAR(250) NOT NULL,
+        points				INT NOT NULL,
+        version				INT NOT NULL DEFAULT 0
+    );
+END;
+
+IF OBJECT_ID(N'[dbo].[prediction]', N'U') IS NULL
+BEGIN
+    CREATE TABLE dbo.prediction (
+        id					INT PRIMARY KEY IDENTITY,
+        winner				NVARCHAR(250) NOT NULL,
+        confidence			INT NOT NULL
+    );
+END;
+
+IF OBJECT_ID(N'[dbo].[roster]', N'U') IS NULL
+BEGIN
+    CREATE TABLE dbo.roster (
+        id					INT PRIMARY KEY IDENTITY,
+        name				NVARCHAR(250) NOT NULL,
+        captain				NVARCHAR(250) NOT NULL
+    );
+END;
 ";
 
 #[cfg(feature = "mssql")]
@@ -375,4 +565,71 @@ INSERT INTO dbo.tournament (id, ext_id, slug, start_date, end_date, league) VALU
 INSERT INTO dbo.tournament (id, ext_id, slug, start_date, end_date, league) VALUES (22, 107417471555810057, 'lcl_spring_2022', '2022-02-11', '2022-04-16', 41);
 INSERT INTO dbo.tournament (id, ext_id, slug, start_date, end_date, league) VALUES (23, 107418086627198298, 'lcs_academy_2022_spring', '2022-01-19', '2022-05-31', 42);
 SET IDENTITY_INSERT dbo.tournament OFF;
+
+-- Values for sponsor table
+SET IDENTITY_INSERT dbo.sponsor ON;
+INSERT INTO dbo.sponsor (id, name, league, deleted_at) VALUES (1, 'Red Bull', 1, NULL);
+INSERT INTO dbo.sponsor (id, name, league, deleted_at) VALUES (2, 'Logitech', 1, NULL);
+INSERT INTO dbo.sponsor (id, name, league, deleted_at) VALUES (3, 'Mastercard', 33, '2022-05-01 12:00:00');
+INSERT INTO dbo.sponsor (id, name, league, deleted_at) VALUES (4, 'Secretlab', 33, NULL);
+SET IDENTITY_INSERT dbo.sponsor OFF;
+
+-- Values for subscriber table
+SET IDENTITY_INSERT dbo.subscriber ON;
+INSERT INTO dbo.subscriber (id, email, name, locked) VALUES (1, 'first@canyon.com', '  Padded Name  ', 0);
+INSERT INTO dbo.subscriber (id, email, name, locked) VALUES (2, 'locked@canyon.com', 'Locked Subscriber', 1);
+SET IDENTITY_INSERT dbo.subscriber OFF;
+
+-- Values for tournament_sponsor table
+INSERT INTO dbo.tournament_sponsor (tournament, sponsor, amount) VALUES (18, 1, 1000);
+INSERT INTO dbo.tournament_sponsor (tournament, sponsor, amount) VALUES (18, 2, 2500);
+INSERT INTO dbo.tournament_sponsor (tournament, sponsor, amount) VALUES (19, 4, 750);
+
+-- Values for caster table
+INSERT INTO dbo.caster (id, nickname, region) VALUES ('0190a3c2-6f1e-7a4b-9c3d-2e5f8a1b4c7d', 'Drakos', 'EUW');
+INSERT INTO dbo.caster (id, nickname, region) VALUES ('0190a3c2-7b2f-7c5d-8e4f-3a6b9c2d5e8f', 'Vedius', 'EUW');
+
+-- Values for tournament_caster table
+INSERT INTO dbo.tournament_caster (tournament, caster) VALUES (18, '0190a3c2-6f1e-7a4b-9c3d-2e5f8a1b4c7d');
+INSERT INTO dbo.tournament_caster (tournament, caster) VALUES (18, '0190a3c2-7b2f-7c5d-8e4f-3a6b9c2d5e8f');
+
+-- Values for coach table
+INSERT INTO dbo.coach (FullName, LeagueID, [order]) VALUES ('Mithy', 1, 1);
+INSERT INTO dbo.coach (FullName, LeagueID, [order]) VALUES ('YamatoCannon', 1, 2);
+
+-- Values for venue table
+INSERT INTO dbo.venue (name, address_street, address_city, address_zip, billing_street, billing_city, billing_zip) VALUES ('LEC Studio', 'Rudolf-Diesel-Strasse 1', 'Berlin', '10557', 'Kurfuerstendamm 21', 'Berlin', '10719');
+INSERT INTO dbo.venue (name, address_street, address_city, address_zip, billing_street, billing_city, billing_zip) VALUES ('Palacio Vistalegre', 'Calle Utebo 1', 'Madrid', NULL, 'Calle Alcala 50', 'Madrid', '28014');
+
+-- Values for broadcast table
+INSERT INTO dbo.broadcast (id, caster, channel) VALUES ('5f1c2d3e-4a5b-4c6d-8e7f-9a0b1c2d3e4f', '0190a3c2-6f1e-7a4b-9c3d-2e5f8a1b4c7d', 'lec');
+
+-- Values for region table
+INSERT INTO dbo.region (code, name) VALUES ('EUW', 'Europe West');
+INSERT INTO dbo.region (code, name) VALUES ('KR', 'Korea');
+
+-- Values for league_region table
+INSERT INTO dbo.league_region (league, region) VALUES (1, 'EUW');
+INSERT INTO dbo.league_region (league, region) VALUES (1, 'KR');
+
+-- Values for prize table
+INSERT INTO dbo.prize (tournament, amount, tax_rate) VALUES (18, 25000.5000, 21.00);
+INSERT INTO dbo.prize (tournament, amount, tax_rate) VALUES (19, 1234.5678, NULL);
+
+-- Values for replay table
+INSERT INTO dbo.replay (tournament, summary, highlights) VALUES (18, '{\"winner\": \"blue\", \"duration\": 1834, \"patch\": \"13.10\"}', '[\"pentakill\", \"baron steal\"]');
+INSERT INTO dbo.replay (tournament, summary, highlights) VALUES (19, '{\"winner\": \"red\", \"duration\": 2210, \"patch\": \"13.11\"}', NULL);
+
+-- Values for asset table
+INSERT INTO dbo.asset (league, checksum, thumbnail) VALUES (1, 0xDEADBEEF, 0x89504E47);
+INSERT INTO dbo.asset (league, checksum, thumbnail) VALUES (2, 0x00FF10, NULL);
+
+-- Values for series table
+INSERT INTO dbo.series (tournament, status, format, side) VALUES (18, 'finished', 5, 'blue');
+INSERT INTO dbo.series (tournament, status, format, side) VALUES (19, 'in_progress', 3, NULL);
+INSERT INTO dbo.series (tournament, status, format, side) VALUES (19, 'cancelled', 1, 'red');
+
+-- Values for ticket table
+INSERT INTO dbo.ticket (tournament, holder, price, discount) VALUES (18, 'faker@lolesports.com', 15000, NULL);
+INSERT INTO dbo.ticket (tournament, holder, price, discount) VALUES (19, 'caps@lolesports.com', 9900, 1000);
 ";
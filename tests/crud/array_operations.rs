@@ -0,0 +1,105 @@
+//! Integration tests for the CRUD operations and the QueryBuilder operators available
+//! in `Canyon` over the entities with PostgreSQL array columns, mapped to the `Vec<T>` type
+use canyon_sql::{
+    crud::CrudOperations,
+    query::{operators::Array, ops::QueryBuilder},
+};
+
+#[cfg(feature = "mssql")]
+use crate::constants::SQL_SERVER_DS;
+use crate::tests_models::patch::*;
+
+/// The arrays are retrieved with their elements in order, and the `NULL` values
+/// into the `None` variant of the optional fields
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_array_values() {
+    let patch = Patch::find_by_pk(&1)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No entity found for the primary key value passed in");
+    assert_eq!(patch.champions, vec!["Ahri", "Jinx", "Thresh"]);
+    assert_eq!(patch.item_ids, Some(vec![3089, 3031]));
+
+    let patch_without_items = Patch::find_by_pk(&2).await.unwrap().unwrap();
+    assert_eq!(patch_without_items.item_ids, None);
+}
+
+/// The `Vec<T>` values are sent as query parameters on the insert and update operations
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_update_array_values() {
+    let mut patch = Patch {
+        id: Default::default(),
+        version: String::from("13.12"),
+        champions: vec![String::from("Naafiri")],
+        item_ids: None,
+    };
+    patch.insert().await.expect("Failed insert operation");
+
+    patch.champions.push(String::from("K'Sante"));
+    patch.item_ids = Some(Vec::new());
+    patch.update().await.expect("Failed update operation");
+
+    let stored_patch = Patch::find_by_pk(&patch.id).await.unwrap().unwrap();
+    assert_eq!(stored_patch, patch);
+
+    patch.delete().await.expect("Failed delete operation");
+}
+
+/// SQL Server has no array columns, so the operations that bind a `Vec<T>` value
+/// against a SQL Server datasource fail with an error, without reaching the database
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_array_values_datasource_fails() {
+    let mut patch = Patch {
+        id: Default::default(),
+        version: String::from("13.13"),
+        champions: vec![String::from("Briar")],
+        item_ids: None,
+    };
+    let error = patch
+        .insert_datasource(SQL_SERVER_DS)
+        .await
+        .expect_err("Array values can't be bound on SQL Server");
+    assert!(error
+        .to_string()
+        .contains("The array columns are only supported on PostgreSQL"));
+}
+
+/// Filters the entities with the `ANY`, `@>` and `&&` array operators
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_with_array_operators() {
+    let versions = vec![String::from("13.10"), String::from("13.09")];
+    let mut by_version = Patch::select_query();
+    by_version.r#where(PatchFieldValue::version(&versions), Array::Any);
+    assert_eq!(
+        by_version.read_sql(),
+        "SELECT * FROM patch WHERE version = ANY($1)"
+    );
+
+    let patches: Vec<Patch> = Patch::select_query()
+        .r#where(PatchFieldValue::version(&versions), Array::Any)
+        .query()
+        .await
+        .unwrap();
+    assert!(patches.iter().any(|patch| patch.id == 1));
+    assert!(patches
+        .iter()
+        .all(|patch| versions.contains(&patch.version)));
+
+    let champions = vec![String::from("Jinx"), String::from("Thresh")];
+    let patches: Vec<Patch> = Patch::select_query()
+        .r#where(PatchFieldValue::champions(&champions), Array::Contains)
+        .query()
+        .await
+        .unwrap();
+    assert!(patches.iter().any(|patch| patch.id == 1));
+    assert!(patches.iter().all(|patch| patch.id != 2));
+
+    let patches: Vec<Patch> = Patch::select_query()
+        .r#where(PatchFieldValue::champions(&champions), Array::Overlaps)
+        .query()
+        .await
+        .unwrap();
+    assert!(patches.iter().any(|patch| patch.id == 1));
+    assert!(patches.iter().any(|patch| patch.id == 2));
+}
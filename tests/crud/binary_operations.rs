@@ -0,0 +1,80 @@
+//! Integration tests for the CRUD operations available in `Canyon` over the
+//! entities with binary columns, mapped to the `Vec<u8>` type
+use canyon_sql::crud::CrudOperations;
+
+#[cfg(feature = "mssql")]
+use crate::constants::SQL_SERVER_DS;
+use crate::tests_models::asset::*;
+
+/// The binary values are retrieved byte by byte, and the `NULL` values into the
+/// `None` variant of the optional fields
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_binary_values() {
+    let asset = Asset::find_by_pk(&1)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No entity found for the primary key value passed in");
+    assert_eq!(asset.checksum, vec![0xde, 0xad, 0xbe, 0xef]);
+    assert_eq!(asset.thumbnail, Some(vec![0x89, 0x50, 0x4e, 0x47]));
+
+    let asset_without_thumbnail = Asset::find_by_pk(&2).await.unwrap().unwrap();
+    assert_eq!(asset_without_thumbnail.checksum, vec![0x00, 0xff, 0x10]);
+    assert_eq!(asset_without_thumbnail.thumbnail, None);
+}
+
+/// The `Vec<u8>` values are sent as query parameters on the insert and update operations
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_update_binary_values() {
+    let mut asset = Asset {
+        id: Default::default(),
+        league: 1,
+        checksum: vec![0x01, 0x02, 0x03],
+        thumbnail: None,
+    };
+    asset.insert().await.expect("Failed insert operation");
+
+    asset.checksum.push(0x00);
+    asset.thumbnail = Some(Vec::new());
+    asset.update().await.expect("Failed update operation");
+
+    let stored_asset = Asset::find_by_pk(&asset.id).await.unwrap().unwrap();
+    assert_eq!(stored_asset, asset);
+
+    asset.delete().await.expect("Failed delete operation");
+}
+
+/// Same as the insert and update test above, but targeting the database
+/// defined in the specified datasource
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_binary_values_datasource() {
+    let mut asset = Asset {
+        id: Default::default(),
+        league: 2,
+        checksum: vec![0xca, 0xfe],
+        thumbnail: Some(vec![0xff; 16]),
+    };
+    asset
+        .insert_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed insert operation");
+
+    asset.thumbnail = None;
+    asset
+        .update_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed update operation");
+
+    let stored_asset = Asset::find_by_pk_datasource(&asset.id, SQL_SERVER_DS)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored_asset, asset);
+
+    asset
+        .delete_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed delete operation");
+}
@@ -0,0 +1,189 @@
+//! Integration tests for the CRUD operations available in `Canyon` over the
+//! entities whose fields are mapped to columns with a different name, and the
+//! ones with fields that aren't persisted
+use canyon_sql::{
+    crud::CrudOperations,
+    query::{operators::Comp, ops::QueryBuilder},
+};
+
+#[cfg(feature = "mssql")]
+use crate::constants::SQL_SERVER_DS;
+use crate::tests_models::coach::*;
+use crate::tests_models::league::*;
+
+/// The values of the fields are read from the columns declared with
+/// `#[column(name = "...")]`, and the `#[transient]` fields are filled with
+/// their default value
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_with_renamed_columns() {
+    let coach = Coach::find_by_pk(&1)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No coach found for the given primary key");
+
+    assert_eq!(coach.id, 1);
+    assert_eq!(coach.name, "Mithy");
+    assert_eq!(coach.league, 1);
+    assert_eq!(coach.position, 1);
+    assert_eq!(coach.display_name, String::default());
+    assert!(!coach.loaded);
+
+    let coaches = Coach::find_by_pks(&[&2, &1], true)
+        .await
+        .expect("Failed the query to the database");
+    assert_eq!(
+        coaches.iter().map(|coach| coach.id).collect::<Vec<i32>>(),
+        vec![2, 1]
+    );
+}
+
+/// The statements of the `insert`, `update` and `delete` operations write the
+/// renamed columns, and they never include the `#[transient]` fields
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_update_delete_with_renamed_columns() {
+    let mut coach = Coach {
+        id: Default::default(),
+        name: "Grabbz".to_string(),
+        league: 2,
+        position: 3,
+        display_name: "Grabbz (TAL)".to_string(),
+        loaded: true,
+    };
+    coach.insert().await.expect("Failed insert operation");
+    assert!(coach.id > 2);
+
+    let mut stored_coach = Coach::find_by_pk(&coach.id)
+        .await
+        .expect("Failed the query to the database")
+        .expect("The coach must be stored with the returned key");
+    assert_eq!(stored_coach.name, coach.name);
+    assert_eq!(stored_coach.position, 3);
+    assert_eq!(stored_coach.display_name, String::default());
+
+    stored_coach.name = "Dylan Falco".to_string();
+    stored_coach.position = 4;
+    stored_coach
+        .update()
+        .await
+        .expect("Failed update operation");
+
+    let updated_coach = Coach::find_by_pk(&coach.id)
+        .await
+        .expect("Failed the query to the database")
+        .expect("The updated coach must exist");
+    assert_eq!(updated_coach.name, "Dylan Falco");
+    assert_eq!(updated_coach.position, 4);
+
+    updated_coach
+        .delete()
+        .await
+        .expect("Failed delete operation");
+    assert_eq!(
+        Coach::find_by_pk(&coach.id)
+            .await
+            .expect("Failed the query to the database"),
+        None
+    );
+}
+
+/// The `Fields` and `FieldValue` enums of the entities refers to the renamed
+/// columns, quote escaped, so they're matched verbatim by the query builders
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_querybuilder_with_renamed_columns() {
+    let mut select = Coach::select_query();
+    select
+        .r#where(CoachFieldValue::league(&1), Comp::Eq)
+        .order_by(CoachField::position, true);
+    assert_eq!(
+        select.read_sql(),
+        "SELECT * FROM coach WHERE \"LeagueID\" = $1 ORDER BY \"order\" DESC "
+    );
+
+    let coaches: Vec<Coach> = Coach::select_query()
+        .r#where(CoachFieldValue::league(&1), Comp::Eq)
+        .order_by(CoachField::position, true)
+        .query()
+        .await
+        .expect("Failed the query");
+    assert_eq!(coaches.len(), 2);
+    assert_eq!(coaches[0].name, "YamatoCannon");
+    assert_eq!(coaches[1].name, "Mithy");
+}
+
+/// The searches by the foreign key relations use the renamed column of the child
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_search_by_foreign_key_with_renamed_columns() {
+    let coach = Coach::find_by_pk(&1)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No coach found for the given primary key");
+    let league: League = coach
+        .search_league()
+        .await
+        .expect("Failed the query to the database")
+        .expect("The coach must belong to a league");
+    assert_eq!(league.id, 1);
+
+    let coaches = Coach::search_league_childrens(&league)
+        .await
+        .expect("Failed the query to the database");
+    assert!(coaches.iter().all(|coach| coach.league == league.id));
+    assert!(coaches.iter().any(|coach| coach.name == "Mithy"));
+}
+
+/// Same as the above tests, but with the specified datasource
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_operations_with_renamed_columns_datasource() {
+    let mut coach = Coach {
+        id: Default::default(),
+        name: "Grabbz".to_string(),
+        league: 2,
+        position: 3,
+        display_name: "Grabbz (TAL)".to_string(),
+        loaded: true,
+    };
+    coach
+        .insert_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed insert operation");
+    assert!(coach.id > 2);
+
+    let mut stored_coach = Coach::find_by_pk_datasource(&coach.id, SQL_SERVER_DS)
+        .await
+        .expect("Failed the query to the database")
+        .expect("The coach must be stored with the returned key");
+    assert_eq!(stored_coach.name, "Grabbz");
+    assert_eq!(stored_coach.display_name, String::default());
+    assert!(!stored_coach.loaded);
+
+    stored_coach.position = 4;
+    stored_coach
+        .update_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed update operation");
+
+    let coaches: Vec<Coach> = Coach::select_query_datasource(SQL_SERVER_DS)
+        .r#where(CoachFieldValue::position(&4), Comp::Eq)
+        .query()
+        .await
+        .expect("Failed the query");
+    assert_eq!(coaches.len(), 1);
+    assert_eq!(coaches[0].name, "Grabbz");
+
+    let league = coaches[0]
+        .search_league_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed the query to the database")
+        .expect("The coach must belong to a league");
+    assert_eq!(league.id, 2);
+
+    coaches[0]
+        .delete_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed delete operation");
+}
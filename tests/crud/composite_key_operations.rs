@@ -0,0 +1,171 @@
+//! Integration tests for the CRUD operations available in `Canyon` over
+//! the entities that declares a composite primary key
+use canyon_sql::{crud::CrudOperations, uuid::Uuid};
+
+#[cfg(feature = "mssql")]
+use crate::constants::SQL_SERVER_DS;
+use crate::tests_models::tournament_caster::*;
+use crate::tests_models::tournament_sponsor::*;
+
+/// The search by primary key receives a tuple with the values of every
+/// column of the key, in the order that they are declared
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_by_composite_pk() {
+    let tournament_sponsor = TournamentSponsor::find_by_pk(&(18, 2))
+        .await
+        .expect("Failed the query to the database")
+        .expect("No entity found for the primary key value passed in");
+    assert_eq!(tournament_sponsor.amount, 2500);
+
+    assert!(TournamentSponsor::find_by_pk(&(19, 1))
+        .await
+        .unwrap()
+        .is_none());
+    assert!(TournamentSponsor::exists_by_pk(&(19, 4)).await.unwrap());
+    assert!(!TournamentSponsor::exists_by_pk(&(4, 19)).await.unwrap());
+
+    // Every column of the key must receive a value
+    assert!(TournamentSponsor::find_by_pk(&18).await.is_err());
+}
+
+/// The rows are matched by every column of their composite keys
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_by_composite_pks() {
+    let tournament_sponsors = TournamentSponsor::find_by_pks(&[&(19, 4), &(18, 1), &(18, 4)], true)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        tournament_sponsors
+            .iter()
+            .map(|ts| (ts.tournament, ts.sponsor))
+            .collect::<Vec<(i32, i32)>>(),
+        vec![(19, 4), (18, 1)]
+    );
+}
+
+/// The values of the composite key are inserted along with the rest of the
+/// columns, and used to update and delete the row afterwards
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_update_delete_with_composite_pk() {
+    let mut tournament_sponsor = TournamentSponsor {
+        tournament: 20,
+        sponsor: 2,
+        amount: 300,
+    };
+    tournament_sponsor
+        .insert()
+        .await
+        .expect("Failed insert operation");
+    assert_eq!(
+        (tournament_sponsor.tournament, tournament_sponsor.sponsor),
+        (20, 2)
+    );
+
+    tournament_sponsor.amount = 450;
+    tournament_sponsor
+        .update()
+        .await
+        .expect("Failed update operation");
+    let updated = TournamentSponsor::find_by_pk(&(20, 2))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.amount, 450);
+
+    // The rows that shares just one of the key columns aren't affected
+    assert_eq!(
+        TournamentSponsor::find_by_pk(&(18, 2))
+            .await
+            .unwrap()
+            .unwrap()
+            .amount,
+        2500
+    );
+
+    tournament_sponsor
+        .delete()
+        .await
+        .expect("Failed delete operation");
+    assert!(!TournamentSponsor::exists_by_pk(&(20, 2)).await.unwrap());
+    assert!(TournamentSponsor::exists_by_pk(&(18, 2)).await.unwrap());
+}
+
+/// The entities whose columns are all part of the primary key have nothing to
+/// update, so their `update` succeeds without sending any statement
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_update_with_only_primary_key_columns() {
+    let caster = Uuid::parse_str("0190a3c2-7b2f-7c5d-8e4f-3a6b9c2d5e8f").unwrap();
+    let mut tournament_caster = TournamentCaster {
+        tournament: 19,
+        caster,
+    };
+    tournament_caster
+        .insert()
+        .await
+        .expect("Failed insert operation");
+
+    tournament_caster
+        .update()
+        .await
+        .expect("Failed update operation");
+    assert_eq!(
+        TournamentCaster::find_by_pk(&(19, caster)).await.unwrap(),
+        Some(tournament_caster.clone())
+    );
+
+    tournament_caster
+        .delete()
+        .await
+        .expect("Failed delete operation");
+    assert!(!TournamentCaster::exists_by_pk(&(19, caster)).await.unwrap());
+    assert!(TournamentCaster::exists_by_pk(&(18, caster)).await.unwrap());
+}
+
+/// Same as the insert, update and delete test above, but targeting the database
+/// defined in the specified datasource
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_composite_pk_datasource() {
+    let mut tournament_sponsor = TournamentSponsor {
+        tournament: 21,
+        sponsor: 4,
+        amount: 100,
+    };
+    tournament_sponsor
+        .insert_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed insert datasource operation");
+
+    tournament_sponsor.amount = 150;
+    tournament_sponsor
+        .update_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed update datasource operation");
+    let updated = TournamentSponsor::find_by_pk_datasource(&(21, 4), SQL_SERVER_DS)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.amount, 150);
+
+    let tournament_sponsors =
+        TournamentSponsor::find_by_pks_datasource(&[&(21, 4), &(18, 1)], true, SQL_SERVER_DS)
+            .await
+            .unwrap();
+    assert_eq!(tournament_sponsors.len(), 2);
+    assert_eq!(tournament_sponsors[0].amount, 150);
+
+    tournament_sponsor
+        .delete_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed delete datasource operation");
+    assert!(
+        !TournamentSponsor::exists_by_pk_datasource(&(21, 4), SQL_SERVER_DS)
+            .await
+            .unwrap()
+    );
+}
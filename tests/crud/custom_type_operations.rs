@@ -0,0 +1,121 @@
+//! Integration tests for the CRUD operations and the QueryBuilder filters available
+//! in `Canyon` over the entities with fields whose types are user-defined types, that
+//! implements `CanyonType`
+use canyon_sql::{
+    crud::{CanyonType, CrudOperations},
+    query::{operators::Comp, ops::QueryBuilder},
+};
+
+#[cfg(feature = "mssql")]
+use crate::constants::SQL_SERVER_DS;
+use crate::tests_models::ticket::*;
+
+/// The values read from the columns are checked by the implementation of `CanyonType`
+#[test]
+fn test_canyon_type_from_value() {
+    assert_eq!(
+        Email::from_value("faker@lolesports.com".to_string()).unwrap(),
+        Email("faker@lolesports.com".to_string())
+    );
+    assert!(Email::from_value("faker".to_string()).is_err());
+    assert_eq!(Cents::from_value(9900).unwrap(), Cents(9900));
+}
+
+/// The values stored in the database are converted into the user-defined types,
+/// and the `NULL` values into the `None` variant of the optional ones
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_custom_type_values() {
+    let ticket = Ticket::find_by_pk(&1)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No entity found for the primary key value passed in");
+    assert_eq!(ticket.holder, Email("faker@lolesports.com".to_string()));
+    assert_eq!(ticket.price, Cents(15000));
+    assert_eq!(ticket.discount, None);
+
+    let discounted_ticket = Ticket::find_by_pk(&2).await.unwrap().unwrap();
+    assert_eq!(discounted_ticket.discount, Some(Cents(1000)));
+}
+
+/// The user-defined types are sent as the parameters returned by their `to_parameter`
+/// on the insert and update operations
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_update_custom_type_values() {
+    let mut ticket = Ticket {
+        id: Default::default(),
+        tournament: 18,
+        holder: Email("chovy@lolesports.com".to_string()),
+        price: Cents(12000),
+        discount: Some(Cents(2000)),
+    };
+    ticket.insert().await.expect("Failed insert operation");
+
+    ticket.price = Cents(12500);
+    ticket.discount = None;
+    ticket.update().await.expect("Failed update operation");
+
+    let stored_ticket = Ticket::find_by_pk(&ticket.id).await.unwrap().unwrap();
+    assert_eq!(stored_ticket, ticket);
+
+    ticket.delete().await.expect("Failed delete operation");
+}
+
+/// The user-defined types can be used as the values of the filters of the QueryBuilder
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_with_custom_type_filters() {
+    let holder = Email("caps@lolesports.com".to_string());
+    let tickets: Vec<Ticket> = Ticket::select_query()
+        .r#where(TicketFieldValue::holder(&holder), Comp::Eq)
+        .query()
+        .await
+        .unwrap();
+    assert_eq!(tickets.len(), 1);
+    assert_eq!(tickets[0].id, 2);
+
+    let tickets: Vec<Ticket> = Ticket::select_query()
+        .r#where(TicketFieldValue::price(&Cents(10000)), Comp::Gt)
+        .query()
+        .await
+        .unwrap();
+    assert!(tickets.iter().all(|ticket| ticket.price.0 > 10000));
+    assert!(tickets.iter().any(|ticket| ticket.id == 1));
+}
+
+/// Same as the insert, update and filter tests above, but targeting the database
+/// defined in the specified datasource
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_custom_type_values_datasource() {
+    let mut ticket = Ticket {
+        id: Default::default(),
+        tournament: 19,
+        holder: Email("perkz@lolesports.com".to_string()),
+        price: Cents(8000),
+        discount: None,
+    };
+    ticket
+        .insert_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed insert operation");
+
+    ticket.discount = Some(Cents(500));
+    ticket
+        .update_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed update operation");
+
+    let tickets: Vec<Ticket> = Ticket::select_query_datasource(SQL_SERVER_DS)
+        .r#where(TicketFieldValue::holder(&ticket.holder), Comp::Eq)
+        .query()
+        .await
+        .unwrap();
+    assert_eq!(tickets, vec![ticket.clone()]);
+
+    ticket
+        .delete_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed delete operation");
+}
@@ -0,0 +1,71 @@
+//! Integration tests for the CRUD operations available in `Canyon` over the
+//! entities with `NUMERIC` columns, mapped to the `Decimal` type of the
+//! `rust_decimal` feature
+use canyon_sql::{crud::CrudOperations, decimal::Decimal};
+
+#[cfg(feature = "mssql")]
+use crate::constants::SQL_SERVER_DS;
+use crate::tests_models::prize::*;
+
+/// The `NUMERIC` values are retrieved without losing any of the digits of their scale
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_decimal_values() {
+    let prize = Prize::find_by_pk(&1)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No entity found for the primary key value passed in");
+    assert_eq!(prize.amount, Decimal::new(250005000, 4));
+    assert_eq!(prize.tax_rate, Some(Decimal::new(2100, 2)));
+
+    let prize_without_taxes = Prize::find_by_pk(&2).await.unwrap().unwrap();
+    assert_eq!(prize_without_taxes.amount, Decimal::new(12345678, 4));
+    assert_eq!(prize_without_taxes.tax_rate, None);
+}
+
+/// The `Decimal` values are sent as query parameters on the insert and update operations
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_update_decimal_values() {
+    let mut prize = Prize {
+        id: Default::default(),
+        tournament: 18,
+        amount: Decimal::new(99999, 2),
+        tax_rate: None,
+    };
+    prize.insert().await.expect("Failed insert operation");
+
+    prize.amount += Decimal::new(1, 4);
+    prize.tax_rate = Some(Decimal::new(1050, 2));
+    prize.update().await.expect("Failed update operation");
+
+    let stored_prize = Prize::find_by_pk(&prize.id).await.unwrap().unwrap();
+    assert_eq!(stored_prize.amount, Decimal::new(9999901, 4));
+    assert_eq!(stored_prize.tax_rate, Some(Decimal::new(1050, 2)));
+
+    prize.delete().await.expect("Failed delete operation");
+}
+
+/// Same as the insert and update test above, but targeting the database
+/// defined in the specified datasource
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_decimal_values_datasource() {
+    let mut prize = Prize {
+        id: Default::default(),
+        tournament: 19,
+        amount: Decimal::new(5000025, 4),
+        tax_rate: Some(Decimal::new(750, 2)),
+    };
+    prize
+        .insert_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed insert datasource operation");
+
+    let stored_prize = Prize::find_by_pk_datasource(&prize.id, SQL_SERVER_DS)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored_prize.amount, Decimal::new(5000025, 4));
+    assert_eq!(stored_prize.tax_rate, Some(Decimal::new(750, 2)));
+}
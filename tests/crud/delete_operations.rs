@@ -1,12 +1,13 @@
 ///! Integration tests for the CRUD operations available in `Canyon` that
 ///! generates and executes *INSERT* statements
-use canyon_sql::crud::CrudOperations;
+use canyon_sql::crud::{CrudOperations, StaleEntityError};
 
 #[cfg(feature = "postgres")]
 use crate::constants::PSQL_DS;
 #[cfg(feature = "mssql")]
 use crate::constants::SQL_SERVER_DS;
 use crate::tests_models::league::*;
+use crate::tests_models::standing::*;
 
 /// Deletes a row from the database that is mapped into some instance of a `T` entity.
 ///
@@ -107,3 +108,40 @@ fn test_crud_delete_datasource_method_operation() {
         None
     );
 }
+
+/// The entities with a field annotated with `#[version]` are only deleted if the row
+/// wasn't modified since the instance was loaded. Otherwise, the delete is rejected
+/// with a `StaleEntityError`
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_delete_with_a_stale_version() {
+    let mut standing = Standing {
+        id: Default::default(),
+        team: "Some New Team".to_string(),
+        points: 0,
+        version: 0,
+    };
+    standing.insert().await.expect("Failed insert operation");
+
+    let mut updated_standing = standing.clone();
+    updated_standing.points = 3;
+    updated_standing
+        .update()
+        .await
+        .expect("Failed the update operation");
+
+    let error = standing
+        .delete()
+        .await
+        .expect_err("The delete of a stale entity must fail");
+    assert!(error.downcast_ref::<StaleEntityError>().is_some());
+    assert!(Standing::exists_by_pk(&standing.id).await.unwrap());
+
+    // Once reloaded, the entity is up to date and can be deleted
+    let reloaded_standing = Standing::find_by_pk(&standing.id).await.unwrap().unwrap();
+    reloaded_standing
+        .delete()
+        .await
+        .expect("Failed delete operation");
+    assert!(!Standing::exists_by_pk(&standing.id).await.unwrap());
+}
@@ -0,0 +1,213 @@
+//! Integration tests for the CRUD operations available in `Canyon` over the
+//! entities with value objects embedded in their tables
+use canyon_sql::{
+    crud::{CrudOperations, Tracked},
+    query::{operators::Comp, ops::QueryBuilder},
+};
+
+#[cfg(feature = "mssql")]
+use crate::constants::SQL_SERVER_DS;
+use crate::tests_models::venue::*;
+
+fn new_venue() -> Venue {
+    Venue {
+        id: Default::default(),
+        name: "Riot Games Arena".to_string(),
+        address: Address {
+            street: "Hauptstrasse 5".to_string(),
+            city: "Berlin".to_string(),
+            postal_code: None,
+        },
+        billing: Address {
+            street: "Gran Via 1".to_string(),
+            city: "Barcelona".to_string(),
+            postal_code: Some("08001".to_string()),
+        },
+    }
+}
+
+/// The embedded value objects are built from their prefixed columns
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_with_embedded_fields() {
+    let venue = Venue::find_by_pk(&1)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No venue found for the given primary key");
+
+    assert_eq!(venue.name, "LEC Studio");
+    assert_eq!(venue.address.street, "Rudolf-Diesel-Strasse 1");
+    assert_eq!(venue.address.city, "Berlin");
+    assert_eq!(venue.address.postal_code, Some("10557".to_string()));
+    assert_eq!(venue.billing.street, "Kurfuerstendamm 21");
+    assert_eq!(venue.billing.postal_code, Some("10719".to_string()));
+
+    let venue = Venue::find_by_pk(&2)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No venue found for the given primary key");
+    assert_eq!(venue.address.postal_code, None);
+}
+
+/// The `insert`, `update` and `delete` operations write every prefixed column of
+/// the embedded value objects
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_update_delete_with_embedded_fields() {
+    let mut venue = new_venue();
+    venue.insert().await.expect("Failed insert operation");
+    assert!(venue.id > 2);
+
+    let mut stored_venue = Venue::find_by_pk(&venue.id)
+        .await
+        .expect("Failed the query to the database")
+        .expect("The venue must be stored with the returned key");
+    assert_eq!(stored_venue, venue);
+
+    stored_venue.address.postal_code = Some("10115".to_string());
+    stored_venue.billing.city = "Girona".to_string();
+    stored_venue
+        .update()
+        .await
+        .expect("Failed update operation");
+
+    let updated_venue = Venue::find_by_pk(&venue.id)
+        .await
+        .expect("Failed the query to the database")
+        .expect("The updated venue must exist");
+    assert_eq!(updated_venue, stored_venue);
+
+    updated_venue
+        .delete()
+        .await
+        .expect("Failed delete operation");
+    assert_eq!(
+        Venue::find_by_pk(&venue.id)
+            .await
+            .expect("Failed the query to the database"),
+        None
+    );
+}
+
+/// The multi insert writes the embedded value objects of every instance
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_multi_insert_with_embedded_fields() {
+    let mut venue = new_venue();
+    let mut other_venue = new_venue();
+    other_venue.name = "Caja Magica".to_string();
+    other_venue.billing.city = "Madrid".to_string();
+
+    Venue::multi_insert(&mut [&mut venue, &mut other_venue])
+        .await
+        .expect("Failed multi insert operation");
+
+    for inserted in [&venue, &other_venue] {
+        let stored_venue = Venue::find_by_pk(&inserted.id)
+            .await
+            .expect("Failed the query to the database")
+            .expect("The venue must be stored with the returned key");
+        assert_eq!(&stored_venue, inserted);
+        stored_venue
+            .delete()
+            .await
+            .expect("Failed delete operation");
+    }
+}
+
+/// The fields of the embedded value objects are available on the `Field` and
+/// `FieldValue` enums of the entities through the variants of the embedded fields,
+/// referring to their prefixed columns
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_querybuilder_with_embedded_fields() {
+    let mut select = Venue::select_query();
+    select
+        .r#where(
+            VenueFieldValue::billing(AddressFieldValue::city(&"Madrid")),
+            Comp::Eq,
+        )
+        .order_by(VenueField::address(AddressField::postal_code), false);
+    assert_eq!(
+        select.read_sql(),
+        "SELECT * FROM venue WHERE billing_city = $1 ORDER BY address_zip"
+    );
+
+    let venues: Vec<Venue> = Venue::select_query()
+        .r#where(
+            VenueFieldValue::billing(AddressFieldValue::city(&"Madrid")),
+            Comp::Eq,
+        )
+        .query()
+        .await
+        .expect("Failed the query");
+    assert_eq!(venues.len(), 1);
+    assert_eq!(venues[0].name, "Palacio Vistalegre");
+}
+
+/// The embedded value objects are tracked as a whole, so all of their columns are
+/// set when any of their fields is modified
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_tracked_update_with_embedded_fields() {
+    let mut venue = new_venue();
+    venue.insert().await.expect("Failed insert operation");
+
+    let mut tracked = Tracked::new(venue.clone());
+    tracked.billing.street = "Rambla 10".to_string();
+    assert_eq!(tracked.changed_fields(), vec!["billing"]);
+    tracked.update().await.expect("Failed the tracked update");
+
+    let updated_venue = Venue::find_by_pk(&venue.id)
+        .await
+        .expect("Failed the query to the database")
+        .expect("The updated venue must exist");
+    assert_eq!(updated_venue.billing.street, "Rambla 10");
+    assert_eq!(updated_venue.billing.city, "Barcelona");
+    assert_eq!(updated_venue.address, venue.address);
+
+    updated_venue
+        .delete()
+        .await
+        .expect("Failed delete operation");
+}
+
+/// Same as the above tests, but with the specified datasource
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_operations_with_embedded_fields_datasource() {
+    let mut venue = new_venue();
+    venue
+        .insert_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed insert operation");
+    assert!(venue.id > 2);
+
+    let mut stored_venue = Venue::find_by_pk_datasource(&venue.id, SQL_SERVER_DS)
+        .await
+        .expect("Failed the query to the database")
+        .expect("The venue must be stored with the returned key");
+    assert_eq!(stored_venue, venue);
+
+    stored_venue.billing.city = "Girona".to_string();
+    stored_venue
+        .update_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed update operation");
+
+    let venues: Vec<Venue> = Venue::select_query_datasource(SQL_SERVER_DS)
+        .r#where(
+            VenueFieldValue::billing(AddressFieldValue::city(&"Girona")),
+            Comp::Eq,
+        )
+        .query()
+        .await
+        .expect("Failed the query");
+    assert_eq!(venues.len(), 1);
+    assert_eq!(venues[0], stored_venue);
+
+    venues[0]
+        .delete_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed delete operation");
+}
@@ -0,0 +1,139 @@
+//! Integration tests for the CRUD operations and the QueryBuilder filters available
+//! in `Canyon` over the entities with fields whose types are Rust enums, that derives
+//! `CanyonEnum`
+use canyon_sql::{
+    crud::{CanyonEnum, CrudOperations},
+    query::{operators::Comp, ops::QueryBuilder},
+};
+
+#[cfg(feature = "mssql")]
+use crate::constants::SQL_SERVER_DS;
+use crate::tests_models::series::*;
+
+/// The variants are stored as their renamed labels, their discriminants or the
+/// labels of the native enum type, depending on the `#[canyon_enum(...)]` attribute
+#[test]
+fn test_canyon_enum_representations() {
+    assert_eq!(SeriesStatus::InProgress.label(), "in_progress");
+    assert_eq!(SeriesStatus::Canceled.label(), "cancelled");
+    assert_eq!(
+        SeriesStatus::from_label("finished"),
+        Some(SeriesStatus::Finished)
+    );
+    assert_eq!(SeriesStatus::from_label("Finished"), None);
+
+    assert_eq!(SeriesFormat::Bo5.discriminant(), 5);
+    assert_eq!(SeriesFormat::from_discriminant(3), Some(SeriesFormat::Bo3));
+    assert_eq!(SeriesFormat::from_discriminant(2), None);
+
+    assert_eq!(
+        MapSide::REPRESENTATION,
+        canyon_sql::crud::EnumRepresentation::Native("map_side")
+    );
+    assert_eq!(MapSide::Blue.label(), "blue");
+}
+
+/// The values stored in the database are converted into the variants of the enums,
+/// and the `NULL` values into the `None` variant of the optional ones
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_enum_values() {
+    let series = Series::find_by_pk(&1)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No entity found for the primary key value passed in");
+    assert_eq!(series.status, SeriesStatus::Finished);
+    assert_eq!(series.format, SeriesFormat::Bo5);
+    assert_eq!(series.side, Some(MapSide::Blue));
+
+    let series_without_side = Series::find_by_pk(&2).await.unwrap().unwrap();
+    assert_eq!(series_without_side.status, SeriesStatus::InProgress);
+    assert_eq!(series_without_side.side, None);
+}
+
+/// The variants are sent as the values of their representation on the insert and
+/// update operations
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_update_enum_values() {
+    let mut series = Series {
+        id: Default::default(),
+        tournament: 18,
+        status: SeriesStatus::Scheduled,
+        format: SeriesFormat::Bo3,
+        side: None,
+    };
+    series.insert().await.expect("Failed insert operation");
+
+    series.status = SeriesStatus::InProgress;
+    series.side = Some(MapSide::Red);
+    series.update().await.expect("Failed update operation");
+
+    let stored_series = Series::find_by_pk(&series.id).await.unwrap().unwrap();
+    assert_eq!(stored_series, series);
+
+    series.delete().await.expect("Failed delete operation");
+}
+
+/// The variants can be used as the values of the filters of the QueryBuilder
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_with_enum_filters() {
+    let series: Vec<Series> = Series::select_query()
+        .r#where(SeriesFieldValue::tournament(&19), Comp::Eq)
+        .and(SeriesFieldValue::status(&SeriesStatus::Canceled), Comp::Eq)
+        .query()
+        .await
+        .unwrap();
+    assert_eq!(series.len(), 1);
+    assert_eq!(series[0].id, 3);
+    assert_eq!(series[0].format, SeriesFormat::Bo1);
+
+    let series: Vec<Series> = Series::select_query()
+        .r#where(SeriesFieldValue::format(&SeriesFormat::Bo1), Comp::Gt)
+        .query()
+        .await
+        .unwrap();
+    assert!(series
+        .iter()
+        .all(|series| series.format != SeriesFormat::Bo1));
+    assert!(series.iter().any(|series| series.id == 1));
+}
+
+/// Same as the insert, update and filter tests above, but targeting the database
+/// defined in the specified datasource, where the native enums are stored as text
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_enum_values_datasource() {
+    let mut series = Series {
+        id: Default::default(),
+        tournament: 18,
+        status: SeriesStatus::Scheduled,
+        format: SeriesFormat::Bo5,
+        side: Some(MapSide::Blue),
+    };
+    series
+        .insert_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed insert operation");
+
+    series.status = SeriesStatus::Finished;
+    series.side = None;
+    series
+        .update_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed update operation");
+
+    let stored_series: Vec<Series> = Series::select_query_datasource(SQL_SERVER_DS)
+        .r#where(SeriesFieldValue::id(&series.id), Comp::Eq)
+        .and(SeriesFieldValue::status(&SeriesStatus::Finished), Comp::Eq)
+        .query()
+        .await
+        .unwrap();
+    assert_eq!(stored_series, vec![series.clone()]);
+
+    series
+        .delete_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed delete operation");
+}
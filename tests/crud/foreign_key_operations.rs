@@ -8,10 +8,15 @@
 ///! Names of the foreign key methods are autogenerated for the direct and
 ///! reverse side of the implementations.
 ///! For more info: TODO -> Link to the docs of the foreign key chapter
-use canyon_sql::crud::CrudOperations;
+use canyon_sql::{
+    crud::CrudOperations,
+    query::{operators::Comp, ops::QueryBuilder},
+};
 
 #[cfg(feature = "mssql")]
 use crate::constants::SQL_SERVER_DS;
+#[cfg(feature = "postgres")]
+use crate::tests_models::coach::*;
 use crate::tests_models::league::*;
 use crate::tests_models::tournament::*;
 
@@ -110,3 +115,93 @@ fn test_crud_search_reverse_side_foreign_key_datasource() {
         .iter()
         .for_each(|t| assert_eq!(t.league, some_league.id));
 }
+
+/// Eager loads the parents of a collection of children with just one query for
+/// all of them, pairing every child with its parent
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_eager_load_parents() {
+    let tournaments: Vec<(Tournament, Option<League>)> = Tournament::select_query()
+        .with(TournamentRelation::league)
+        .query()
+        .await
+        .expect("Result variant of the query is err");
+
+    assert!(!tournaments.is_empty());
+    tournaments.iter().for_each(|(tournament, league)| {
+        let league = league
+            .as_ref()
+            .expect("Every tournament must be paired with its league");
+        assert_eq!(tournament.league, league.id)
+    });
+}
+
+/// Eager loads the children of a collection of parents with just one query for
+/// all of them, pairing every parent with its children
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_eager_load_children() {
+    let leagues: Vec<(League, Vec<Tournament>)> = League::select_query()
+        .r#where(LeagueFieldValue::id(&1), Comp::Eq)
+        .with_children(TournamentRelation::league)
+        .query()
+        .await
+        .expect("Result variant of the query is err");
+
+    assert_eq!(leagues.len(), 1);
+    let (league, tournaments) = &leagues[0];
+    assert!(!tournaments.is_empty());
+    tournaments
+        .iter()
+        .for_each(|t| assert_eq!(t.league, league.id));
+
+    // The relations are searched by the renamed column of the child
+    let leagues: Vec<(League, Vec<Coach>)> = League::select_query()
+        .r#where(LeagueFieldValue::id(&1), Comp::Eq)
+        .with_children(CoachRelation::league)
+        .query()
+        .await
+        .expect("Result variant of the query is err");
+
+    assert_eq!(leagues.len(), 1);
+    let (league, coaches) = &leagues[0];
+    assert_eq!(coaches.len(), 2);
+    coaches
+        .iter()
+        .for_each(|coach| assert_eq!(coach.league, league.id));
+}
+
+/// Same as the eager loads of the parents and the children, but with the
+/// specified datasource
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_eager_load_relations_datasource() {
+    let tournaments: Vec<(Tournament, Option<League>)> =
+        Tournament::select_query_datasource(SQL_SERVER_DS)
+            .with(TournamentRelation::league)
+            .query()
+            .await
+            .expect("Result variant of the query is err");
+
+    assert!(!tournaments.is_empty());
+    tournaments.iter().for_each(|(tournament, league)| {
+        let league = league
+            .as_ref()
+            .expect("Every tournament must be paired with its league");
+        assert_eq!(tournament.league, league.id)
+    });
+
+    let leagues: Vec<(League, Vec<Tournament>)> = League::select_query_datasource(SQL_SERVER_DS)
+        .r#where(LeagueFieldValue::id(&1), Comp::Eq)
+        .with_children(TournamentRelation::league)
+        .query()
+        .await
+        .expect("Result variant of the query is err");
+
+    assert_eq!(leagues.len(), 1);
+    let (league, tournaments) = &leagues[0];
+    assert!(!tournaments.is_empty());
+    tournaments
+        .iter()
+        .for_each(|t| assert_eq!(t.league, league.id));
+}
@@ -0,0 +1,142 @@
+//! Integration tests for the CRUD operations available in `Canyon` over the
+//! entities whose primary keys are `Uuid` or text values, generated by Canyon,
+//! by the database or provided by the entity itself
+use canyon_sql::{crud::CrudOperations, uuid::Uuid};
+
+#[cfg(feature = "mssql")]
+use crate::constants::SQL_SERVER_DS;
+use crate::tests_models::broadcast::*;
+use crate::tests_models::caster::*;
+use crate::tests_models::region::*;
+
+/// The `Uuid` of the keys declared with `generated = "uuid_v7"` is created by Canyon
+/// before the insert, unless the entity already has one
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_client_generated_uuid() {
+    let mut caster = Caster {
+        id: Default::default(),
+        nickname: "Medic".to_string(),
+        region: "EUW".to_string(),
+    };
+    caster.insert().await.expect("Failed insert operation");
+    assert_ne!(caster.id, Uuid::nil());
+    assert_eq!(caster.id.get_version_num(), 7);
+
+    let stored_caster = Caster::find_by_pk(&caster.id)
+        .await
+        .unwrap()
+        .expect("The caster must be stored with the generated key");
+    assert_eq!(stored_caster, caster);
+
+    let id = Uuid::new_v4();
+    let mut another_caster = Caster {
+        id,
+        nickname: "Sjokz".to_string(),
+        region: "EUW".to_string(),
+    };
+    let mut new_caster = Caster {
+        id: Default::default(),
+        nickname: "Quickshot".to_string(),
+        region: "EUW".to_string(),
+    };
+    Caster::multi_insert(&mut [&mut another_caster, &mut new_caster])
+        .await
+        .expect("Failed multi insert operation");
+    assert_eq!(another_caster.id, id);
+    assert_ne!(new_caster.id, Uuid::nil());
+    assert!(Caster::exists_by_pk(&id).await.unwrap());
+}
+
+/// The keys declared with `generated = "db"` are created by the database, and
+/// read back after the insert
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_db_generated_uuid() {
+    let caster_id = Uuid::parse_str("0190a3c2-7b2f-7c5d-8e4f-3a6b9c2d5e8f").unwrap();
+    let mut broadcast = Broadcast {
+        id: Default::default(),
+        caster: caster_id,
+        channel: "lck".to_string(),
+    };
+    broadcast.insert().await.expect("Failed insert operation");
+    assert_ne!(broadcast.id, Uuid::nil());
+
+    broadcast.channel = "lck_en".to_string();
+    broadcast.update().await.expect("Failed update operation");
+    let stored_broadcast = Broadcast::find_by_pk(&broadcast.id).await.unwrap().unwrap();
+    assert_eq!(stored_broadcast.channel, "lck_en");
+    assert_eq!(stored_broadcast.caster, caster_id);
+
+    broadcast.delete().await.expect("Failed delete operation");
+    assert!(!Broadcast::exists_by_pk(&broadcast.id).await.unwrap());
+}
+
+/// The text keys are provided by the entity, and can be used to search the rows
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_string_primary_key() {
+    let region = Region::find_by_pk(&"KR")
+        .await
+        .unwrap()
+        .expect("No region found for the code passed in");
+    assert_eq!(region.name, "Korea");
+
+    let mut new_region = Region {
+        code: "LAN".to_string(),
+        name: "Latin America North".to_string(),
+    };
+    new_region.insert().await.expect("Failed insert operation");
+    assert_eq!(new_region.code, "LAN");
+
+    let regions = Region::find_by_pks(&[&"LAN", &"EUW"], true).await.unwrap();
+    assert_eq!(
+        regions.iter().map(|r| r.code.as_str()).collect::<Vec<_>>(),
+        vec!["LAN", "EUW"]
+    );
+
+    new_region.delete().await.expect("Failed delete operation");
+}
+
+/// Same as the generated keys tests above, but targeting the database
+/// defined in the specified datasource
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_generated_keys_datasource() {
+    let mut caster = Caster {
+        id: Default::default(),
+        nickname: "Ender".to_string(),
+        region: "EUW".to_string(),
+    };
+    caster
+        .insert_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed insert datasource operation");
+    assert_ne!(caster.id, Uuid::nil());
+
+    let mut broadcast = Broadcast {
+        id: Default::default(),
+        caster: caster.id,
+        channel: "lec_es".to_string(),
+    };
+    broadcast
+        .insert_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed insert datasource operation");
+    assert_ne!(broadcast.id, Uuid::nil());
+    assert!(
+        Broadcast::exists_by_pk_datasource(&broadcast.id, SQL_SERVER_DS)
+            .await
+            .unwrap()
+    );
+
+    let mut new_region = Region {
+        code: "OCE".to_string(),
+        name: "Oceania".to_string(),
+    };
+    new_region
+        .insert_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed insert datasource operation");
+    assert_eq!(new_region.code, "OCE");
+}
@@ -0,0 +1,143 @@
+//! Integration tests for the lifecycle hooks called by the CRUD operations
+//! available in `Canyon` over the entities that implements `EntityHooks`
+use std::sync::atomic::Ordering;
+
+use canyon_sql::{
+    crud::CrudOperations,
+    query::{operators::Comp, ops::QueryBuilder},
+};
+
+#[cfg(feature = "mssql")]
+use crate::constants::SQL_SERVER_DS;
+use crate::tests_models::subscriber::*;
+
+/// The `before_insert` hook normalizes the email before the row is inserted, and
+/// aborts the insert when it's empty, so the statement never reaches the database
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_hooks() {
+    let mut subscriber = Subscriber {
+        id: Default::default(),
+        email: "  New.Subscriber@Canyon.com ".to_string(),
+        name: "New Subscriber".to_string(),
+        locked: false,
+    };
+    subscriber.insert().await.expect("Failed insert operation");
+    assert_eq!(subscriber.email, "new.subscriber@canyon.com");
+
+    let inserted_subscriber = Subscriber::find_by_pk(&subscriber.id)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No entity found for the primary key value passed in");
+    assert_eq!(inserted_subscriber.email, "new.subscriber@canyon.com");
+
+    let mut invalid_subscriber = Subscriber {
+        id: Default::default(),
+        email: "   ".to_string(),
+        name: "Invalid Subscriber".to_string(),
+        locked: false,
+    };
+    let error = invalid_subscriber
+        .insert()
+        .await
+        .expect_err("The before_insert hook must abort the insert");
+    assert_eq!(
+        error.to_string(),
+        "The email of a subscriber can't be empty"
+    );
+    assert_eq!(invalid_subscriber.id, 0);
+    assert!(!Subscriber::select_query()
+        .r#where(SubscriberFieldValue::name(&"Invalid Subscriber"), Comp::Eq)
+        .exists()
+        .await
+        .unwrap());
+}
+
+/// The `after_load` hook is called over every entity retrieved from the database
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_after_load_hook() {
+    let subscriber = Subscriber::find_by_pk(&1)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No entity found for the primary key value passed in");
+    assert_eq!(subscriber.name, "Padded Name");
+
+    let subscribers = Subscriber::find_all().await.unwrap();
+    assert!(subscribers
+        .iter()
+        .all(|subscriber| subscriber.name == subscriber.name.trim()));
+
+    let subscribers = Subscriber::select_query()
+        .r#where(SubscriberFieldValue::id(&1), Comp::Eq)
+        .query()
+        .await
+        .unwrap();
+    assert_eq!(subscribers[0].name, "Padded Name");
+}
+
+/// The `before_delete` hook rejects the delete of the locked subscribers, while
+/// the `after_delete` one is called once the row is deleted
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_delete_hooks() {
+    let locked_subscriber = Subscriber::find_by_pk(&2).await.unwrap().unwrap();
+    assert!(locked_subscriber.delete().await.is_err());
+    assert!(Subscriber::exists_by_pk(&2).await.unwrap());
+
+    let mut subscriber = Subscriber {
+        id: Default::default(),
+        email: "to.delete@canyon.com".to_string(),
+        name: "Subscriber to delete".to_string(),
+        locked: false,
+    };
+    subscriber.insert().await.expect("Failed insert operation");
+
+    let deleted_before = DELETED_SUBSCRIBERS.load(Ordering::SeqCst);
+    subscriber.delete().await.expect("Failed delete operation");
+    assert!(DELETED_SUBSCRIBERS.load(Ordering::SeqCst) > deleted_before);
+    assert!(!Subscriber::exists_by_pk(&subscriber.id).await.unwrap());
+}
+
+/// Same as the insert and delete hooks tests above, but targeting the database
+/// defined in the specified datasource
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_hooks_datasource() {
+    let mut subscriber = Subscriber {
+        id: Default::default(),
+        email: " Other.Subscriber@Canyon.com".to_string(),
+        name: "  Other Subscriber ".to_string(),
+        locked: false,
+    };
+    subscriber
+        .insert_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed insert datasource operation");
+    assert_eq!(subscriber.email, "other.subscriber@canyon.com");
+
+    let inserted_subscriber = Subscriber::find_by_pk_datasource(&subscriber.id, SQL_SERVER_DS)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(inserted_subscriber.name, "Other Subscriber");
+
+    let locked_subscriber = Subscriber::find_by_pk_datasource(&2, SQL_SERVER_DS)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(locked_subscriber
+        .delete_datasource(SQL_SERVER_DS)
+        .await
+        .is_err());
+
+    subscriber
+        .delete_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed delete datasource operation");
+    assert!(
+        !Subscriber::exists_by_pk_datasource(&subscriber.id, SQL_SERVER_DS)
+            .await
+            .unwrap()
+    );
+}
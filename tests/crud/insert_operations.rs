@@ -4,6 +4,7 @@ use canyon_sql::crud::CrudOperations;
 
 #[cfg(feature = "mssql")]
 use crate::constants::SQL_SERVER_DS;
+use crate::tests_models::announcement::*;
 use crate::tests_models::league::*;
 
 /// Inserts a new record on the database, given an entity that is
@@ -218,3 +219,53 @@ fn test_crud_multi_insert_datasource_operation() {
     assert_eq!(new_league_mi_2.id, inserted_league_2.id);
     assert_eq!(new_league_mi_3.id, inserted_league_3.id);
 }
+
+/// The fields annotated with `#[created_at]` and `#[updated_at]` are filled with
+/// the current UTC time on the instances that are inserted, with both the `insert`
+/// and the `multi_insert` operations
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_fills_the_audit_timestamps() {
+    let new_announcement = || Announcement {
+        id: Default::default(),
+        title: "Some New Announcement".to_string(),
+        created_at: Default::default(),
+        updated_at: None,
+    };
+    let before_insert = canyon_sql::date_time::Utc::now().naive_utc();
+
+    let mut announcement = new_announcement();
+    announcement
+        .insert()
+        .await
+        .expect("Failed insert operation");
+    assert!(announcement.created_at >= before_insert);
+    assert_eq!(announcement.updated_at, Some(announcement.created_at));
+
+    let inserted_announcement = Announcement::find_by_pk(&announcement.id)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No entity found for the primary key value passed in");
+    assert!(
+        (inserted_announcement.created_at - announcement.created_at)
+            .num_milliseconds()
+            .abs()
+            < 1
+    );
+
+    let mut announcement_2 = new_announcement();
+    let mut announcement_3 = new_announcement();
+    Announcement::multi_insert(&mut [&mut announcement_2, &mut announcement_3])
+        .await
+        .expect("Failed multi insert operation");
+    assert!(announcement_2.created_at >= before_insert);
+    assert!(announcement_3.created_at >= before_insert);
+    assert!(announcement_3.updated_at.is_some());
+
+    for announcement in [announcement, announcement_2, announcement_3] {
+        announcement
+            .delete()
+            .await
+            .expect("Failed delete operation");
+    }
+}
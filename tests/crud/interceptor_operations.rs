@@ -0,0 +1,249 @@
+//! Integration tests for the `QueryInterceptor`s, called by `Canyon` before and after
+//! every statement sent to the databases.
+//!
+//! The interceptors are registered for the whole test run, so they only act over the
+//! statements that contains the marker comment of their test
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use canyon_sql::{
+    crud::{
+        bounds::QueryParameter, register_datasource_interceptor, register_interceptor,
+        InterceptedQuery, QueryInterceptor, QueryOutcome,
+    },
+    macros::async_trait,
+    query_rows,
+};
+
+#[cfg(feature = "mssql")]
+use crate::constants::SQL_SERVER_DS;
+
+/// Rewrites the `'original'` literal of the statements that contains the `marker`
+struct LiteralRewriter {
+    marker: &'static str,
+}
+
+#[async_trait]
+impl QueryInterceptor for LiteralRewriter {
+    async fn before_query(
+        &self,
+        query: &mut InterceptedQuery<'_, '_>,
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        if query.statement().contains(self.marker) {
+            let statement = query.statement().replace("'original'", "'intercepted'");
+            query.set_statement(statement);
+        }
+        Ok(())
+    }
+}
+
+/// Rejects the statements with the `/* maintenance */` marker that aren't a `SELECT`
+#[cfg(feature = "postgres")]
+struct MaintenanceMode;
+
+#[cfg(feature = "postgres")]
+#[derive(Debug)]
+struct MaintenanceError;
+
+#[cfg(feature = "postgres")]
+impl Display for MaintenanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The database is in maintenance mode")
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl Error for MaintenanceError {}
+
+#[cfg(feature = "postgres")]
+#[async_trait]
+impl QueryInterceptor for MaintenanceMode {
+    async fn before_query(
+        &self,
+        query: &mut InterceptedQuery<'_, '_>,
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        if query.statement().contains("/* maintenance */")
+            && !query.statement().starts_with("SELECT")
+        {
+            return Err(Box::new(MaintenanceError));
+        }
+        Ok(())
+    }
+}
+
+/// Scopes the statements with the `/* scope */` marker to the leagues after the first
+/// one, binding the bound of the filter as a new parameter
+#[cfg(feature = "postgres")]
+struct LeagueScope;
+
+#[cfg(feature = "postgres")]
+#[async_trait]
+impl QueryInterceptor for LeagueScope {
+    async fn before_query(
+        &self,
+        query: &mut InterceptedQuery<'_, '_>,
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        if query.statement().contains("/* scope */") {
+            let placeholder = query.push_param(1);
+            let statement = format!("{} AND id > ${placeholder}", query.statement());
+            query.set_statement(statement);
+        }
+        if query.statement().contains("/* widen */") && !query.replace_param(1, 3) {
+            return Err("The statement doesn't have a first parameter".into());
+        }
+        Ok(())
+    }
+}
+
+/// Collects the outcome of the statements with the `/* metrics */` marker
+#[cfg(feature = "postgres")]
+struct QueryMetrics;
+
+#[cfg(feature = "postgres")]
+static EXECUTED_QUERIES: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "postgres")]
+static FAILED_QUERIES: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "postgres")]
+static RETURNED_ROWS: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "postgres")]
+static BOUND_PARAMS: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(feature = "postgres")]
+#[async_trait]
+impl QueryInterceptor for QueryMetrics {
+    async fn after_query(&self, query: &InterceptedQuery<'_, '_>, outcome: &QueryOutcome<'_>) {
+        if query.statement().contains("/* metrics */") {
+            EXECUTED_QUERIES.fetch_add(1, Ordering::SeqCst);
+            BOUND_PARAMS.fetch_add(query.params().len(), Ordering::SeqCst);
+            match outcome.rows() {
+                Some(rows) => RETURNED_ROWS.fetch_add(rows, Ordering::SeqCst),
+                None => FAILED_QUERIES.fetch_add(1, Ordering::SeqCst),
+            };
+        }
+    }
+}
+
+/// The interceptors rewrites the statements before they're sent to the database
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_interceptor_rewrites_statement() {
+    register_interceptor(LiteralRewriter {
+        marker: "/* rewrite */",
+    });
+
+    let rows = query_rows("SELECT 'original' AS value /* rewrite */", &[], "")
+        .await
+        .expect("Failed the query to the database");
+    assert_eq!(rows[0].get::<String>("value").unwrap(), "intercepted");
+
+    let rows = query_rows("SELECT 'original' AS value", &[], "")
+        .await
+        .expect("Failed the query to the database");
+    assert_eq!(rows[0].get::<String>("value").unwrap(), "original");
+}
+
+/// The interceptors adds and replaces the parameters bound to the placeholders of the
+/// statements they rewrites
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_interceptor_rewrites_params() {
+    register_interceptor(LeagueScope);
+
+    let params: &[&dyn QueryParameter<'_>] = &[&2];
+    let rows = query_rows(
+        "SELECT id FROM league WHERE id <= $1 /* scope */",
+        params,
+        "",
+    )
+    .await
+    .expect("Failed the query to the database");
+    let ids: Vec<i32> = rows
+        .iter()
+        .map(|row| row.get::<i32>("id").unwrap())
+        .collect();
+    assert_eq!(ids, vec![2]);
+
+    let rows = query_rows(
+        "SELECT id FROM league WHERE id <= $1 /* scope */ /* widen */",
+        params,
+        "",
+    )
+    .await
+    .expect("Failed the query to the database");
+    assert_eq!(rows.len(), 2);
+
+    assert!(query_rows("SELECT 1 AS value /* widen */", &[], "")
+        .await
+        .is_err());
+}
+
+/// The statements rejected by an interceptor aren't sent to the database, returning
+/// the error of the interceptor
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_interceptor_rejects_statement() {
+    register_interceptor(MaintenanceMode);
+
+    let error = query_rows("DELETE FROM league WHERE id = 1 /* maintenance */", &[], "")
+        .await
+        .expect_err("The interceptor must reject the statement");
+    assert!(error.downcast_ref::<MaintenanceError>().is_some());
+
+    let rows = query_rows(
+        "SELECT id FROM league WHERE id = 1 /* maintenance */",
+        &[],
+        "",
+    )
+    .await
+    .expect("Failed the query to the database");
+    assert_eq!(rows.len(), 1);
+}
+
+/// The interceptors are called after the statements are executed, with their parameters
+/// and the outcome of the execution
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_interceptor_collects_outcome() {
+    register_interceptor(QueryMetrics);
+
+    let params: &[&dyn QueryParameter<'_>] = &[&3];
+    let rows = query_rows(
+        "SELECT id FROM league WHERE id < $1 /* metrics */",
+        params,
+        "",
+    )
+    .await
+    .expect("Failed the query to the database");
+    assert!(
+        query_rows("SELECT * FROM missing_table /* metrics */", &[], "")
+            .await
+            .is_err()
+    );
+
+    assert_eq!(EXECUTED_QUERIES.load(Ordering::SeqCst), 2);
+    assert_eq!(FAILED_QUERIES.load(Ordering::SeqCst), 1);
+    assert_eq!(RETURNED_ROWS.load(Ordering::SeqCst), rows.len());
+    assert_eq!(BOUND_PARAMS.load(Ordering::SeqCst), 1);
+}
+
+/// The interceptors registered for a datasource are only called for its statements
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_datasource_interceptor_rewrites_statement() {
+    register_datasource_interceptor(
+        SQL_SERVER_DS,
+        LiteralRewriter {
+            marker: "/* rewrite_datasource */",
+        },
+    );
+
+    let rows = query_rows(
+        "SELECT 'original' AS value /* rewrite_datasource */",
+        &[],
+        SQL_SERVER_DS,
+    )
+    .await
+    .expect("Failed the query to the database");
+    assert_eq!(rows[0].get::<String>("value").unwrap(), "intercepted");
+}
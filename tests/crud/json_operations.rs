@@ -0,0 +1,128 @@
+//! Integration tests for the CRUD operations and the QueryBuilder filters available
+//! in `Canyon` over the entities with fields stored as JSON documents, declared with
+//! the `#[json]` annotation
+use canyon_sql::{
+    crud::CrudOperations,
+    json::{json, Json},
+    query::{operators::Comp, ops::QueryBuilder},
+};
+
+#[cfg(feature = "mssql")]
+use crate::constants::SQL_SERVER_DS;
+use crate::tests_models::replay::*;
+
+/// The JSON documents are deserialized into the fields of the entity, and the
+/// `NULL` values into the `None` variant of the optional ones
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_json_values() {
+    let replay = Replay::find_by_pk(&1)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No entity found for the primary key value passed in");
+    assert_eq!(replay.summary["winner"], "blue");
+    assert_eq!(replay.summary["duration"], 1834);
+    assert_eq!(replay.highlights, Some(json!(["pentakill", "baron steal"])));
+
+    let replay_without_highlights = Replay::find_by_pk(&2).await.unwrap().unwrap();
+    assert_eq!(replay_without_highlights.highlights, None);
+}
+
+/// The values of the `#[json]` fields are sent as JSON documents on the insert
+/// and update operations
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_update_json_values() {
+    let mut replay = Replay {
+        id: Default::default(),
+        tournament: 18,
+        summary: json!({"winner": "red", "duration": 1502, "patch": "13.12"}),
+        highlights: None,
+    };
+    replay.insert().await.expect("Failed insert operation");
+
+    replay.summary["duration"] = json!(1503);
+    replay.highlights = Some(json!(["first blood"]));
+    replay.update().await.expect("Failed update operation");
+
+    let stored_replay = Replay::find_by_pk(&replay.id).await.unwrap().unwrap();
+    assert_eq!(stored_replay, replay);
+
+    replay.delete().await.expect("Failed delete operation");
+}
+
+/// Filters the entities by the values nested inside the JSON documents, and by
+/// the documents that contains a given one
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_with_json_filters() {
+    let mut by_patch = Replay::select_query();
+    by_patch.json_value(ReplayField::summary, &["patch"], Comp::Eq, &"13.10");
+    assert_eq!(
+        by_patch.read_sql(),
+        "SELECT * FROM replay WHERE summary ->> 'patch' = $1"
+    );
+
+    let replays: Vec<Replay> = Replay::select_query()
+        .json_value(ReplayField::summary, &["winner"], Comp::Eq, &"blue")
+        .query()
+        .await
+        .unwrap();
+    assert!(replays
+        .iter()
+        .all(|replay| replay.summary["winner"] == "blue"));
+    assert!(replays.iter().any(|replay| replay.id == 1));
+
+    let patch = Json(json!({"patch": "13.11"}));
+    let replays: Vec<Replay> = Replay::select_query()
+        .r#where(ReplayFieldValue::tournament(&19), Comp::Eq)
+        .json_contains(ReplayField::summary, &patch)
+        .query()
+        .await
+        .unwrap();
+    assert_eq!(replays.len(), 1);
+    assert_eq!(replays[0].id, 2);
+}
+
+/// Same as the insert, update and filter tests above, but targeting the database
+/// defined in the specified datasource, where the documents are stored as text
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_json_values_datasource() {
+    let mut replay = Replay {
+        id: Default::default(),
+        tournament: 19,
+        summary: json!({"winner": "blue", "duration": 1977, "patch": "13.12"}),
+        highlights: Some(json!(["quadrakill"])),
+    };
+    replay
+        .insert_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed insert operation");
+
+    replay.highlights = None;
+    replay
+        .update_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed update operation");
+
+    let replays: Vec<Replay> = Replay::select_query_datasource(SQL_SERVER_DS)
+        .json_value(ReplayField::summary, &["patch"], Comp::Eq, &"13.12")
+        .query()
+        .await
+        .unwrap();
+    assert_eq!(replays, vec![replay.clone()]);
+
+    // SQL Server doesn't provides a containment operator
+    let patch = Json(json!({"patch": "13.12"}));
+    assert!(Replay::select_query_datasource(SQL_SERVER_DS)
+        .json_contains(ReplayField::summary, &patch)
+        .query()
+        .await
+        .is_err());
+
+    replay
+        .delete_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed delete operation");
+}
@@ -0,0 +1,170 @@
+//! Integration tests for the mock datasources, where the generated CRUD operations
+//! and the query builders runs against the rows of the registered expectations,
+//! without reaching any database.
+//!
+//! The expectations of the mock datasource are shared by the whole test run, so every
+//! test expects the statements over its own rows
+use canyon_sql::{
+    crud::{CrudOperations, MockDatasource, MockError},
+    query::{operators::Comp, ops::QueryBuilder},
+    query_rows,
+};
+
+use crate::constants::MOCK_DS;
+use crate::tests_models::league::*;
+
+fn new_league(id: i32, slug: &str) -> League {
+    League {
+        id,
+        ext_id: 100_000 + id as i64,
+        slug: slug.to_string(),
+        name: format!("Mock League {id}"),
+        region: "MOCK".to_string(),
+        image_url: "https://mock.canyon.io".to_string(),
+    }
+}
+
+/// The generated selects maps the rows of the expectations, built from instances
+/// of the entities, recording the executed statements
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_mock_find_all_and_find_by_pk() {
+    let mock = MockDatasource::new(MOCK_DS);
+    let leagues = vec![new_league(1, "mock-one"), new_league(2, "mock-two")];
+    mock.expect(r"^SELECT \* FROM league$")
+        .returning(leagues.clone());
+    mock.expect(r"^SELECT \* FROM league WHERE")
+        .with_params(&[&2])
+        .returning(vec![leagues[1].clone()]);
+
+    let found = League::find_all_datasource(MOCK_DS)
+        .await
+        .expect("Failed the query to the mock datasource");
+    assert_eq!(found, leagues);
+    mock.assert_executed(r"^SELECT \* FROM league$");
+
+    let found = League::find_by_pk_datasource(&2, MOCK_DS)
+        .await
+        .expect("Failed the query to the mock datasource");
+    assert_eq!(found, Some(leagues[1].clone()));
+
+    // The parameters doesn't match the ones of the expectation
+    let error = League::find_by_pk_datasource(&3, MOCK_DS)
+        .await
+        .expect_err("There's no expectation for the statement");
+    assert!(error.downcast_ref::<MockError>().is_some());
+}
+
+/// The query builders runs unchanged against the mock datasources, and the `count`
+/// maps the `i64` value of the row
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_mock_select_query_and_count() {
+    let mock = MockDatasource::new(MOCK_DS);
+    mock.expect(r"^SELECT \* FROM league WHERE region = \$1")
+        .with_params(&[&"MOCK_REGION"])
+        .returning(vec![new_league(3, "mock-three")]);
+    mock.expect(r"^SELECT COUNT \(\*\) FROM league$")
+        .returning(vec![12_i64]);
+
+    let leagues = League::select_query_datasource(MOCK_DS)
+        .r#where(LeagueFieldValue::region(&"MOCK_REGION"), Comp::Eq)
+        .query()
+        .await
+        .expect("Failed the query to the mock datasource");
+    assert_eq!(leagues, vec![new_league(3, "mock-three")]);
+
+    let count = League::count_datasource(MOCK_DS)
+        .await
+        .expect("Failed the query to the mock datasource");
+    assert_eq!(count, 12);
+}
+
+/// The primary key of the inserted entities is taken from the instance of the row
+/// returned by the expectation, and the statements of the failing expectations
+/// returns a `MockError`
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_mock_insert_and_failing_delete() {
+    let mock = MockDatasource::new(MOCK_DS);
+    mock.expect(r"^INSERT INTO league")
+        .returning(vec![new_league(99, "mock-inserted")]);
+    mock.expect(r"^DELETE FROM league")
+        .with_params(&[&99])
+        .failing("The database is in maintenance mode");
+
+    let mut league = new_league(0, "mock-inserted");
+    league
+        .insert_datasource(MOCK_DS)
+        .await
+        .expect("Failed insert operation");
+    assert_eq!(league.id, 99);
+    assert!(mock.executed().iter().any(|executed| executed
+        .statement
+        .starts_with("INSERT INTO league")
+        && executed.params.contains(&"\"mock-inserted\"".to_string())));
+
+    let error = league
+        .delete_datasource(MOCK_DS)
+        .await
+        .expect_err("The expectation fails the statement");
+    assert_eq!(
+        error.downcast_ref::<MockError>().map(|e| e.message()),
+        Some("The database is in maintenance mode")
+    );
+}
+
+/// The `first()` and `exists()` terminals of the query builder just request one row to
+/// the database, and `one()` requests two, enough to detect a second match
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_mock_first_one_and_exists_limit_the_rows() {
+    let mock = MockDatasource::new(MOCK_DS);
+    mock.expect(r"^SELECT \* FROM league WHERE region = \$1 LIMIT 1;$")
+        .with_params(&[&"MOCK_LIMITED"])
+        .returning(vec![new_league(4, "mock-four")]);
+    mock.expect(r"^SELECT \* FROM league WHERE region = \$1 LIMIT 2;$")
+        .with_params(&[&"MOCK_LIMITED"])
+        .returning(vec![new_league(4, "mock-four"), new_league(5, "mock-five")]);
+
+    let first = League::select_query_datasource(MOCK_DS)
+        .r#where(LeagueFieldValue::region(&"MOCK_LIMITED"), Comp::Eq)
+        .first()
+        .await
+        .expect("Failed the query to the mock datasource");
+    assert_eq!(first, Some(new_league(4, "mock-four")));
+
+    assert!(League::select_query_datasource(MOCK_DS)
+        .r#where(LeagueFieldValue::region(&"MOCK_LIMITED"), Comp::Eq)
+        .exists()
+        .await
+        .expect("Failed the query to the mock datasource"));
+
+    assert!(League::select_query_datasource(MOCK_DS)
+        .r#where(LeagueFieldValue::region(&"MOCK_LIMITED"), Comp::Eq)
+        .one()
+        .await
+        .is_err());
+
+    // The lower limit of the query is kept
+    League::select_query_datasource(MOCK_DS)
+        .r#where(LeagueFieldValue::region(&"MOCK_LIMITED"), Comp::Eq)
+        .limit(1)
+        .one()
+        .await
+        .expect("Failed the query to the mock datasource");
+}
+
+/// The rows of the mock datasources hold instances instead of columns, so reading
+/// them as dynamic rows fails with an error
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_mock_query_rows_fails() {
+    let mock = MockDatasource::new(MOCK_DS);
+    mock.expect(r"^SELECT slug FROM league WHERE region = 'MOCK_ROWS'$")
+        .returning(vec![new_league(6, "mock-six")]);
+
+    let error = query_rows(
+        "SELECT slug FROM league WHERE region = 'MOCK_ROWS'",
+        &[],
+        MOCK_DS,
+    )
+    .await
+    .expect_err("The mock rows can't be read as dynamic rows");
+    assert!(error.to_string().contains("dynamic rows"));
+}
@@ -1,10 +1,30 @@
 #![allow(unused_imports)]
 
+#[cfg(feature = "postgres")]
+pub mod array_operations;
+pub mod binary_operations;
+pub mod column_mapping_operations;
+pub mod composite_key_operations;
+pub mod custom_type_operations;
+#[cfg(feature = "rust_decimal")]
+pub mod decimal_operations;
 pub mod delete_operations;
+pub mod embedded_operations;
+pub mod enum_operations;
 pub mod foreign_key_operations;
+pub mod generated_key_operations;
+pub mod hooks_operations;
 #[cfg(feature = "mssql")]
 pub mod init_mssql;
 pub mod insert_operations;
+pub mod interceptor_operations;
+pub mod json_operations;
+#[cfg(feature = "mock")]
+pub mod mock_operations;
 pub mod querybuilder_operations;
+pub mod raw_query_operations;
+pub mod relation_operations;
 pub mod select_operations;
+pub mod soft_delete_operations;
 pub mod update_operations;
+pub mod validation_operations;
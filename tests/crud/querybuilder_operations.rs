@@ -162,6 +162,208 @@ fn test_crud_find_with_querybuilder_datasource() {
     assert!(!filtered_find_players.unwrap().is_empty());
 }
 
+/// Retrieves only the first entity matched by the filters of the [`SelectQueryBuilder`],
+/// or checks if there's any match at all, with the `first()` and `exists()` terminals
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_first_and_exists_with_querybuilder() {
+    let first_league = League::select_query()
+        .r#where(LeagueFieldValue::region(&"KOREA"), Comp::Eq)
+        .order_by(LeagueField::id, false)
+        .first()
+        .await
+        .unwrap();
+    assert_eq!(first_league.unwrap().region, "KOREA");
+
+    let no_league = League::select_query()
+        .r#where(LeagueFieldValue::id(&-1), Comp::Eq)
+        .first()
+        .await
+        .unwrap();
+    assert!(no_league.is_none());
+
+    assert!(League::select_query()
+        .r#where(LeagueFieldValue::region(&"KOREA"), Comp::Eq)
+        .exists()
+        .await
+        .unwrap());
+    assert!(!League::select_query()
+        .r#where(LeagueFieldValue::id(&-1), Comp::Eq)
+        .exists()
+        .await
+        .unwrap());
+}
+
+/// The `one()` terminal of the [`SelectQueryBuilder`] only succeeds when the query
+/// matches exactly one row
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_one_with_querybuilder() {
+    let league = League::select_query()
+        .r#where(LeagueFieldValue::id(&1), Comp::Eq)
+        .one()
+        .await
+        .unwrap();
+    assert_eq!(league.id, 1);
+
+    assert!(League::select_query()
+        .r#where(LeagueFieldValue::id(&-1), Comp::Eq)
+        .one()
+        .await
+        .is_err());
+    assert!(League::select_query()
+        .r#where(LeagueFieldValue::id(&10), Comp::Lt)
+        .one()
+        .await
+        .is_err());
+}
+
+/// Restricts the number of retrieved entities with the `limit()` and `offset()`
+/// operations of the [`SelectQueryBuilder`]
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_limit_and_offset_with_querybuilder() {
+    let leagues = League::select_query()
+        .r#where(LeagueFieldValue::id(&10), Comp::Lt)
+        .order_by(LeagueField::id, false)
+        .limit(3)
+        .offset(2)
+        .query()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        leagues.iter().map(|league| league.id).collect::<Vec<i32>>(),
+        vec![3, 4, 5]
+    );
+}
+
+/// SQL Server needs an `ORDER BY` clause for use the `OFFSET ... FETCH NEXT` syntax
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_limit_and_offset_with_querybuilder_datasource() {
+    let leagues = League::select_query_datasource(SQL_SERVER_DS)
+        .order_by(LeagueField::id, false)
+        .limit(2)
+        .offset(1)
+        .query()
+        .await
+        .unwrap();
+    assert_eq!(leagues.len(), 2);
+
+    let unsorted = League::select_query_datasource(SQL_SERVER_DS)
+        .limit(2)
+        .query()
+        .await;
+    assert!(unsorted.is_err());
+}
+
+/// Retrieves a page of entities alongside the total of rows that matches the query
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_paginate_with_querybuilder() {
+    let page = League::select_query()
+        .r#where(LeagueFieldValue::id(&10), Comp::Lt)
+        .order_by(LeagueField::id, false)
+        .paginate(1, 4)
+        .await
+        .unwrap();
+
+    assert_eq!(page.total, 9);
+    assert_eq!(page.total_pages(), 3);
+    assert!(page.has_next());
+    assert_eq!(
+        page.items
+            .iter()
+            .map(|league| league.id)
+            .collect::<Vec<i32>>(),
+        vec![5, 6, 7, 8]
+    );
+}
+
+/// Keyset pagination, retrieving the entities placed after the given cursor
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_keyset_pagination_with_querybuilder() {
+    let mut q = League::select_query();
+    q.r#where(LeagueFieldValue::id(&10), Comp::Lt)
+        .after([LeagueFieldValue::id(&6)], false);
+    assert_eq!(
+        q.read_sql(),
+        "SELECT * FROM league WHERE (id < $1) AND ((id > $2)) ORDER BY id"
+    );
+
+    let leagues = League::select_query()
+        .r#where(LeagueFieldValue::id(&10), Comp::Lt)
+        .after([LeagueFieldValue::id(&6)], false)
+        .limit(2)
+        .query()
+        .await
+        .unwrap();
+    assert_eq!(
+        leagues.iter().map(|league| league.id).collect::<Vec<i32>>(),
+        vec![7, 8]
+    );
+
+    // The previous filters are grouped, so the rows matched by the `OR` condition
+    // placed before the cursor are skipped too
+    let mut q = League::select_query();
+    q.r#where(LeagueFieldValue::id(&3), Comp::Lt)
+        .or(LeagueFieldValue::id(&7), Comp::Gt)
+        .after([LeagueFieldValue::id(&1)], false);
+    assert_eq!(
+        q.read_sql(),
+        "SELECT * FROM league WHERE (id < $1 OR id > $2) AND ((id > $3)) ORDER BY id"
+    );
+
+    let leagues = League::select_query()
+        .r#where(LeagueFieldValue::id(&3), Comp::Lt)
+        .or(LeagueFieldValue::id(&7), Comp::Gt)
+        .after([LeagueFieldValue::id(&1)], false)
+        .query()
+        .await
+        .unwrap();
+    assert!(!leagues.is_empty());
+    assert!(leagues.iter().all(|league| league.id > 1));
+    assert!(leagues.iter().all(|league| league.id < 3 || league.id > 7));
+}
+
+/// The keyset pagination can be combined with the `order_by` method, called before or
+/// after it, in a single `ORDER BY` clause led by the columns of the cursor
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_keyset_pagination_with_order_by() {
+    let mut q = League::select_query();
+    q.r#where(LeagueFieldValue::id(&10), Comp::Lt)
+        .order_by(LeagueField::region, false)
+        .after([LeagueFieldValue::id(&6)], false);
+    assert_eq!(
+        q.read_sql(),
+        "SELECT * FROM league WHERE (id < $1) AND ((id > $2)) ORDER BY id, region"
+    );
+
+    let mut q = League::select_query();
+    q.after([LeagueFieldValue::id(&6)], true)
+        .order_by(LeagueField::region, false);
+    assert_eq!(
+        q.read_sql(),
+        "SELECT * FROM league WHERE ((id < $1)) ORDER BY id DESC, region"
+    );
+
+    let leagues = League::select_query()
+        .r#where(LeagueFieldValue::id(&10), Comp::Lt)
+        .order_by(LeagueField::id, true)
+        .after([LeagueFieldValue::id(&6)], false)
+        .limit(2)
+        .query()
+        .await
+        .unwrap();
+    assert_eq!(
+        leagues.iter().map(|league| league.id).collect::<Vec<i32>>(),
+        vec![7, 8]
+    );
+}
+
 /// Updates the values of the range on entries defined by the constraint parameters
 /// in the database entity
 #[cfg(feature = "postgres")]
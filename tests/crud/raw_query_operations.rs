@@ -0,0 +1,164 @@
+//! Integration tests for the queries available in `Canyon` whose results aren't
+//! mapped into entities, but into plain structs that derives `CanyonMapper` or into
+//! dynamic rows
+use canyon_sql::{
+    crud::{bounds::QueryParameter, DynamicRow, MappingError},
+    macros::CanyonMapper,
+    query_as, query_rows,
+};
+
+#[cfg(feature = "mssql")]
+use crate::constants::SQL_SERVER_DS;
+
+/// A struct that isn't an entity (it has no table), just the result of some query
+#[derive(Debug, CanyonMapper)]
+struct LeagueSummary {
+    id: i32,
+    name: String,
+    region_code: Option<String>,
+}
+
+/// The results of a custom `SELECT` are mapped into any type that derives `CanyonMapper`,
+/// matching its fields against the columns by name
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_query_as_operation() {
+    // The leagues whose names aren't modified by the rest of the tests
+    let params: &[&dyn QueryParameter<'_>] = &[&1, &9];
+    let summaries: Vec<LeagueSummary> = query_as(
+        "SELECT id, name, NULL::TEXT AS region_code FROM league \
+        WHERE id = $1 OR id = $2 ORDER BY id",
+        params,
+        "",
+    )
+    .await
+    .expect("Failed the query to the database");
+
+    assert_eq!(summaries.len(), 2);
+    assert_eq!(summaries[0].id, 1);
+    assert_eq!(summaries[0].name, "European Masters");
+    assert_eq!(summaries[0].region_code, None);
+    assert_eq!(summaries[1].name, "NLC");
+}
+
+/// The rows of a custom `SELECT` can be read without mapping them into a type, by the
+/// name of their columns, or converted into JSON
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_query_rows_operation() {
+    let rows: Vec<DynamicRow> = query_rows(
+        "SELECT id, name, NULL::TEXT AS region_code, COUNT(*) OVER () AS total \
+        FROM league WHERE id = 1",
+        &[],
+        "",
+    )
+    .await
+    .expect("Failed the query to the database");
+    assert_eq!(rows.len(), 1);
+
+    let row = &rows[0];
+    assert_eq!(
+        row.column_names(),
+        vec!["id", "name", "region_code", "total"]
+    );
+    assert_eq!(row.get::<i32>("id").unwrap(), 1);
+    assert_eq!(row.get::<String>("name").unwrap(), "European Masters");
+    assert_eq!(row.get::<Option<String>>("region_code").unwrap(), None);
+    assert!(row.get::<String>("region_code").is_err());
+    assert!(row.get::<i32>("missing").is_err());
+    assert_eq!(row.get_postgres::<i64>("total").unwrap(), 1);
+    assert_eq!(row.get_postgres_opt::<String>("region_code").unwrap(), None);
+    assert!(row.get_postgres::<String>("region_code").is_err());
+    assert_eq!(
+        row.get_postgres::<String>("id")
+            .expect_err("An integer column can't be retrieved as a String")
+            .column(),
+        "id"
+    );
+    assert_eq!(row.columns().len(), 4);
+
+    assert_eq!(
+        row.to_json().unwrap(),
+        canyon_sql::json::json!({
+            "id": 1,
+            "name": "European Masters",
+            "region_code": null,
+            "total": 1
+        })
+    );
+}
+
+/// The rows that can't be mapped return a `MappingError` instead of panicking, with
+/// the column, the Rust type of the field and the SQL type of the column
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_query_as_mapping_errors() {
+    let error = query_as::<LeagueSummary, _, _>(
+        "SELECT id, NULL::TEXT AS name, region AS region_code FROM league WHERE id = 1",
+        &[],
+        "",
+    )
+    .await
+    .expect_err("The NULL name can't be mapped into a String");
+    let mapping_error = error
+        .downcast_ref::<MappingError>()
+        .expect("The error must be a MappingError");
+    assert_eq!(mapping_error.column(), "name");
+    assert_eq!(mapping_error.rust_type(), "alloc::string::String");
+    assert_eq!(mapping_error.sql_type(), Some("text"));
+
+    let error =
+        query_as::<LeagueSummary, _, _>("SELECT id, name FROM league WHERE id = 1", &[], "")
+            .await
+            .expect_err("The row doesn't contain the region_code column");
+    let mapping_error = error
+        .downcast_ref::<MappingError>()
+        .expect("The error must be a MappingError");
+    assert_eq!(mapping_error.column(), "region_code");
+    assert_eq!(mapping_error.sql_type(), None);
+}
+
+/// Same as the above tests, but with the specified datasource
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_query_as_and_query_rows_datasource_operations() {
+    let params: &[&dyn QueryParameter<'_>] = &[&1, &9];
+    let summaries: Vec<LeagueSummary> = query_as(
+        "SELECT id, name, CAST(NULL AS NVARCHAR(10)) AS region_code \
+        FROM league WHERE id = $1 OR id = $2 ORDER BY id",
+        params,
+        SQL_SERVER_DS,
+    )
+    .await
+    .expect("Failed the query to the database");
+    assert_eq!(summaries.len(), 2);
+    assert_eq!(summaries[1].name, "NLC");
+
+    let rows = query_rows(
+        "SELECT id, name FROM league WHERE id = 1",
+        &[],
+        SQL_SERVER_DS,
+    )
+    .await
+    .expect("Failed the query to the database");
+    assert_eq!(rows[0].column_names(), vec!["id", "name"]);
+    assert_eq!(rows[0].get::<i32>("id").unwrap(), 1);
+    assert_eq!(
+        rows[0].to_json().unwrap(),
+        canyon_sql::json::json!({ "id": 1, "name": "European Masters" })
+    );
+
+    let error = query_as::<LeagueSummary, _, _>(
+        "SELECT id, CAST(NULL AS NVARCHAR(10)) AS name, region AS region_code \
+        FROM league WHERE id = 1",
+        &[],
+        SQL_SERVER_DS,
+    )
+    .await
+    .expect_err("The NULL name can't be mapped into a String");
+    let mapping_error = error
+        .downcast_ref::<MappingError>()
+        .expect("The error must be a MappingError");
+    assert_eq!(mapping_error.column(), "name");
+    assert!(mapping_error.sql_type().is_some());
+}
@@ -0,0 +1,170 @@
+//! Integration tests for the accessors of the relations declared on the entities
+//! with the `#[has_many(...)]` and `#[many_to_many(...)]` annotations, and the
+//! operations over the join tables of the latter
+use canyon_sql::crud::CrudOperations;
+
+#[cfg(feature = "mssql")]
+use crate::constants::SQL_SERVER_DS;
+use crate::tests_models::league::*;
+use crate::tests_models::region::*;
+
+#[cfg(feature = "postgres")]
+async fn find_regions(codes: &[&str]) -> Vec<Region> {
+    let mut regions = Vec::new();
+    for code in codes {
+        regions.push(
+            Region::find_by_pk(code)
+                .await
+                .expect("Failed the query to the database")
+                .expect("No region found for the code passed in"),
+        );
+    }
+    regions
+}
+
+/// The `#[has_many]` accessors retrieves the children whose foreign key references
+/// the entity, also through the renamed columns of the children
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_has_many_accessors() {
+    let league = League::find_by_pk(&1)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No league found for the given primary key");
+
+    let tournaments = league
+        .tournaments()
+        .await
+        .expect("Failed the query to the database");
+    assert!(!tournaments.is_empty());
+    assert!(tournaments.iter().all(|t| t.league == league.id));
+
+    let coaches = league
+        .coaches()
+        .await
+        .expect("Failed the query to the database");
+    assert_eq!(coaches.len(), 2);
+    assert!(coaches.iter().all(|coach| coach.league == league.id));
+}
+
+/// The `#[many_to_many]` accessors retrieves the entities related on the join table
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_many_to_many_accessors() {
+    let league = League::find_by_pk(&1)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No league found for the given primary key");
+
+    let mut regions = league
+        .regions()
+        .await
+        .expect("Failed the query to the database");
+    regions.sort_by(|a, b| a.code.cmp(&b.code));
+    assert_eq!(
+        regions.iter().map(|r| r.code.as_str()).collect::<Vec<_>>(),
+        vec!["EUW", "KR"]
+    );
+}
+
+/// The related entities are attached, detached and synced on the join table
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_many_to_many_attach_detach_sync() {
+    let league = League::find_by_pk(&3)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No league found for the given primary key");
+    let regions = find_regions(&["EUW", "KR"]).await;
+    let (euw, kr) = (&regions[0], &regions[1]);
+
+    // Attaching an already related entity doesn't duplicate the relation
+    league
+        .attach_regions(&[euw])
+        .await
+        .expect("Failed attach operation");
+    league
+        .attach_regions(&[euw, kr])
+        .await
+        .expect("Failed attach operation");
+    assert_eq!(league.regions().await.unwrap().len(), 2);
+
+    league
+        .detach_regions(&[euw])
+        .await
+        .expect("Failed detach operation");
+    assert_eq!(league.regions().await.unwrap(), vec![kr.clone()]);
+
+    // A failed sync keeps the current relations, since the new ones are inserted first
+    let unknown = Region {
+        code: "UNKNOWN".to_string(),
+        name: "Not stored region".to_string(),
+    };
+    assert!(league.sync_regions(&[euw, &unknown]).await.is_err());
+    assert_eq!(league.regions().await.unwrap(), vec![kr.clone()]);
+
+    league
+        .sync_regions(&[euw])
+        .await
+        .expect("Failed sync operation");
+    assert_eq!(league.regions().await.unwrap(), vec![euw.clone()]);
+
+    league
+        .sync_regions(&[])
+        .await
+        .expect("Failed sync operation");
+    assert!(league.regions().await.unwrap().is_empty());
+}
+
+/// Same as the above tests, but with the specified datasource
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_relation_accessors_datasource() {
+    let league = League::find_by_pk_datasource(&4, SQL_SERVER_DS)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No league found for the given primary key");
+
+    let tournaments = league
+        .tournaments_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed the query to the database");
+    assert!(tournaments.iter().all(|t| t.league == league.id));
+
+    let regions = Region::find_by_pks_datasource(&[&"EUW", &"KR"], true, SQL_SERVER_DS)
+        .await
+        .expect("Failed the query to the database");
+    let (euw, kr) = (&regions[0], &regions[1]);
+
+    league
+        .attach_regions_datasource(&[euw, kr], SQL_SERVER_DS)
+        .await
+        .expect("Failed attach operation");
+    assert_eq!(
+        league
+            .regions_datasource(SQL_SERVER_DS)
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+
+    league
+        .detach_regions_datasource(&[kr], SQL_SERVER_DS)
+        .await
+        .expect("Failed detach operation");
+    assert_eq!(
+        league.regions_datasource(SQL_SERVER_DS).await.unwrap(),
+        vec![euw.clone()]
+    );
+
+    league
+        .sync_regions_datasource(&[], SQL_SERVER_DS)
+        .await
+        .expect("Failed sync operation");
+    assert!(league
+        .regions_datasource(SQL_SERVER_DS)
+        .await
+        .unwrap()
+        .is_empty());
+}
@@ -129,3 +129,67 @@ fn test_crud_count_datasource_operation() {
         League::count_datasource(SQL_SERVER_DS).await.unwrap()
     );
 }
+
+/// Tests the behaviour of a SELECT * FROM {table_name} WHERE <pk> IN (<pk_values>), retrieving
+/// several entities in just one round trip through the `::find_by_pks()` associated function.
+///
+/// Uses the *default datasource*.
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_by_pks() {
+    let find_by_pks_result: Result<Vec<League>, Box<dyn Error + Send + Sync>> =
+        League::find_by_pks(&[&3, &1, &2], true).await;
+    let leagues = find_by_pks_result.unwrap();
+
+    assert_eq!(leagues.len(), 3);
+    assert_eq!(
+        leagues.iter().map(|league| league.id).collect::<Vec<i32>>(),
+        vec![3, 1, 2]
+    );
+
+    // An empty collection of keys does not even reach the database
+    assert!(League::find_by_pks(&[], false).await.unwrap().is_empty());
+}
+
+/// Same as the `find_by_pks()`, but with the specified datasource
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_by_pks_datasource() {
+    let find_by_pks_result: Result<Vec<League>, Box<dyn Error + Send + Sync>> =
+        League::find_by_pks_datasource(&[&27, &1], true, SQL_SERVER_DS).await;
+    let leagues = find_by_pks_result.unwrap();
+
+    assert_eq!(leagues.len(), 2);
+    assert_eq!(leagues[0].id, 27);
+    assert_eq!(leagues[1].id, 1);
+}
+
+/// Checks if a row with the given primary key exists on the target database
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_exists_by_pk() {
+    assert!(League::exists_by_pk(&1).await.unwrap());
+    assert!(!League::exists_by_pk(&-1).await.unwrap());
+}
+
+/// Checks if a row with the given primary key exists on the target database
+/// using the specified datasource
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_exists_by_pk_datasource() {
+    assert!(League::exists_by_pk_datasource(&27, SQL_SERVER_DS)
+        .await
+        .unwrap());
+    assert!(!League::exists_by_pk_datasource(&-1, SQL_SERVER_DS)
+        .await
+        .unwrap());
+}
+
+/// The entities without a `#[primary_key]` annotation can't use the
+/// operations that depends on the primary key
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_find_by_pks_without_primary_key() {
+    assert!(Player::find_by_pks(&[&1], false).await.is_err());
+    assert!(Player::exists_by_pk(&1).await.is_err());
+}
@@ -0,0 +1,255 @@
+//! Integration tests for the CRUD operations available in `Canyon` over the
+//! entities that has a field annotated with `#[soft_delete]`
+use canyon_sql::{
+    crud::CrudOperations,
+    query::{operators::Comp, ops::QueryBuilder},
+};
+
+#[cfg(feature = "mssql")]
+use crate::constants::SQL_SERVER_DS;
+use crate::tests_models::sponsor::*;
+
+/// The rows marked as deleted are excluded by the generated queries, so they
+/// can't be retrieved neither by `find_all`, nor by it's primary key.
+///
+/// The sponsor with the id 3 is already marked as deleted on the initial data
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_soft_deleted_rows_are_excluded() {
+    let sponsors = Sponsor::find_all().await.unwrap();
+    assert!(!sponsors.is_empty());
+    assert!(sponsors.iter().all(|sponsor| sponsor.deleted_at.is_none()));
+    assert!(!sponsors.iter().any(|sponsor| sponsor.id == 3));
+
+    assert_eq!(Sponsor::find_by_pk(&3).await.unwrap(), None);
+    assert!(!Sponsor::exists_by_pk(&3).await.unwrap());
+    assert_eq!(
+        Sponsor::find_by_pks(&[&1, &3, &4], true)
+            .await
+            .unwrap()
+            .iter()
+            .map(|sponsor| sponsor.id)
+            .collect::<Vec<i32>>(),
+        vec![1, 4]
+    );
+}
+
+/// Counts only the rows that aren't marked as deleted
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_soft_delete_count_operation() {
+    assert_eq!(
+        Sponsor::find_all().await.unwrap().len() as i64,
+        Sponsor::count().await.unwrap()
+    );
+}
+
+/// The `SelectQueryBuilder` generated by `select_query` discards the rows marked as
+/// deleted, while `with_deleted` includes them and `only_deleted` just retrieves them
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_soft_delete_querybuilders() {
+    let mut select_query = Sponsor::select_query();
+    select_query.r#where(SponsorFieldValue::id(&5), Comp::Lt);
+    assert_eq!(
+        select_query.read_sql(),
+        "SELECT * FROM (SELECT * FROM sponsor WHERE deleted_at IS NULL) AS sponsor WHERE id < $1"
+    );
+
+    let sponsors = Sponsor::select_query()
+        .r#where(SponsorFieldValue::id(&5), Comp::Lt)
+        .order_by(SponsorField::id, false)
+        .query()
+        .await
+        .unwrap();
+    assert_eq!(
+        sponsors.iter().map(|s| s.id).collect::<Vec<i32>>(),
+        vec![1, 2, 4]
+    );
+
+    let all_sponsors = Sponsor::with_deleted()
+        .r#where(SponsorFieldValue::id(&5), Comp::Lt)
+        .query()
+        .await
+        .unwrap();
+    assert_eq!(all_sponsors.len(), 4);
+
+    let deleted_sponsors = Sponsor::only_deleted()
+        .r#where(SponsorFieldValue::id(&5), Comp::Lt)
+        .query()
+        .await
+        .unwrap();
+    assert_eq!(deleted_sponsors.len(), 1);
+    assert_eq!(deleted_sponsors[0].id, 3);
+    assert!(deleted_sponsors[0].deleted_at.is_some());
+}
+
+/// The whole lifecycle of a soft deleted entity. The `delete` operation just marks
+/// the row as deleted (once), so it can be restored later, until it's permanently
+/// removed with `force_delete`
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_soft_delete_restore_and_force_delete_operations() {
+    let mut new_sponsor = Sponsor {
+        id: Default::default(),
+        name: "Some New Sponsor".to_string(),
+        league: 1,
+        deleted_at: None,
+    };
+    new_sponsor.insert().await.expect("Failed insert operation");
+
+    new_sponsor
+        .delete()
+        .await
+        .expect("Failed soft delete operation");
+    assert_eq!(Sponsor::find_by_pk(&new_sponsor.id).await.unwrap(), None);
+
+    let deleted_sponsor = Sponsor::only_deleted()
+        .r#where(SponsorFieldValue::id(&new_sponsor.id), Comp::Eq)
+        .query()
+        .await
+        .unwrap();
+    assert_eq!(deleted_sponsor.len(), 1);
+    assert!(deleted_sponsor[0].deleted_at.is_some());
+
+    // Deleting an already deleted row keeps its original deletion timestamp
+    deleted_sponsor[0]
+        .delete()
+        .await
+        .expect("Failed soft delete operation over a deleted row");
+    let redeleted_sponsor = Sponsor::only_deleted()
+        .r#where(SponsorFieldValue::id(&new_sponsor.id), Comp::Eq)
+        .query()
+        .await
+        .unwrap();
+    assert_eq!(
+        redeleted_sponsor[0].deleted_at,
+        deleted_sponsor[0].deleted_at
+    );
+
+    new_sponsor
+        .restore()
+        .await
+        .expect("Failed restore operation");
+    let restored_sponsor = Sponsor::find_by_pk(&new_sponsor.id).await.unwrap();
+    assert!(restored_sponsor.is_some());
+    assert_eq!(restored_sponsor.unwrap().deleted_at, None);
+
+    new_sponsor
+        .force_delete()
+        .await
+        .expect("Failed force delete operation");
+    assert!(Sponsor::with_deleted()
+        .r#where(SponsorFieldValue::id(&new_sponsor.id), Comp::Eq)
+        .query()
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+/// The `DeleteQueryBuilder` generated by `delete_query` also just marks the rows as
+/// deleted, while the one of `force_delete_query` permanently removes them
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_soft_delete_with_delete_querybuilder() {
+    let mut new_sponsor = Sponsor {
+        id: Default::default(),
+        name: "Some Sponsor Deleted With The QueryBuilder".to_string(),
+        league: 1,
+        deleted_at: None,
+    };
+    new_sponsor.insert().await.expect("Failed insert operation");
+
+    let mut delete_query = Sponsor::delete_query();
+    delete_query.r#where(SponsorFieldValue::id(&new_sponsor.id), Comp::Eq);
+    assert_eq!(
+        delete_query.read_sql(),
+        "UPDATE sponsor SET deleted_at = COALESCE(deleted_at, (CURRENT_TIMESTAMP AT TIME ZONE 'UTC')) \
+        WHERE id = $1"
+    );
+
+    Sponsor::delete_query()
+        .r#where(SponsorFieldValue::id(&new_sponsor.id), Comp::Eq)
+        .query()
+        .await
+        .expect("Failed soft delete operation with the querybuilder");
+    assert_eq!(Sponsor::find_by_pk(&new_sponsor.id).await.unwrap(), None);
+
+    let deleted_sponsor = Sponsor::with_deleted()
+        .r#where(SponsorFieldValue::id(&new_sponsor.id), Comp::Eq)
+        .query()
+        .await
+        .unwrap();
+    assert_eq!(deleted_sponsor.len(), 1);
+    assert!(deleted_sponsor[0].deleted_at.is_some());
+
+    Sponsor::force_delete_query()
+        .r#where(SponsorFieldValue::id(&new_sponsor.id), Comp::Eq)
+        .query()
+        .await
+        .expect("Failed force delete operation with the querybuilder");
+    assert!(Sponsor::with_deleted()
+        .r#where(SponsorFieldValue::id(&new_sponsor.id), Comp::Eq)
+        .query()
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+/// Same as the lifecycle test above, but targeting the database defined in
+/// the specified datasource
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_soft_delete_restore_and_force_delete_datasource_operations() {
+    let mut new_sponsor = Sponsor {
+        id: Default::default(),
+        name: "Some New Sponsor".to_string(),
+        league: 1,
+        deleted_at: None,
+    };
+    new_sponsor
+        .insert_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed insert datasource operation");
+
+    new_sponsor
+        .delete_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed soft delete datasource operation");
+    assert_eq!(
+        Sponsor::find_by_pk_datasource(&new_sponsor.id, SQL_SERVER_DS)
+            .await
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        Sponsor::only_deleted_datasource(SQL_SERVER_DS)
+            .r#where(SponsorFieldValue::id(&new_sponsor.id), Comp::Eq)
+            .query()
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+
+    new_sponsor
+        .restore_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed restore datasource operation");
+    assert!(
+        Sponsor::exists_by_pk_datasource(&new_sponsor.id, SQL_SERVER_DS)
+            .await
+            .unwrap()
+    );
+
+    new_sponsor
+        .force_delete_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed force delete datasource operation");
+    assert!(Sponsor::with_deleted_datasource(SQL_SERVER_DS)
+        .r#where(SponsorFieldValue::id(&new_sponsor.id), Comp::Eq)
+        .query()
+        .await
+        .unwrap()
+        .is_empty());
+}
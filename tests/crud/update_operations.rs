@@ -1,20 +1,26 @@
 ///! Integration tests for the CRUD operations available in `Canyon` that
 ///! generates and executes *UPDATE* statements
-use canyon_sql::crud::CrudOperations;
+use canyon_sql::{
+    crud::{CrudOperations, StaleEntityError, Tracked},
+    query::{operators::Comp, ops::QueryBuilder},
+};
 
 #[cfg(feature = "mssql")]
 use crate::constants::SQL_SERVER_DS;
+use crate::tests_models::announcement::*;
 use crate::tests_models::league::*;
+use crate::tests_models::roster::*;
+use crate::tests_models::standing::*;
 
 /// Update operation is a *CRUD* method defined for some entity `T`, that works by appliying
 /// some change to a Rust's entity instance, and persisting them into the database.
 ///
-/// The `t.update(&self)` operation is only enabled for types that
+/// The `t.update(&mut self)` operation is only enabled for types that
 /// has, at least, one of it's fields annotated with a `#[primary_key]`
 /// operation, because we use that concrete field to construct the clause that targets
 /// that entity.
 ///
-/// Attempt of usage the `t.update(&self)` method on an entity without `#[primary_key]`
+/// Attempt of usage the `t.update(&mut self)` method on an entity without `#[primary_key]`
 /// will raise a runtime error.
 #[cfg(feature = "postgres")]
 #[canyon_sql::macros::canyon_tokio_test]
@@ -96,3 +102,314 @@ fn test_crud_update_datasource_method_operation() {
         .await
         .expect("Failed to restablish the initial value update operation");
 }
+
+/// The `update` operation, and the `SET` clause of the `UpdateQueryBuilder`,
+/// sets the current UTC time on the column of the field annotated with `#[updated_at]`.
+/// The `update` operation also assigns it to the field of the instance
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_update_refreshes_the_updated_at_timestamp() {
+    let mut announcement = Announcement {
+        id: Default::default(),
+        title: "Some New Announcement".to_string(),
+        created_at: Default::default(),
+        updated_at: None,
+    };
+    announcement
+        .insert()
+        .await
+        .expect("Failed insert operation");
+    let inserted_at = announcement.updated_at.unwrap();
+
+    announcement.title = "Some Updated Announcement".to_string();
+    announcement
+        .update()
+        .await
+        .expect("Failed the update operation");
+    assert!(announcement.updated_at.unwrap() > inserted_at);
+
+    let updated_announcement = Announcement::find_by_pk(&announcement.id)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No entity found for the primary key value passed in");
+    assert_eq!(updated_announcement.title, "Some Updated Announcement");
+    assert_eq!(updated_announcement.created_at, announcement.created_at);
+    // The database stores the timestamps with a precision of microseconds
+    assert_eq!(
+        (updated_announcement.updated_at.unwrap() - announcement.updated_at.unwrap())
+            .num_milliseconds(),
+        0
+    );
+
+    let mut update_query = Announcement::update_query();
+    update_query
+        .set(&[(AnnouncementField::title, "Updated with the QueryBuilder")])
+        .r#where(AnnouncementFieldValue::id(&announcement.id), Comp::Eq);
+    assert_eq!(
+        update_query.read_sql(),
+        "UPDATE announcement SET title = $1, updated_at = (CURRENT_TIMESTAMP AT TIME ZONE 'UTC') WHERE id = $2"
+    );
+
+    Announcement::update_query()
+        .set(&[(AnnouncementField::title, "Updated with the QueryBuilder")])
+        .r#where(AnnouncementFieldValue::id(&announcement.id), Comp::Eq)
+        .query()
+        .await
+        .expect("Failed to update records with the querybuilder");
+
+    let updated_announcement = Announcement::find_by_pk(&announcement.id)
+        .await
+        .expect("Failed the query to the database")
+        .expect("No entity found for the primary key value passed in");
+    assert_eq!(updated_announcement.title, "Updated with the QueryBuilder");
+    assert!(updated_announcement.updated_at.unwrap() >= announcement.updated_at.unwrap());
+
+    announcement
+        .delete()
+        .await
+        .expect("Failed delete operation");
+}
+
+/// The entities with a field annotated with `#[version]` are only updated if the row
+/// wasn't modified since the instance was loaded. Otherwise, the update is rejected
+/// with a `StaleEntityError`
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_update_with_a_stale_version() {
+    let mut standing = Standing {
+        id: Default::default(),
+        team: "Some New Team".to_string(),
+        points: 0,
+        version: 0,
+    };
+    standing.insert().await.expect("Failed insert operation");
+
+    // Two copies of the same row, loaded before any of them is modified
+    let mut first_copy = Standing::find_by_pk(&standing.id).await.unwrap().unwrap();
+    let mut second_copy = first_copy.clone();
+
+    first_copy.points = 3;
+    first_copy
+        .update()
+        .await
+        .expect("Failed the update operation");
+
+    second_copy.points = 1;
+    let error = second_copy
+        .update()
+        .await
+        .expect_err("The update of a stale entity must fail");
+    let stale_error = error
+        .downcast_ref::<StaleEntityError>()
+        .expect("The error must be a StaleEntityError");
+    assert_eq!(stale_error.entity(), "Standing");
+    assert_eq!(stale_error.primary_key(), standing.id.to_string());
+
+    let updated_standing = Standing::find_by_pk(&standing.id).await.unwrap().unwrap();
+    assert_eq!(updated_standing.points, 3);
+    assert_eq!(updated_standing.version, 1);
+
+    updated_standing
+        .delete()
+        .await
+        .expect("Failed delete operation");
+}
+
+/// The version incremented by a successful update is written back into the instance,
+/// so the same instance can be updated again without reloading it
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_consecutive_updates_with_a_version() {
+    let mut standing = Standing {
+        id: Default::default(),
+        team: "Some New Team".to_string(),
+        points: 0,
+        version: 0,
+    };
+    standing.insert().await.expect("Failed insert operation");
+
+    standing.points = 3;
+    standing
+        .update()
+        .await
+        .expect("Failed the first update operation");
+    assert_eq!(standing.version, 1);
+
+    standing.points = 6;
+    standing
+        .update()
+        .await
+        .expect("Failed the second update operation");
+    assert_eq!(standing.version, 2);
+
+    let updated_standing = Standing::find_by_pk(&standing.id).await.unwrap().unwrap();
+    assert_eq!(updated_standing.points, 6);
+    assert_eq!(updated_standing.version, 2);
+
+    standing.delete().await.expect("Failed delete operation");
+}
+
+/// Same as the above test, but with the specified datasource
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_update_datasource_with_a_stale_version() {
+    let mut standing = Standing {
+        id: Default::default(),
+        team: "Some New Team".to_string(),
+        points: 0,
+        version: 0,
+    };
+    standing
+        .insert_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed insert datasource operation");
+
+    let mut first_copy = standing.clone();
+    first_copy.points = 3;
+    first_copy
+        .update_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed the update datasource operation");
+
+    let error = standing
+        .update_datasource(SQL_SERVER_DS)
+        .await
+        .expect_err("The update of a stale entity must fail");
+    assert!(error.downcast_ref::<StaleEntityError>().is_some());
+
+    let updated_standing = Standing::find_by_pk_datasource(&standing.id, SQL_SERVER_DS)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated_standing.points, 3);
+    assert_eq!(updated_standing.version, 1);
+
+    updated_standing
+        .delete_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed delete datasource operation");
+}
+
+/// A `Tracked` entity records the fields modified since it was loaded, so its `update`
+/// only sets their columns, and it's a no-op when nothing changed. The columns modified
+/// meanwhile by someone else keeps their values
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_tracked_update_operation() {
+    let mut roster = Roster {
+        id: Default::default(),
+        name: "Some New Roster".to_string(),
+        captain: "Faker".to_string(),
+    };
+    roster.insert().await.expect("Failed insert operation");
+
+    let mut tracked = Tracked::new(Roster::find_by_pk(&roster.id).await.unwrap().unwrap());
+    assert!(
+        Roster::patch(&roster.id, vec![RosterFieldValue::captain(&"Caps")])
+            .await
+            .expect("Failed the patch operation")
+    );
+
+    assert!(!tracked.is_changed());
+    tracked.update().await.expect("Failed the tracked update");
+    assert_eq!(
+        Roster::find_by_pk(&roster.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .captain,
+        "Caps"
+    );
+
+    tracked.name = "Some Tracked Roster".to_string();
+    assert_eq!(tracked.changed_fields(), vec!["name"]);
+    tracked.update().await.expect("Failed the tracked update");
+    assert!(!tracked.is_changed());
+
+    let updated_roster = Roster::find_by_pk(&roster.id).await.unwrap().unwrap();
+    assert_eq!(updated_roster.name, "Some Tracked Roster");
+    assert_eq!(updated_roster.captain, "Caps");
+
+    roster.delete().await.expect("Failed delete operation");
+}
+
+/// The `patch` operation updates just the given columns of the row with the given
+/// primary key, without loading it
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_patch_operation() {
+    let mut roster = Roster {
+        id: Default::default(),
+        name: "Some New Roster".to_string(),
+        captain: "Faker".to_string(),
+    };
+    roster.insert().await.expect("Failed insert operation");
+
+    let patched = Roster::patch(&roster.id, vec![RosterFieldValue::captain(&"Caps")])
+        .await
+        .expect("Failed the patch operation");
+    assert!(patched);
+
+    let patched_roster = Roster::find_by_pk(&roster.id).await.unwrap().unwrap();
+    assert_eq!(patched_roster.name, "Some New Roster");
+    assert_eq!(patched_roster.captain, "Caps");
+
+    // Nothing is updated without changes, or without a row with the primary key
+    let no_changes: Vec<RosterFieldValue> = vec![];
+    assert!(!Roster::patch(&roster.id, no_changes).await.unwrap());
+    assert!(
+        !Roster::patch(&-1, vec![RosterFieldValue::captain(&"Caps")])
+            .await
+            .unwrap()
+    );
+
+    roster.delete().await.expect("Failed delete operation");
+}
+
+/// Same as the above tests, but with the specified datasource
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_tracked_update_and_patch_datasource_operations() {
+    let mut roster = Roster {
+        id: Default::default(),
+        name: "Some New Roster".to_string(),
+        captain: "Faker".to_string(),
+    };
+    roster
+        .insert_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed insert datasource operation");
+
+    let mut tracked = Tracked::new(
+        Roster::find_by_pk_datasource(&roster.id, SQL_SERVER_DS)
+            .await
+            .unwrap()
+            .unwrap(),
+    );
+    let patched = Roster::patch_datasource(
+        &roster.id,
+        vec![RosterFieldValue::captain(&"Caps")],
+        SQL_SERVER_DS,
+    )
+    .await
+    .expect("Failed the patch datasource operation");
+    assert!(patched);
+
+    tracked.name = "Some Tracked Roster".to_string();
+    tracked
+        .update_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed the tracked update datasource");
+
+    let updated_roster = Roster::find_by_pk_datasource(&roster.id, SQL_SERVER_DS)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated_roster.name, "Some Tracked Roster");
+    assert_eq!(updated_roster.captain, "Caps");
+
+    roster
+        .delete_datasource(SQL_SERVER_DS)
+        .await
+        .expect("Failed delete datasource operation");
+}
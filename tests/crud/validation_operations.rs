@@ -0,0 +1,163 @@
+//! Integration tests for the validation of the rules declared with the
+//! `#[validate(...)]` field annotations, checked by the write operations
+//! available in `Canyon` before reaching the database
+use canyon_sql::{
+    crud::{CrudOperations, Validate, ValidationErrors},
+    query::{operators::Comp, ops::QueryBuilder},
+};
+
+#[cfg(feature = "mssql")]
+use crate::constants::SQL_SERVER_DS;
+use crate::tests_models::prediction::*;
+use crate::tests_models::subscriber::*;
+
+/// The `validate` method returns every rule not satisfied by the values of the entity,
+/// without querying the database
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_validate_method() {
+    let subscriber = Subscriber {
+        id: Default::default(),
+        email: "not an email".to_string(),
+        name: "A name that is too long for a subscriber".to_string(),
+        locked: false,
+    };
+    let errors = subscriber
+        .validate()
+        .expect_err("The subscriber is invalid");
+    assert_eq!(errors.errors().len(), 2);
+    assert_eq!(
+        errors
+            .field_errors("email")
+            .map(|error| error.rule)
+            .collect::<Vec<_>>(),
+        vec!["regex"]
+    );
+    assert_eq!(
+        errors
+            .field_errors("name")
+            .map(|error| error.rule)
+            .collect::<Vec<_>>(),
+        vec!["length"]
+    );
+
+    let reserved = Subscriber {
+        id: Default::default(),
+        email: "admin@canyon.com".to_string(),
+        name: "Admin".to_string(),
+        locked: false,
+    };
+    let errors = reserved.validate().expect_err("The name is reserved");
+    assert_eq!(errors.errors()[0].rule, "custom");
+    assert_eq!(errors.errors()[0].message, "is a reserved name");
+
+    let prediction = Prediction {
+        id: Default::default(),
+        winner: "T1".to_string(),
+        confidence: 101,
+    };
+    let errors = prediction
+        .validate()
+        .expect_err("The confidence is out of range");
+    assert_eq!(errors.errors()[0].field, "confidence");
+    assert_eq!(errors.errors()[0].rule, "range");
+
+    let prediction = Prediction {
+        confidence: 100,
+        ..prediction
+    };
+    assert!(prediction.validate().is_ok());
+}
+
+/// The `insert` and `multi_insert` operations are aborted when the entity is invalid,
+/// returning the `ValidationErrors`
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_validation() {
+    let mut subscriber = Subscriber {
+        id: Default::default(),
+        email: "invalid.email".to_string(),
+        name: "Invalid Email Subscriber".to_string(),
+        locked: false,
+    };
+    let error = subscriber
+        .insert()
+        .await
+        .expect_err("The insert of an invalid entity must fail");
+    let errors = error
+        .downcast_ref::<ValidationErrors>()
+        .expect("The error must be a ValidationErrors");
+    assert_eq!(errors.errors()[0].field, "email");
+    assert_eq!(subscriber.id, 0);
+
+    // The validation is done after the `before_insert` hook normalizes the email
+    let mut valid_subscriber = Subscriber {
+        id: Default::default(),
+        email: "  Valid.Email@Canyon.com ".to_string(),
+        name: "Valid Email Subscriber".to_string(),
+        locked: false,
+    };
+    let mut another_invalid_subscriber = subscriber.clone();
+    let error =
+        Subscriber::multi_insert(&mut [&mut valid_subscriber, &mut another_invalid_subscriber])
+            .await
+            .expect_err("The multi insert with an invalid entity must fail");
+    assert!(error.downcast_ref::<ValidationErrors>().is_some());
+    assert!(!Subscriber::select_query()
+        .r#where(
+            SubscriberFieldValue::name(&"Valid Email Subscriber"),
+            Comp::Eq
+        )
+        .exists()
+        .await
+        .unwrap());
+}
+
+/// The `update` operation is aborted when the entity is invalid, so the row
+/// keeps its previous values
+#[cfg(feature = "postgres")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_update_validation() {
+    let mut subscriber = Subscriber {
+        id: Default::default(),
+        email: "to.update@canyon.com".to_string(),
+        name: "Subscriber to update".to_string(),
+        locked: false,
+    };
+    subscriber.insert().await.expect("Failed insert operation");
+
+    subscriber.name = String::new();
+    let error = subscriber
+        .update()
+        .await
+        .expect_err("The update of an invalid entity must fail");
+    let errors = error.downcast_ref::<ValidationErrors>().unwrap();
+    assert_eq!(errors.errors()[0].field, "name");
+    assert_eq!(errors.errors()[0].rule, "length");
+
+    let stored_subscriber = Subscriber::find_by_pk(&subscriber.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored_subscriber.name, "Subscriber to update");
+
+    subscriber.delete().await.expect("Failed delete operation");
+}
+
+/// Same as the insert validation test above, but targeting the database
+/// defined in the specified datasource
+#[cfg(feature = "mssql")]
+#[canyon_sql::macros::canyon_tokio_test]
+fn test_crud_insert_datasource_validation() {
+    let mut prediction = Prediction {
+        id: Default::default(),
+        winner: "G2".to_string(),
+        confidence: -1,
+    };
+    let error = prediction
+        .insert_datasource(SQL_SERVER_DS)
+        .await
+        .expect_err("The insert of an invalid entity must fail");
+    assert!(error.downcast_ref::<ValidationErrors>().is_some());
+    assert_eq!(prediction.id, 0);
+}
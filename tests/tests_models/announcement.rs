@@ -0,0 +1,17 @@
+use canyon_sql::{date_time::NaiveDateTime, macros::*};
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity]
+/// Data model that represents a database entity for the Announcements of the leagues.
+///
+/// For test the behaviour of Canyon with the audit timestamps, we will use this class.
+/// Canyon fills automatically its `created_at` and `updated_at` fields.
+pub struct Announcement {
+    #[primary_key]
+    id: i32,
+    title: String,
+    #[created_at]
+    created_at: NaiveDateTime,
+    #[updated_at]
+    updated_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,17 @@
+use crate::tests_models::league::League;
+use canyon_sql::macros::*;
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity]
+/// Data model that represents a database entity for the media Assets of every League.
+///
+/// For test the behaviour of Canyon with the binary columns, mapped to the `Vec<u8>`
+/// type, we will use this class.
+pub struct Asset {
+    #[primary_key]
+    id: i32,
+    #[foreign_key(table = "league", column = "id")]
+    league: i32,
+    checksum: Vec<u8>,
+    thumbnail: Option<Vec<u8>>,
+}
@@ -0,0 +1,16 @@
+use crate::tests_models::caster::Caster;
+use canyon_sql::{macros::*, uuid::Uuid};
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity]
+/// Data model that represents a database entity for the Broadcasts of the casters.
+///
+/// For test the behaviour of Canyon with the `Uuid` primary keys generated by the
+/// database, we will use this class. The value is read back after the insert.
+pub struct Broadcast {
+    #[primary_key(generated = "db")]
+    id: Uuid,
+    #[foreign_key(table = "caster", column = "id")]
+    caster: Uuid,
+    channel: String,
+}
@@ -0,0 +1,15 @@
+use canyon_sql::{macros::*, uuid::Uuid};
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity]
+/// Data model that represents a database entity for the Casters of the matches.
+///
+/// For test the behaviour of Canyon with the primary keys created on the client
+/// side, we will use this class. The `Uuid` of every caster is generated by Canyon
+/// before the insert, ordered by its creation time.
+pub struct Caster {
+    #[primary_key(generated = "uuid_v7")]
+    id: Uuid,
+    nickname: String,
+    region: String,
+}
@@ -0,0 +1,27 @@
+use crate::tests_models::league::League;
+use canyon_sql::macros::*;
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity]
+/// Data model that represents a database entity for the Coaches of the leagues.
+///
+/// For test the behaviour of Canyon with the legacy tables whose columns doesn't
+/// follow the naming of the fields, we will use this class. Its columns are mapped
+/// with `#[column(name = "...")]`, one of them being a reserved word, and it has
+/// fields that aren't persisted, filled with their default value when loaded.
+pub struct Coach {
+    #[primary_key]
+    #[column(name = "CoachID")]
+    id: i32,
+    #[column(name = "FullName")]
+    name: String,
+    #[foreign_key(table = "league", column = "id")]
+    #[column(name = "LeagueID")]
+    league: i32,
+    #[column(name = "order")]
+    position: i32,
+    #[transient]
+    display_name: String,
+    #[canyon(skip)]
+    loaded: bool,
+}
@@ -1,8 +1,12 @@
+use crate::tests_models::{coach::*, region::*, tournament::*};
 use canyon_sql::macros::*;
 
-#[derive(Debug, Fields, CanyonCrud, CanyonMapper, ForeignKeyable, Eq, PartialEq)]
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, ForeignKeyable, Eq, PartialEq)]
 // #[canyon_entity(table_name = "league", schema = "public")]
 #[canyon_entity(table_name = "league")]
+#[has_many(Tournament)]
+#[has_many(Coach, name = "coaches")]
+#[many_to_many(Region, through = "league_region")]
 pub struct League {
     #[primary_key]
     id: i32,
@@ -1,3 +1,24 @@
+pub mod announcement;
+pub mod asset;
+pub mod broadcast;
+pub mod caster;
+pub mod coach;
 pub mod league;
+#[cfg(feature = "postgres")]
+pub mod patch;
 pub mod player;
+pub mod prediction;
+#[cfg(feature = "rust_decimal")]
+pub mod prize;
+pub mod region;
+pub mod replay;
+pub mod roster;
+pub mod series;
+pub mod sponsor;
+pub mod standing;
+pub mod subscriber;
+pub mod ticket;
 pub mod tournament;
+pub mod tournament_caster;
+pub mod tournament_sponsor;
+pub mod venue;
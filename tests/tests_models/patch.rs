@@ -0,0 +1,15 @@
+use canyon_sql::macros::*;
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity]
+/// Data model that represents a database entity for the game Patches.
+///
+/// For test the behaviour of Canyon with the PostgreSQL array columns, mapped to
+/// the `Vec<T>` type, we will use this class.
+pub struct Patch {
+    #[primary_key]
+    id: i32,
+    version: String,
+    champions: Vec<String>,
+    item_ids: Option<Vec<i32>>,
+}
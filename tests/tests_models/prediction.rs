@@ -0,0 +1,16 @@
+use canyon_sql::macros::*;
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity]
+/// Data model that represents a database entity for the Predictions of the winners
+/// of the matches.
+///
+/// For test the behaviour of Canyon with the validation rules of the numeric fields,
+/// we will use this class. Its `confidence` must be a percentage.
+pub struct Prediction {
+    #[primary_key]
+    id: i32,
+    winner: String,
+    #[validate(range(min = 0, max = 100))]
+    confidence: i32,
+}
@@ -0,0 +1,19 @@
+use crate::tests_models::tournament::Tournament;
+use canyon_sql::{decimal::Decimal, macros::*};
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity]
+/// Data model that represents a database entity for the Prizes awarded on every Tournament.
+///
+/// For test the behaviour of Canyon with the `NUMERIC` columns, mapped to the `Decimal`
+/// type of the `rust_decimal` feature, we will use this class.
+pub struct Prize {
+    #[primary_key]
+    id: i32,
+    #[foreign_key(table = "tournament", column = "id")]
+    tournament: i32,
+    #[column(precision = 18, scale = 4)]
+    amount: Decimal,
+    #[column(precision = 5, scale = 2)]
+    tax_rate: Option<Decimal>,
+}
@@ -0,0 +1,13 @@
+use canyon_sql::macros::*;
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity]
+/// Data model that represents a database entity for the Regions where the leagues are played.
+///
+/// For test the behaviour of Canyon with the text primary keys, we will use this class.
+/// The code of every region is always provided by the entity.
+pub struct Region {
+    #[primary_key(autoincremental = false)]
+    pub code: String,
+    pub name: String,
+}
@@ -0,0 +1,20 @@
+use crate::tests_models::tournament::Tournament;
+use canyon_sql::{json::Value, macros::*};
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity]
+/// Data model that represents a database entity for the Replays of the games played
+/// on every Tournament.
+///
+/// For test the behaviour of Canyon with the fields stored as JSON documents, declared
+/// with the `#[json]` annotation, we will use this class.
+pub struct Replay {
+    #[primary_key]
+    id: i32,
+    #[foreign_key(table = "tournament", column = "id")]
+    tournament: i32,
+    #[json]
+    summary: Value,
+    #[json]
+    highlights: Option<Value>,
+}
@@ -0,0 +1,15 @@
+use canyon_sql::macros::*;
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity]
+#[tracked]
+/// Data model that represents a database entity for the Rosters of the teams.
+///
+/// For test the behaviour of Canyon with the tracked entities, we will use this
+/// class. Its updates just sets the columns of its modified fields.
+pub struct Roster {
+    #[primary_key]
+    id: i32,
+    name: String,
+    captain: String,
+}
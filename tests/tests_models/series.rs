@@ -0,0 +1,47 @@
+use crate::tests_models::tournament::Tournament;
+use canyon_sql::macros::*;
+
+/// The state of a [`Series`], stored as the `snake_case` name of the variant
+#[derive(Debug, Clone, Copy, CanyonEnum, Eq, PartialEq)]
+#[canyon_enum(rename_all = "snake_case")]
+pub enum SeriesStatus {
+    Scheduled,
+    InProgress,
+    Finished,
+    #[canyon_enum(rename = "cancelled")]
+    Canceled,
+}
+
+/// The number of games of a [`Series`], stored as the discriminant of the variant
+#[derive(Debug, Clone, Copy, CanyonEnum, Eq, PartialEq)]
+#[canyon_enum(integer)]
+pub enum SeriesFormat {
+    Bo1 = 1,
+    Bo3 = 3,
+    Bo5 = 5,
+}
+
+/// The side of the map of the first team of a [`Series`], stored as a native enum
+/// type on PostgreSQL
+#[derive(Debug, Clone, Copy, CanyonEnum, Eq, PartialEq)]
+#[canyon_enum(pg_enum, rename_all = "lowercase")]
+pub enum MapSide {
+    Blue,
+    Red,
+}
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity]
+/// Data model that represents a database entity for the Series played on every Tournament.
+///
+/// For test the behaviour of Canyon with the fields whose types are Rust enums, that
+/// derives `CanyonEnum`, we will use this class.
+pub struct Series {
+    #[primary_key]
+    id: i32,
+    #[foreign_key(table = "tournament", column = "id")]
+    tournament: i32,
+    status: SeriesStatus,
+    format: SeriesFormat,
+    side: Option<MapSide>,
+}
@@ -0,0 +1,17 @@
+use canyon_sql::{date_time::NaiveDateTime, macros::*};
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity]
+/// Data model that represents a database entity for the Sponsors of the leagues.
+///
+/// For test the behaviour of Canyon with the entities that are soft deleted, we
+/// will use this class. Its rows aren't removed from the table when they are deleted,
+/// they are just marked as deleted with the timestamp stored in `deleted_at`.
+pub struct Sponsor {
+    #[primary_key]
+    id: i32,
+    name: String,
+    league: i32,
+    #[soft_delete]
+    deleted_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,16 @@
+use canyon_sql::macros::*;
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity]
+/// Data model that represents a database entity for the Standings of the teams.
+///
+/// For test the behaviour of Canyon with the optimistic locking, we will use this
+/// class. Its `version` is used for detect concurrent modifications of the same row.
+pub struct Standing {
+    #[primary_key]
+    id: i32,
+    team: String,
+    points: i32,
+    #[version]
+    version: i32,
+}
@@ -0,0 +1,64 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use canyon_sql::{crud::EntityHooks, macros::*};
+
+/// Counts the subscribers removed through the `delete` operation, incremented
+/// by the `after_delete` hook
+pub static DELETED_SUBSCRIBERS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[entity_hooks]
+#[canyon_entity]
+/// Data model that represents a database entity for the Subscribers of the newsletter.
+///
+/// For test the behaviour of Canyon with the entities that implements their own
+/// lifecycle hooks, we will use this class. The email is normalized before being
+/// inserted, the name is trimmed when it's loaded and the locked subscribers can't
+/// be deleted.
+///
+/// Also, its fields declares validation rules, checked after the `before_insert` hook.
+pub struct Subscriber {
+    #[primary_key]
+    id: i32,
+    #[validate(length(max = 64), regex = "^[^@\\s]+@[^@\\s]+$")]
+    email: String,
+    #[validate(length(min = 1, max = 32), custom = "not_reserved_name")]
+    name: String,
+    locked: bool,
+}
+
+/// Custom validation rule for the name of the subscribers
+fn not_reserved_name(name: &str) -> Result<(), String> {
+    if name.trim().eq_ignore_ascii_case("admin") {
+        return Err("is a reserved name".to_string());
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl EntityHooks for Subscriber {
+    async fn before_insert(&mut self) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        self.email = self.email.trim().to_lowercase();
+        if self.email.is_empty() {
+            return Err("The email of a subscriber can't be empty".into());
+        }
+        Ok(())
+    }
+
+    async fn before_delete(&self) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        if self.locked {
+            return Err("A locked subscriber can't be deleted".into());
+        }
+        Ok(())
+    }
+
+    async fn after_delete(&self) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        DELETED_SUBSCRIBERS.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn after_load(&mut self) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
+        self.name = self.name.trim().to_string();
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use std::error::Error;
+
+use crate::tests_models::tournament::Tournament;
+use canyon_sql::{crud::CanyonType, macros::*};
+
+/// The email of the holder of a [`Ticket`], checked when it's read from the database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Email(pub String);
+
+#[canyon_type]
+impl CanyonType for Email {
+    const POSTGRES_TYPE: &'static str = "text";
+    const SQLSERVER_TYPE: &'static str = "nvarchar(320)";
+
+    type Value = String;
+
+    fn to_parameter(&self) -> &dyn canyon_sql::crud::bounds::QueryParameter<'_> {
+        &self.0
+    }
+
+    fn from_value(value: String) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        if value.contains('@') {
+            Ok(Self(value))
+        } else {
+            Err(format!("`{value}` isn't a valid email").into())
+        }
+    }
+}
+
+/// An amount of money of a [`Ticket`], in cents
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cents(pub i64);
+
+#[canyon_type]
+impl CanyonType for Cents {
+    const POSTGRES_TYPE: &'static str = "bigint";
+    const SQLSERVER_TYPE: &'static str = "bigint";
+
+    type Value = i64;
+
+    fn to_parameter(&self) -> &dyn canyon_sql::crud::bounds::QueryParameter<'_> {
+        &self.0
+    }
+
+    fn from_value(value: i64) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        Ok(Self(value))
+    }
+}
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity]
+/// Data model that represents a database entity for the Tickets sold for every Tournament.
+///
+/// For test the behaviour of Canyon with the fields whose types are user-defined
+/// types, that implements `CanyonType`, we will use this class.
+pub struct Ticket {
+    #[primary_key]
+    id: i32,
+    #[foreign_key(table = "tournament", column = "id")]
+    tournament: i32,
+    holder: Email,
+    price: Cents,
+    discount: Option<Cents>,
+}
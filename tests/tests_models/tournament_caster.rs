@@ -0,0 +1,14 @@
+use canyon_sql::{macros::*, uuid::Uuid};
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity]
+/// Data model that represents a database entity for the Casters of every Tournament.
+///
+/// For test the behaviour of Canyon with the entities whose columns are all part of
+/// their composite primary key (like the join tables), we will use this class.
+pub struct TournamentCaster {
+    #[primary_key(autoincremental = false)]
+    tournament: i32,
+    #[primary_key(autoincremental = false)]
+    caster: Uuid,
+}
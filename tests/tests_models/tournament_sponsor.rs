@@ -0,0 +1,16 @@
+use canyon_sql::macros::*;
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity]
+/// Data model that represents a database entity for the Sponsors of every Tournament.
+///
+/// For test the behaviour of Canyon with the entities that declares a composite
+/// primary key, we will use this class. Every row is identified by the pair formed
+/// by the tournament and the sponsor, whose values are always provided by the entity.
+pub struct TournamentSponsor {
+    #[primary_key(autoincremental = false)]
+    tournament: i32,
+    #[primary_key(autoincremental = false)]
+    sponsor: i32,
+    amount: i64,
+}
@@ -0,0 +1,30 @@
+use canyon_sql::macros::*;
+
+#[derive(Debug, Clone, Fields, CanyonCrud, CanyonMapper, Eq, PartialEq)]
+#[canyon_entity]
+#[tracked]
+/// Data model that represents a database entity for the Venues where the
+/// tournaments are played.
+///
+/// For test the behaviour of Canyon with the value objects embedded in the
+/// entities, we will use this class. The fields of its addresses are stored
+/// in the columns of the table, prefixed with `address_` by default and with
+/// the declared `billing_` prefix.
+pub struct Venue {
+    #[primary_key]
+    id: i32,
+    name: String,
+    #[embedded]
+    address: Address,
+    #[embedded(prefix = "billing_")]
+    billing: Address,
+}
+
+/// The address of a venue, stored in the columns of the entities that embeds it
+#[derive(Debug, Clone, Eq, PartialEq, CanyonEmbeddable)]
+pub struct Address {
+    pub street: String,
+    pub city: String,
+    #[column(name = "zip")]
+    pub postal_code: Option<String>,
+}